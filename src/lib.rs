@@ -512,6 +512,7 @@ impl ApplicationHandler for App {
             }
             WindowEvent::CloseRequested => {
                 log::info!("Close requested. Exiting...");
+                renderer.save_pipeline_cache();
                 event_loop.exit();
             }
             WindowEvent::RedrawRequested => {
@@ -915,15 +916,10 @@ impl ApplicationHandler for App {
 }
 
 use pass_configs::PassConfigs;
-use passes::{
-    BlitPass, BlitPassData, BrightnessContrastPass, BrightnessContrastPassData, ColorInvertPass,
-    ColorInvertPassData, ComputeGrayscalePass, ComputeGrayscalePassData, ConvolutionPass,
-    ConvolutionPassData, EdgeDetectionPass, EdgeDetectionPassData, EguiPass,
-    GaussianBlurHorizontalPass, GaussianBlurPassData, GaussianBlurVerticalPass, GrayscalePass,
-    GrayscalePassData, PostProcessPass, PostProcessPassData, ScenePass, ScenePassData, SharpenPass,
-    SharpenPassData, VignettePass, VignettePassData,
-};
-use wgpu_render_graph::{RenderGraph, ResourceId};
+use passes::ScenePassData;
+use wgpu_render_graph::{GraphDescription, RenderGraph, ResourceId, ResourceKind, ShaderLibrary};
+
+const RENDER_GRAPH_DESCRIPTION: &str = include_str!("render_graph.json");
 
 pub struct ViewportRenderTarget {
     pub tile_id: egui_tiles::TileId,
@@ -945,25 +941,13 @@ pub struct Renderer {
     scene: Scene,
     render_graph: RenderGraph<PassConfigs>,
     pass_configs: PassConfigs,
+    shaders: ShaderLibrary,
     surface_resource_id: ResourceId,
     depth_resource_id: ResourceId,
-    hdr_resource_id: ResourceId,
-    output_resource_id: ResourceId,
-    output_with_edges_resource_id: ResourceId,
-    output_with_brightness_contrast_resource_id: ResourceId,
-    blur_horizontal_resource_id: ResourceId,
-    blur_vertical_resource_id: ResourceId,
-    convolution_resource_id: ResourceId,
-    vignette_resource_id: ResourceId,
-    grayscale_resource_id: ResourceId,
-    color_invert_resource_id: ResourceId,
     viewport_display_resource_id: ResourceId,
     viewport_display_texture: wgpu::Texture,
     viewport_display_view: wgpu::TextureView,
-    sharpen_resource_id: ResourceId,
-    egui_output_resource_id: ResourceId,
     viewport_texture_id: Option<egui::TextureId>,
-    _sharpen_uniform_buffer: Arc<wgpu::Buffer>,
     viewport_targets: HashMap<egui_tiles::TileId, ViewportRenderTarget>,
     camera_render_targets: HashMap<usize, (wgpu::Texture, wgpu::TextureView)>,
     camera_depth_targets: HashMap<usize, (wgpu::Texture, wgpu::TextureView)>,
@@ -986,308 +970,59 @@ impl Renderer {
 
         let scene = Scene::new(&gpu.device, wgpu::TextureFormat::Rgba16Float, &gpu.queue);
 
-        let (post_process_pipeline, post_process_bind_group_layout) =
-            PostProcessPass::create_pipeline(&gpu.device, gpu.surface_format);
-
-        let post_process_sampler = Arc::new(gpu.device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Post Process Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        }));
-
-        let post_process_data = PostProcessPassData {
-            pipeline: post_process_pipeline,
-            bind_group_layout: post_process_bind_group_layout,
-            sampler: post_process_sampler,
-        };
-
-        let (edge_detection_pipeline, edge_detection_bind_group_layout) =
-            EdgeDetectionPass::create_pipeline(&gpu.device, gpu.surface_format);
-
-        let edge_detection_sampler =
-            Arc::new(gpu.device.create_sampler(&wgpu::SamplerDescriptor {
-                label: Some("Edge Detection Sampler"),
-                address_mode_u: wgpu::AddressMode::ClampToEdge,
-                address_mode_v: wgpu::AddressMode::ClampToEdge,
-                address_mode_w: wgpu::AddressMode::ClampToEdge,
-                mag_filter: wgpu::FilterMode::Linear,
-                min_filter: wgpu::FilterMode::Linear,
-                mipmap_filter: wgpu::FilterMode::Nearest,
-                ..Default::default()
-            }));
-
-        let (blit_pipeline, blit_bind_group_layout) =
-            BlitPass::create_pipeline(&gpu.device, gpu.surface_format);
-
-        let edge_detection_data = EdgeDetectionPassData {
-            pipeline: edge_detection_pipeline,
-            blit_pipeline: Arc::clone(&blit_pipeline),
-            bind_group_layout: edge_detection_bind_group_layout,
-            sampler: edge_detection_sampler,
-        };
-
-        let blit_sampler = Arc::new(gpu.device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Blit Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        }));
-
-        let (brightness_contrast_pipeline, brightness_contrast_bind_group_layout) =
-            BrightnessContrastPass::create_pipeline(&gpu.device, gpu.surface_format);
-
-        let brightness_contrast_uniform_buffer =
-            Arc::new(gpu.device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Brightness/Contrast Uniform Buffer"),
-                size: std::mem::size_of::<[f32; 2]>() as u64,
-                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            }));
-
-        let brightness_contrast_sampler =
-            Arc::new(gpu.device.create_sampler(&wgpu::SamplerDescriptor {
-                label: Some("Brightness/Contrast Sampler"),
-                address_mode_u: wgpu::AddressMode::ClampToEdge,
-                address_mode_v: wgpu::AddressMode::ClampToEdge,
-                address_mode_w: wgpu::AddressMode::ClampToEdge,
-                mag_filter: wgpu::FilterMode::Linear,
-                min_filter: wgpu::FilterMode::Linear,
-                mipmap_filter: wgpu::FilterMode::Nearest,
-                ..Default::default()
-            }));
-
-        let brightness_contrast_data = BrightnessContrastPassData {
-            pipeline: brightness_contrast_pipeline,
-            blit_pipeline: Arc::clone(&blit_pipeline),
-            bind_group_layout: brightness_contrast_bind_group_layout,
-            sampler: brightness_contrast_sampler,
-        };
-
-        let (gaussian_blur_pipeline, gaussian_blur_bind_group_layout) =
-            GaussianBlurHorizontalPass::create_pipeline(&gpu.device, gpu.surface_format);
-
-        let gaussian_blur_horizontal_uniform_buffer =
-            Arc::new(gpu.device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Gaussian Blur Horizontal Uniform Buffer"),
-                size: std::mem::size_of::<[f32; 2]>() as u64,
-                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            }));
-
-        let gaussian_blur_vertical_uniform_buffer =
-            Arc::new(gpu.device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Gaussian Blur Vertical Uniform Buffer"),
-                size: std::mem::size_of::<[f32; 2]>() as u64,
-                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            }));
-
-        let gaussian_blur_sampler = Arc::new(gpu.device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Gaussian Blur Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        }));
-
-        let gaussian_blur_data = GaussianBlurPassData {
-            pipeline: gaussian_blur_pipeline,
-            blit_pipeline: Arc::clone(&blit_pipeline),
-            bind_group_layout: gaussian_blur_bind_group_layout,
-            sampler: gaussian_blur_sampler,
-        };
-
-        let (sharpen_pipeline, sharpen_bind_group_layout) =
-            SharpenPass::create_pipeline(&gpu.device, gpu.surface_format);
-
-        let sharpen_sampler = Arc::new(gpu.device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Sharpen Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        }));
-
-        let sharpen_uniform_buffer = Arc::new(gpu.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Sharpen Uniform Buffer"),
-            size: std::mem::size_of::<[f32; 4]>() as u64,
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        }));
-
-        let sharpen_data = SharpenPassData {
-            pipeline: sharpen_pipeline,
-            blit_pipeline: Arc::clone(&blit_pipeline),
-            bind_group_layout: sharpen_bind_group_layout,
-            sampler: sharpen_sampler,
-        };
-
-        let (convolution_pipeline, convolution_bind_group_layout) =
-            ConvolutionPass::create_pipeline(&gpu.device, gpu.surface_format);
-
-        let convolution_kernel_buffer =
-            Arc::new(gpu.device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Convolution Kernel Buffer"),
-                size: std::mem::size_of::<[[f32; 4]; 3]>() as u64,
-                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            }));
-
-        let convolution_sampler = Arc::new(gpu.device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Convolution Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        }));
-
-        let convolution_data = ConvolutionPassData {
-            pipeline: convolution_pipeline,
-            blit_pipeline: Arc::clone(&blit_pipeline),
-            bind_group_layout: convolution_bind_group_layout,
-            sampler: convolution_sampler,
-        };
-
-        let (vignette_pipeline, vignette_bind_group_layout) =
-            VignettePass::create_pipeline(&gpu.device, gpu.surface_format);
-
-        let vignette_uniform_buffer = Arc::new(gpu.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Vignette Uniform Buffer"),
-            size: std::mem::size_of::<[f32; 8]>() as u64,
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        }));
-
-        let vignette_sampler = Arc::new(gpu.device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Vignette Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        }));
-
-        let vignette_data = VignettePassData {
-            pipeline: vignette_pipeline,
-            blit_pipeline: Arc::clone(&blit_pipeline),
-            bind_group_layout: vignette_bind_group_layout,
-            sampler: vignette_sampler,
-        };
-
-        let (grayscale_pipeline, grayscale_bind_group_layout) =
-            GrayscalePass::create_pipeline(&gpu.device, gpu.surface_format);
-
-        let grayscale_sampler = Arc::new(gpu.device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Grayscale Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        }));
-
-        let grayscale_data = GrayscalePassData {
-            pipeline: grayscale_pipeline,
-            blit_pipeline: Arc::clone(&blit_pipeline),
-            bind_group_layout: grayscale_bind_group_layout,
-            sampler: grayscale_sampler,
-        };
-
-        let (color_invert_pipeline, color_invert_bind_group_layout) =
-            ColorInvertPass::create_pipeline(&gpu.device, gpu.surface_format);
-
-        let color_invert_sampler = Arc::new(gpu.device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Color Invert Sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        }));
-
-        let color_invert_data = ColorInvertPassData {
-            pipeline: color_invert_pipeline,
-            blit_pipeline: Arc::clone(&blit_pipeline),
-            bind_group_layout: color_invert_bind_group_layout,
-            sampler: color_invert_sampler,
-        };
-
-        let blit_data = BlitPassData {
-            pipeline: blit_pipeline,
-            bind_group_layout: blit_bind_group_layout,
-            sampler: blit_sampler,
-        };
-
-        let (compute_grayscale_pipeline, compute_grayscale_bind_group_layout) =
-            ComputeGrayscalePass::create_pipeline(&gpu.device);
-
-        let compute_grayscale_uniform_buffer =
-            Arc::new(gpu.device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Compute Grayscale Uniform Buffer"),
-                size: std::mem::size_of::<[u32; 4]>() as u64,
-                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            }));
-
-        let compute_grayscale_data = ComputeGrayscalePassData {
-            pipeline: compute_grayscale_pipeline,
-            bind_group_layout: compute_grayscale_bind_group_layout,
-        };
+        let mut shaders = ShaderLibrary::new();
+        if let Some(root) = passes::shader_root() {
+            shaders = shaders.with_root(root);
+        }
+        passes::register_shaders(&mut shaders);
+        for error in shaders.errors() {
+            log::error!("{}", error);
+        }
 
         let mut graph = RenderGraph::new();
+        match gpu
+            .pipeline_cache_path
+            .as_ref()
+            .and_then(|path| std::fs::read(path).ok())
+        {
+            // SAFETY: the cache file name is keyed by `pipeline_cache_key` for this adapter,
+            // and its contents were written by `save_pipeline_cache`.
+            Some(data) => unsafe {
+                graph
+                    .pipelines_mut()
+                    .enable_cache_with_data(&gpu.device, &data)
+            },
+            None => graph.pipelines_mut().enable_cache(&gpu.device),
+        };
 
-        let standard_template = wgpu_render_graph::ResourceTemplate::new(
+        let registry = passes::pass_registry(
+            &gpu.device,
+            graph.pipelines_mut(),
             gpu.surface_format,
-            gpu.surface_config.width,
-            gpu.surface_config.height,
+            &shaders,
+            ScenePassData {
+                pipeline: Arc::clone(&scene.pipeline),
+                vertex_buffer: Arc::clone(&scene.vertex_buffer),
+                index_buffer: Arc::clone(&scene.index_buffer),
+                index_count: INDICES.len() as u32,
+                uniform_bind_group: Arc::clone(&scene.uniform.bind_group),
+                texture_bind_group: Arc::clone(&scene.texture_bind_group),
+            },
         );
 
-        let mut pool = graph.resource_pool(&standard_template);
-        let [
-            output_resource_id,
-            output_with_edges_resource_id,
-            output_with_brightness_contrast_resource_id,
-            blur_horizontal_resource_id,
-            blur_vertical_resource_id,
-            convolution_resource_id,
-            vignette_resource_id,
-            grayscale_resource_id,
-            color_invert_resource_id,
-        ]: [wgpu_render_graph::ResourceId; 9] = pool.transient_many(&[
-            "output",
-            "output_with_edges",
-            "output_with_brightness_contrast",
-            "blur_horizontal",
-            "blur_vertical",
-            "convolution",
-            "vignette",
-            "grayscale",
-            "color_invert",
-        ]).try_into().unwrap();
+        let mut description = GraphDescription::from_json(RENDER_GRAPH_DESCRIPTION)
+            .expect("Failed to parse render graph description");
+        for resource in &mut description.resources {
+            if resource.kind == ResourceKind::Color && resource.format.is_none() {
+                resource.format = Some(gpu.surface_format);
+            }
+        }
+        let resources = graph
+            .load_description(&description, &registry)
+            .expect("Failed to load render graph description");
+        let surface_resource_id = resources["surface"];
+        let depth_resource_id = resources["depth"];
+        let viewport_display_resource_id = resources["viewport_display"];
 
         let viewport_display_texture = gpu.device.create_texture(&wgpu::TextureDescriptor {
             label: Some("Viewport Display Texture"),
@@ -1329,268 +1064,9 @@ impl Renderer {
             },
         );
 
-        let viewport_display_resource_id = graph
-            .add_color_texture("viewport_display")
-            .format(gpu.surface_format)
-            .size(gpu.surface_config.width, gpu.surface_config.height)
-            .usage(wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING)
-            .sample_count(1)
-            .mip_levels(1)
-            .external();
-
-        let sharpen_resource_id = graph
-            .add_color_texture("sharpen")
-            .format(gpu.surface_format)
-            .size(gpu.surface_config.width, gpu.surface_config.height)
-            .usage(wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING)
-            .sample_count(1)
-            .mip_levels(1)
-            .transient();
-
-        let egui_output_resource_id = graph
-            .add_color_texture("egui_output")
-            .format(gpu.surface_format)
-            .size(gpu.surface_config.width, gpu.surface_config.height)
-            .usage(wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING)
-            .sample_count(1)
-            .mip_levels(1)
-            .clear_color(wgpu::Color {
-                r: 0.3,
-                g: 0.3,
-                b: 0.3,
-                a: 1.0,
-            })
-            .transient();
-
-        let surface_resource_id = graph
-            .add_color_texture("surface")
-            .format(gpu.surface_format)
-            .size(gpu.surface_config.width, gpu.surface_config.height)
-            .usage(wgpu::TextureUsages::RENDER_ATTACHMENT)
-            .sample_count(1)
-            .mip_levels(1)
-            .external();
-
-        let hdr_template = wgpu_render_graph::ResourceTemplate::new(
-            wgpu::TextureFormat::Rgba16Float,
-            gpu.surface_config.width,
-            gpu.surface_config.height,
-        );
-        let hdr_resource_id = graph.transient_color_from_template_with_clear(
-            "hdr_buffer",
-            &hdr_template,
-            wgpu::Color { r: 0.5, g: 0.5, b: 0.5, a: 1.0 },
-        );
-
-        let depth_resource_id = graph
-            .add_depth_texture("depth")
-            .size(gpu.surface_config.width, gpu.surface_config.height)
-            .clear_depth(1.0)
-            .external();
-
-        let compute_template = wgpu_render_graph::ResourceTemplate::new(
-            wgpu::TextureFormat::Rgba8Unorm,
-            gpu.surface_config.width,
-            gpu.surface_config.height,
-        ).usage(wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING);
-        let compute_grayscale_resource_id = graph.transient_color_from_template("compute_grayscale", &compute_template);
-
-        graph.pass(Box::new(ScenePass::new(ScenePassData {
-            pipeline: Arc::clone(&scene.pipeline),
-            vertex_buffer: Arc::clone(&scene.vertex_buffer),
-            index_buffer: Arc::clone(&scene.index_buffer),
-            index_count: INDICES.len() as u32,
-            uniform_bind_group: Arc::clone(&scene.uniform.bind_group),
-            texture_bind_group: Arc::clone(&scene.texture_bind_group),
-        })))
-            .write("color_output", hdr_resource_id)
-            .write("depth_output", depth_resource_id);
-
-        graph.pass(Box::new(PostProcessPass::new(wgpu_render_graph::arc_data!(PostProcessPassData {
-            pipeline: post_process_data.pipeline,
-            bind_group_layout: post_process_data.bind_group_layout,
-            sampler: post_process_data.sampler,
-        }))))
-            .read("hdr_input", hdr_resource_id)
-            .write("color_output", output_resource_id);
-
-        graph.pass(Box::new(ComputeGrayscalePass::new(
-            compute_grayscale_data,
-            compute_grayscale_uniform_buffer,
-        )))
-            .slot("input", output_resource_id)
-            .slot("output", compute_grayscale_resource_id);
-
-        graph.add_pass_with_slots(
-            Box::new(EdgeDetectionPass::new(EdgeDetectionPassData {
-                pipeline: Arc::clone(&edge_detection_data.pipeline),
-                blit_pipeline: Arc::clone(&edge_detection_data.blit_pipeline),
-                bind_group_layout: Arc::clone(&edge_detection_data.bind_group_layout),
-                sampler: Arc::clone(&edge_detection_data.sampler),
-            })),
-            wgpu_render_graph::pass_slots! {
-                input: compute_grayscale_resource_id,
-                output: output_with_edges_resource_id,
-            },
-        ).expect("Failed to add edge detection pass");
-
-        graph.add_pass_with_slots(
-            Box::new(BrightnessContrastPass::new(
-                BrightnessContrastPassData {
-                    pipeline: Arc::clone(&brightness_contrast_data.pipeline),
-                    blit_pipeline: Arc::clone(&brightness_contrast_data.blit_pipeline),
-                    bind_group_layout: Arc::clone(&brightness_contrast_data.bind_group_layout),
-                    sampler: Arc::clone(&brightness_contrast_data.sampler),
-                },
-                brightness_contrast_uniform_buffer,
-            )),
-            wgpu_render_graph::pass_slots! {
-                input: output_with_edges_resource_id,
-                output: output_with_brightness_contrast_resource_id,
-            },
-        ).expect("Failed to add brightness contrast pass");
-
-        graph.add_pass_with_slots(
-            Box::new(GaussianBlurHorizontalPass::new(
-                GaussianBlurPassData {
-                    pipeline: Arc::clone(&gaussian_blur_data.pipeline),
-                    blit_pipeline: Arc::clone(&gaussian_blur_data.blit_pipeline),
-                    bind_group_layout: Arc::clone(&gaussian_blur_data.bind_group_layout),
-                    sampler: Arc::clone(&gaussian_blur_data.sampler),
-                },
-                gaussian_blur_horizontal_uniform_buffer,
-            )),
-            wgpu_render_graph::pass_slots! {
-                input: output_with_brightness_contrast_resource_id,
-                output: blur_horizontal_resource_id,
-            },
-        ).expect("Failed to add gaussian blur horizontal pass");
-
-        graph.add_pass_with_slots(
-            Box::new(GaussianBlurVerticalPass::new(
-                GaussianBlurPassData {
-                    pipeline: Arc::clone(&gaussian_blur_data.pipeline),
-                    blit_pipeline: Arc::clone(&gaussian_blur_data.blit_pipeline),
-                    bind_group_layout: Arc::clone(&gaussian_blur_data.bind_group_layout),
-                    sampler: Arc::clone(&gaussian_blur_data.sampler),
-                },
-                gaussian_blur_vertical_uniform_buffer,
-            )),
-            wgpu_render_graph::pass_slots! {
-                input: blur_horizontal_resource_id,
-                output: blur_vertical_resource_id,
-            },
-        ).expect("Failed to add gaussian blur vertical pass");
-
-        graph.add_pass_with_slots(
-            Box::new(SharpenPass::new(
-                SharpenPassData {
-                    pipeline: Arc::clone(&sharpen_data.pipeline),
-                    blit_pipeline: Arc::clone(&sharpen_data.blit_pipeline),
-                    bind_group_layout: Arc::clone(&sharpen_data.bind_group_layout),
-                    sampler: Arc::clone(&sharpen_data.sampler),
-                },
-                Arc::clone(&sharpen_uniform_buffer),
-            )),
-            wgpu_render_graph::pass_slots! {
-                input: blur_vertical_resource_id,
-                output: sharpen_resource_id,
-            },
-        ).expect("Failed to add sharpen pass");
-
-        graph.add_pass_with_slots(
-            Box::new(ConvolutionPass::new(
-                ConvolutionPassData {
-                    pipeline: Arc::clone(&convolution_data.pipeline),
-                    blit_pipeline: Arc::clone(&convolution_data.blit_pipeline),
-                    bind_group_layout: Arc::clone(&convolution_data.bind_group_layout),
-                    sampler: Arc::clone(&convolution_data.sampler),
-                },
-                convolution_kernel_buffer,
-            )),
-            wgpu_render_graph::pass_slots! {
-                input: sharpen_resource_id,
-                output: convolution_resource_id,
-            },
-        ).expect("Failed to add convolution pass");
-
-        graph.add_pass_with_slots(
-            Box::new(VignettePass::new(
-                VignettePassData {
-                    pipeline: Arc::clone(&vignette_data.pipeline),
-                    blit_pipeline: Arc::clone(&vignette_data.blit_pipeline),
-                    bind_group_layout: Arc::clone(&vignette_data.bind_group_layout),
-                    sampler: Arc::clone(&vignette_data.sampler),
-                },
-                vignette_uniform_buffer,
-            )),
-            wgpu_render_graph::pass_slots! {
-                input: convolution_resource_id,
-                output: vignette_resource_id,
-            },
-        ).expect("Failed to add vignette pass");
-
-        graph.add_pass_with_slots(
-            Box::new(GrayscalePass::new(GrayscalePassData {
-                pipeline: Arc::clone(&grayscale_data.pipeline),
-                blit_pipeline: Arc::clone(&grayscale_data.blit_pipeline),
-                bind_group_layout: Arc::clone(&grayscale_data.bind_group_layout),
-                sampler: Arc::clone(&grayscale_data.sampler),
-            })),
-            wgpu_render_graph::pass_slots! {
-                input: vignette_resource_id,
-                output: grayscale_resource_id,
-            },
-        ).expect("Failed to add grayscale pass");
-
-        graph.add_pass_with_slots(
-            Box::new(ColorInvertPass::new(ColorInvertPassData {
-                pipeline: Arc::clone(&color_invert_data.pipeline),
-                blit_pipeline: Arc::clone(&color_invert_data.blit_pipeline),
-                bind_group_layout: Arc::clone(&color_invert_data.bind_group_layout),
-                sampler: Arc::clone(&color_invert_data.sampler),
-            })),
-            wgpu_render_graph::pass_slots! {
-                input: grayscale_resource_id,
-                output: color_invert_resource_id,
-            },
-        ).expect("Failed to add color invert pass");
-
-        graph.add_pass_with_slots(
-            Box::new(BlitPass::new(
-                BlitPassData {
-                    pipeline: Arc::clone(&blit_data.pipeline),
-                    bind_group_layout: Arc::clone(&blit_data.bind_group_layout),
-                    sampler: Arc::clone(&blit_data.sampler),
-                },
-                "blit_to_viewport_display".to_string(),
-            )),
-            wgpu_render_graph::pass_slots! {
-                input: color_invert_resource_id,
-                output: viewport_display_resource_id,
-            },
-        ).expect("Failed to add blit to viewport pass");
-
-        graph.add_pass_with_slots(
-            Box::new(EguiPass::new()),
-            wgpu_render_graph::pass_slots! { color_target: egui_output_resource_id },
-        ).expect("Failed to add egui pass");
-
-        graph.add_pass_with_slots(
-            Box::new(BlitPass::new(
-                BlitPassData {
-                    pipeline: Arc::clone(&blit_data.pipeline),
-                    bind_group_layout: Arc::clone(&blit_data.bind_group_layout),
-                    sampler: Arc::clone(&blit_data.sampler),
-                },
-                "blit_to_surface".to_string(),
-            )),
-            wgpu_render_graph::pass_slots! {
-                input: egui_output_resource_id,
-                output: surface_resource_id,
-            },
-        ).expect("Failed to add blit to surface pass");
-
+        graph
+            .set_output_size(gpu.surface_config.width, gpu.surface_config.height)
+            .expect("Failed to size render graph resources");
         graph.compile().expect("Failed to compile render graph");
 
         log::info!("Render graph compiled successfully");
@@ -1604,25 +1080,13 @@ impl Renderer {
             scene,
             render_graph: graph,
             pass_configs,
+            shaders,
             surface_resource_id,
             depth_resource_id,
-            hdr_resource_id,
-            output_resource_id,
-            output_with_edges_resource_id,
-            output_with_brightness_contrast_resource_id,
-            blur_horizontal_resource_id,
-            blur_vertical_resource_id,
-            convolution_resource_id,
-            vignette_resource_id,
-            grayscale_resource_id,
-            color_invert_resource_id,
             viewport_display_resource_id,
             viewport_display_texture,
             viewport_display_view,
-            sharpen_resource_id,
-            egui_output_resource_id,
             viewport_texture_id: None,
-            _sharpen_uniform_buffer: sharpen_uniform_buffer,
             viewport_targets: HashMap::new(),
             camera_render_targets: HashMap::new(),
             camera_depth_targets: HashMap::new(),
@@ -1807,92 +1271,33 @@ impl Renderer {
         }
     }
 
+    pub fn save_pipeline_cache(&self) {
+        let (Some(path), Some(data)) = (
+            self.gpu.pipeline_cache_path.as_ref(),
+            self.render_graph.pipelines().cache_data(),
+        ) else {
+            return;
+        };
+
+        let temp_path = path.with_extension("tmp");
+        if let Err(error) =
+            std::fs::write(&temp_path, &data).and_then(|()| std::fs::rename(&temp_path, path))
+        {
+            log::warn!(
+                "Failed to save pipeline cache to {}: {}",
+                path.display(),
+                error
+            );
+        }
+    }
+
     pub fn resize(&mut self, width: u32, height: u32) {
         self.gpu.resize(width, height);
         self.depth_texture_view = self.gpu.create_depth_texture(width, height);
 
         self.render_graph
-            .resize_transient_resource(&self.gpu.device, self.hdr_resource_id, width, height)
-            .expect("Failed to resize hdr resource");
-
-        self.render_graph
-            .resize_transient_resource(&self.gpu.device, self.output_resource_id, width, height)
-            .expect("Failed to resize output resource");
-
-        self.render_graph
-            .resize_transient_resource(
-                &self.gpu.device,
-                self.output_with_edges_resource_id,
-                width,
-                height,
-            )
-            .expect("Failed to resize output_with_edges resource");
-
-        self.render_graph
-            .resize_transient_resource(
-                &self.gpu.device,
-                self.output_with_brightness_contrast_resource_id,
-                width,
-                height,
-            )
-            .expect("Failed to resize output_with_brightness_contrast resource");
-
-        self.render_graph
-            .resize_transient_resource(
-                &self.gpu.device,
-                self.blur_horizontal_resource_id,
-                width,
-                height,
-            )
-            .expect("Failed to resize blur_horizontal resource");
-
-        self.render_graph
-            .resize_transient_resource(
-                &self.gpu.device,
-                self.blur_vertical_resource_id,
-                width,
-                height,
-            )
-            .expect("Failed to resize blur_vertical resource");
-
-        self.render_graph
-            .resize_transient_resource(
-                &self.gpu.device,
-                self.convolution_resource_id,
-                width,
-                height,
-            )
-            .expect("Failed to resize convolution resource");
-
-        self.render_graph
-            .resize_transient_resource(&self.gpu.device, self.vignette_resource_id, width, height)
-            .expect("Failed to resize vignette resource");
-
-        self.render_graph
-            .resize_transient_resource(&self.gpu.device, self.grayscale_resource_id, width, height)
-            .expect("Failed to resize grayscale resource");
-
-        self.render_graph
-            .resize_transient_resource(
-                &self.gpu.device,
-                self.color_invert_resource_id,
-                width,
-                height,
-            )
-            .expect("Failed to resize color_invert resource");
-
-        self.render_graph
-            .resize_transient_resource(&self.gpu.device, self.sharpen_resource_id, width, height)
-            .expect("Failed to resize sharpen resource");
-
-        self.render_graph
-            .resize_transient_resource(
-                &self.gpu.device,
-                self.egui_output_resource_id,
-                width,
-                height,
-            )
-            .expect("Failed to resize egui_output resource");
+            .set_output_size(width, height)
+            .expect("Failed to resize render graph resources");
 
         self.viewport_display_texture = self.gpu.device.create_texture(&wgpu::TextureDescriptor {
             label: Some("Viewport Display Texture"),
@@ -1958,6 +1363,23 @@ impl Renderer {
     ) {
         let delta_time = delta_time.as_secs_f32();
 
+        let shader_reload = self.shaders.poll();
+        for error in &shader_reload.failed {
+            log::error!("{}", error);
+        }
+        if !shader_reload.reloaded.is_empty() {
+            let reloaded_passes = self.render_graph.reload_shaders(
+                &self.gpu.device,
+                &self.shaders,
+                &shader_reload.reloaded,
+            );
+            log::info!(
+                "Reloaded shaders {:?} in passes {:?}",
+                shader_reload.reloaded,
+                reloaded_passes
+            );
+        }
+
         if let Some(renderer) = &mut self.pass_configs.egui.renderer {
             for (id, image_delta) in &textures_delta.set {
                 renderer.update_texture(&self.gpu.device, &self.gpu.queue, *id, image_delta);
@@ -2047,7 +1469,8 @@ impl Renderer {
             let width = width.ceil() as u32;
             let height = height.ceil() as u32;
 
-            let (_, camera_texture_view) = self.camera_render_targets.get(&camera_id).unwrap();
+            let (camera_texture, camera_texture_view) =
+                self.camera_render_targets.get(&camera_id).unwrap();
 
             let needs_create_depth =
                 if let Some((existing_depth, _)) = self.camera_depth_targets.get(&camera_id) {
@@ -2107,23 +1530,30 @@ impl Renderer {
                     .insert(camera_id, (dummy_surface, dummy_surface_view));
             }
 
-            let (_, camera_depth_view) = self.camera_depth_targets.get(&camera_id).unwrap();
-            let (_, dummy_surface_view) = self.camera_dummy_surfaces.get(&camera_id).unwrap();
+            let (camera_depth, camera_depth_view) =
+                self.camera_depth_targets.get(&camera_id).unwrap();
+            let (dummy_surface, dummy_surface_view) =
+                self.camera_dummy_surfaces.get(&camera_id).unwrap();
 
             let aspect_ratio = width as f32 / height as f32;
             self.scene
                 .update_with_camera(&self.gpu.queue, aspect_ratio, 0.0, &camera);
 
-            self.render_graph
-                .resources_mut()
-                .set_external_texture(self.depth_resource_id, camera_depth_view.clone());
-            self.render_graph.resources_mut().set_external_texture(
+            self.render_graph.resources_mut().set_external_texture_from(
+                self.depth_resource_id,
+                camera_depth,
+                camera_depth_view.clone(),
+            );
+            self.render_graph.resources_mut().set_external_texture_from(
                 self.viewport_display_resource_id,
+                camera_texture,
                 camera_texture_view.clone(),
             );
-            self.render_graph
-                .resources_mut()
-                .set_external_texture(self.surface_resource_id, dummy_surface_view.clone());
+            self.render_graph.resources_mut().set_external_texture_from(
+                self.surface_resource_id,
+                dummy_surface,
+                dummy_surface_view.clone(),
+            );
 
             let saved_paint_jobs = std::mem::take(&mut self.pass_configs.egui.paint_jobs);
             let saved_screen_descriptor = egui_wgpu::ScreenDescriptor {
@@ -2217,14 +1647,17 @@ impl Renderer {
                     usage: None,
                 });
 
-        self.render_graph
-            .resources_mut()
-            .set_external_texture(self.surface_resource_id, surface_texture_view);
+        self.render_graph.resources_mut().set_external_texture_from(
+            self.surface_resource_id,
+            &surface_texture.texture,
+            surface_texture_view,
+        );
         self.render_graph
             .resources_mut()
             .set_external_texture(self.depth_resource_id, self.depth_texture_view.clone());
-        self.render_graph.resources_mut().set_external_texture(
+        self.render_graph.resources_mut().set_external_texture_from(
             self.viewport_display_resource_id,
+            &self.viewport_display_texture,
             self.viewport_display_view.clone(),
         );
 
@@ -2269,6 +1702,7 @@ pub struct Gpu {
     pub queue: wgpu::Queue,
     pub surface_config: wgpu::SurfaceConfiguration,
     pub surface_format: wgpu::TextureFormat,
+    pub pipeline_cache_path: Option<std::path::PathBuf>,
 }
 
 impl Gpu {
@@ -2335,7 +1769,8 @@ impl Gpu {
                 .request_device(&wgpu::DeviceDescriptor {
                     label: Some("WGPU Device"),
                     memory_hints: wgpu::MemoryHints::default(),
-                    required_features: wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
+                    required_features: wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
+                        | (adapter.features() & wgpu::Features::PIPELINE_CACHE),
                     #[cfg(not(target_arch = "wasm32"))]
                     required_limits: wgpu::Limits::default().using_resolution(adapter.limits()),
                     #[cfg(all(target_arch = "wasm32", feature = "webgpu"))]
@@ -2397,10 +1832,27 @@ impl Gpu {
             queue,
             surface_config,
             surface_format,
+            pipeline_cache_path: pipeline_cache_path(&adapter.get_info()),
         }
     }
 }
 
+fn pipeline_cache_path(adapter_info: &wgpu::AdapterInfo) -> Option<std::path::PathBuf> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = adapter_info;
+        None
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let directory = std::env::var_os("APP_PIPELINE_CACHE_DIR")
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(std::env::temp_dir);
+        wgpu::util::pipeline_cache_key(adapter_info).map(|key| directory.join(key))
+    }
+}
+
 struct TextureAtlas {
     texture: wgpu::Texture,
     texture_view: wgpu::TextureView,
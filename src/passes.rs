@@ -1,3 +1,8 @@
+use crate::pass_configs::PassConfigs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use wgpu_render_graph::{PassRegistry, PipelineRegistry, ShaderLibrary};
+
 pub mod blit_pass;
 pub mod brightness_contrast_pass;
 pub mod color_invert_pass;
@@ -28,3 +33,246 @@ pub use post_process_pass::{PostProcessPass, PostProcessPassData};
 pub use scene_pass::{ScenePass, ScenePassData};
 pub use sharpen_pass::{SharpenPass, SharpenPassData};
 pub use vignette_pass::{VignettePass, VignettePassData};
+
+pub fn shader_root() -> Option<PathBuf> {
+    #[cfg(target_arch = "wasm32")]
+    return None;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        if let Some(root) = std::env::var_os("APP_SHADER_ROOT") {
+            return Some(root.into());
+        }
+
+        #[cfg(debug_assertions)]
+        return Some(concat!(env!("CARGO_MANIFEST_DIR"), "/src/passes/shaders").into());
+
+        #[cfg(not(debug_assertions))]
+        return None;
+    }
+}
+
+pub fn register_shaders(shaders: &mut ShaderLibrary) {
+    BlitPass::register_shader(shaders);
+    BrightnessContrastPass::register_shader(shaders);
+    ColorInvertPass::register_shader(shaders);
+    ComputeGrayscalePass::register_shader(shaders);
+    ConvolutionPass::register_shader(shaders);
+    EdgeDetectionPass::register_shader(shaders);
+    GaussianBlurHorizontalPass::register_shader(shaders);
+    GrayscalePass::register_shader(shaders);
+    PostProcessPass::register_shader(shaders);
+    SharpenPass::register_shader(shaders);
+    VignettePass::register_shader(shaders);
+}
+
+fn reload_pipeline<T>(
+    device: &wgpu::Device,
+    pipelines: &mut PipelineRegistry,
+    shader: &str,
+    changed: &[String],
+    create: impl FnOnce(&mut PipelineRegistry) -> T,
+) -> Option<T> {
+    if !changed.iter().any(|name| name == shader) {
+        return None;
+    }
+
+    match pipelines.try_create(device, create) {
+        Ok(created) => Some(created),
+        Err(error) => {
+            log::error!("Keeping the previous '{}' pipeline: {}", shader, error);
+            None
+        }
+    }
+}
+
+fn linear_sampler(device: &wgpu::Device, label: &str) -> Arc<wgpu::Sampler> {
+    Arc::new(device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some(label),
+        address_mode_u: wgpu::AddressMode::ClampToEdge,
+        address_mode_v: wgpu::AddressMode::ClampToEdge,
+        address_mode_w: wgpu::AddressMode::ClampToEdge,
+        mag_filter: wgpu::FilterMode::Linear,
+        min_filter: wgpu::FilterMode::Linear,
+        mipmap_filter: wgpu::FilterMode::Nearest,
+        ..Default::default()
+    }))
+}
+
+pub fn pass_registry(
+    device: &wgpu::Device,
+    pipelines: &mut PipelineRegistry,
+    surface_format: wgpu::TextureFormat,
+    shaders: &ShaderLibrary,
+    scene: ScenePassData,
+) -> PassRegistry<PassConfigs> {
+    let mut registry = PassRegistry::new();
+
+    registry.register("scene", move |_| {
+        Ok(Box::new(ScenePass::new(scene.clone())))
+    });
+
+    let (pipeline, bind_group_layout) =
+        PostProcessPass::create_pipeline(device, pipelines, surface_format, shaders);
+    let post_process = PostProcessPassData {
+        pipeline,
+        bind_group_layout,
+        sampler: linear_sampler(device, "Post Process Sampler"),
+        surface_format,
+    };
+    registry.register("post_process", move |_| {
+        Ok(Box::new(PostProcessPass::new(post_process.clone())))
+    });
+
+    let (pipeline, bind_group_layout) =
+        ComputeGrayscalePass::create_pipeline(device, pipelines, shaders);
+    let compute_grayscale = ComputeGrayscalePassData {
+        pipeline,
+        bind_group_layout,
+    };
+    registry.register("compute_grayscale", move |_| {
+        Ok(Box::new(ComputeGrayscalePass::new(
+            compute_grayscale.clone(),
+        )))
+    });
+
+    let (blit_pipeline, blit_bind_group_layout) =
+        BlitPass::create_pipeline(device, pipelines, surface_format, shaders);
+
+    let (pipeline, bind_group_layout) =
+        EdgeDetectionPass::create_pipeline(device, pipelines, surface_format, shaders);
+    let edge_detection = EdgeDetectionPassData {
+        pipeline,
+        blit_pipeline: Arc::clone(&blit_pipeline),
+        blit_bind_group_layout: Arc::clone(&blit_bind_group_layout),
+        bind_group_layout,
+        sampler: linear_sampler(device, "Edge Detection Sampler"),
+        surface_format,
+    };
+    registry.register("edge_detection", move |_| {
+        Ok(Box::new(EdgeDetectionPass::new(edge_detection.clone())))
+    });
+
+    let (pipeline, bind_group_layout) =
+        BrightnessContrastPass::create_pipeline(device, pipelines, surface_format, shaders);
+    let brightness_contrast = BrightnessContrastPassData {
+        pipeline,
+        blit_pipeline: Arc::clone(&blit_pipeline),
+        blit_bind_group_layout: Arc::clone(&blit_bind_group_layout),
+        bind_group_layout,
+        sampler: linear_sampler(device, "Brightness/Contrast Sampler"),
+        surface_format,
+    };
+    registry.register("brightness_contrast", move |_| {
+        Ok(Box::new(BrightnessContrastPass::new(
+            brightness_contrast.clone(),
+        )))
+    });
+
+    let (pipeline, bind_group_layout) =
+        GaussianBlurHorizontalPass::create_pipeline(device, pipelines, surface_format, shaders);
+    let gaussian_blur = GaussianBlurPassData {
+        pipeline,
+        blit_pipeline: Arc::clone(&blit_pipeline),
+        blit_bind_group_layout: Arc::clone(&blit_bind_group_layout),
+        bind_group_layout,
+        sampler: linear_sampler(device, "Gaussian Blur Sampler"),
+        surface_format,
+    };
+    let gaussian_blur_vertical = gaussian_blur.clone();
+    registry.register("gaussian_blur_horizontal", move |_| {
+        Ok(Box::new(GaussianBlurHorizontalPass::new(
+            gaussian_blur.clone(),
+        )))
+    });
+    registry.register("gaussian_blur_vertical", move |_| {
+        Ok(Box::new(GaussianBlurVerticalPass::new(
+            gaussian_blur_vertical.clone(),
+        )))
+    });
+
+    let (pipeline, bind_group_layout) =
+        SharpenPass::create_pipeline(device, pipelines, surface_format, shaders);
+    let sharpen = SharpenPassData {
+        pipeline,
+        blit_pipeline: Arc::clone(&blit_pipeline),
+        blit_bind_group_layout: Arc::clone(&blit_bind_group_layout),
+        bind_group_layout,
+        sampler: linear_sampler(device, "Sharpen Sampler"),
+        surface_format,
+    };
+    registry.register("sharpen", move |_| {
+        Ok(Box::new(SharpenPass::new(sharpen.clone())))
+    });
+
+    let (pipeline, bind_group_layout) =
+        ConvolutionPass::create_pipeline(device, pipelines, surface_format, shaders);
+    let convolution = ConvolutionPassData {
+        pipeline,
+        blit_pipeline: Arc::clone(&blit_pipeline),
+        blit_bind_group_layout: Arc::clone(&blit_bind_group_layout),
+        bind_group_layout,
+        sampler: linear_sampler(device, "Convolution Sampler"),
+        surface_format,
+    };
+    registry.register("convolution", move |_| {
+        Ok(Box::new(ConvolutionPass::new(convolution.clone())))
+    });
+
+    let (pipeline, bind_group_layout) =
+        VignettePass::create_pipeline(device, pipelines, surface_format, shaders);
+    let vignette = VignettePassData {
+        pipeline,
+        blit_pipeline: Arc::clone(&blit_pipeline),
+        blit_bind_group_layout: Arc::clone(&blit_bind_group_layout),
+        bind_group_layout,
+        sampler: linear_sampler(device, "Vignette Sampler"),
+        surface_format,
+    };
+    registry.register("vignette", move |_| {
+        Ok(Box::new(VignettePass::new(vignette.clone())))
+    });
+
+    let (pipeline, bind_group_layout) =
+        GrayscalePass::create_pipeline(device, pipelines, surface_format, shaders);
+    let grayscale = GrayscalePassData {
+        pipeline,
+        blit_pipeline: Arc::clone(&blit_pipeline),
+        blit_bind_group_layout: Arc::clone(&blit_bind_group_layout),
+        bind_group_layout,
+        sampler: linear_sampler(device, "Grayscale Sampler"),
+        surface_format,
+    };
+    registry.register("grayscale", move |_| {
+        Ok(Box::new(GrayscalePass::new(grayscale.clone())))
+    });
+
+    let (pipeline, bind_group_layout) =
+        ColorInvertPass::create_pipeline(device, pipelines, surface_format, shaders);
+    let color_invert = ColorInvertPassData {
+        pipeline,
+        blit_pipeline: Arc::clone(&blit_pipeline),
+        blit_bind_group_layout: Arc::clone(&blit_bind_group_layout),
+        bind_group_layout,
+        sampler: linear_sampler(device, "Color Invert Sampler"),
+        surface_format,
+    };
+    registry.register("color_invert", move |_| {
+        Ok(Box::new(ColorInvertPass::new(color_invert.clone())))
+    });
+
+    let blit = BlitPassData {
+        pipeline: blit_pipeline,
+        bind_group_layout: blit_bind_group_layout,
+        sampler: linear_sampler(device, "Blit Sampler"),
+        surface_format,
+    };
+    registry.register("blit", move |parameters| {
+        let name = parameters.get_or("name", "blit_pass".to_string());
+        Ok(Box::new(BlitPass::new(blit.clone(), name)))
+    });
+
+    registry.register("egui", |_| Ok(Box::new(EguiPass::new())));
+
+    registry
+}
@@ -1,120 +1,125 @@
 use super::shader_common::FULLSCREEN_VERTEX_SHADER;
 use std::sync::Arc;
-use wgpu::{
-    BindGroup, BindGroupLayout, Operations, RenderPassColorAttachment, RenderPipeline, Sampler,
+use wgpu::{BindGroupLayout, RenderPipeline, Sampler};
+use wgpu_render_graph::{
+    BindGroupSource, PassExecutionContext, PassNode, PipelineRegistry, ShaderLibrary,
 };
-use wgpu_render_graph::{PassExecutionContext, PassNode};
 
-const BLIT_FRAGMENT_SHADER: &str = "
-@group(0) @binding(0)
-var input_texture: texture_2d<f32>;
-
-@group(0) @binding(1)
-var input_sampler: sampler;
-
-@fragment
-fn fragment_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    return textureSample(input_texture, input_sampler, in.uv);
-}
-";
+pub(crate) const SHADER_NAME: &str = "blit";
+const SHADER_PATH: &str = "blit.wgsl";
+const BLIT_FRAGMENT_SHADER: &str = include_str!("shaders/blit.wgsl");
 
+#[derive(Clone)]
 pub struct BlitPassData {
     pub pipeline: Arc<RenderPipeline>,
     pub bind_group_layout: Arc<BindGroupLayout>,
     pub sampler: Arc<Sampler>,
+    pub surface_format: wgpu::TextureFormat,
 }
 
 pub struct BlitPass {
     pub data: BlitPassData,
     name: String,
-    cached_bind_group: Option<BindGroup>,
 }
 
 impl BlitPass {
     pub fn new(data: BlitPassData, name: String) -> Self {
-        Self {
-            data,
-            name,
-            cached_bind_group: None,
-        }
+        Self { data, name }
+    }
+
+    pub fn register_shader(shaders: &mut ShaderLibrary) {
+        shaders.register_file_with_prelude(
+            SHADER_NAME,
+            SHADER_PATH,
+            FULLSCREEN_VERTEX_SHADER,
+            BLIT_FRAGMENT_SHADER,
+        );
     }
 
     pub fn create_pipeline(
         device: &wgpu::Device,
+        pipelines: &mut PipelineRegistry,
         surface_format: wgpu::TextureFormat,
+        shaders: &ShaderLibrary,
     ) -> (Arc<RenderPipeline>, Arc<BindGroupLayout>) {
-        let shader_source = format!("{}\n{}", FULLSCREEN_VERTEX_SHADER, BLIT_FRAGMENT_SHADER);
-
-        let shader_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Blit Shader"),
-            source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Owned(shader_source)),
-        });
-
-        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("Blit Bind Group Layout"),
-            entries: &[
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                        multisampled: false,
+        let shader_source = shaders.source(SHADER_NAME).expect("shader not registered");
+
+        let shader_module = pipelines.shader_module(device, "Blit Shader", shader_source);
+
+        let bind_group_layout = pipelines.bind_group_layout(
+            device,
+            &wgpu::BindGroupLayoutDescriptor {
+                label: Some("Blit Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
                     },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
-                    count: None,
-                },
-            ],
-        });
-
-        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Blit Pipeline Layout"),
-            bind_group_layouts: &[&bind_group_layout],
-            push_constant_ranges: &[],
-        });
-
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Blit Pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader_module,
-                entry_point: Some("vertex_main"),
-                buffers: &[],
-                compilation_options: Default::default(),
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
             },
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                unclipped_depth: false,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                conservative: false,
+        );
+
+        let pipeline_layout = pipelines.pipeline_layout(
+            device,
+            &wgpu::PipelineLayoutDescriptor {
+                label: Some("Blit Pipeline Layout"),
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
             },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
+        );
+
+        let pipeline = pipelines.render_pipeline(
+            device,
+            &wgpu::RenderPipelineDescriptor {
+                label: Some("Blit Pipeline"),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader_module,
+                    entry_point: Some("vertex_main"),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: None,
+                    unclipped_depth: false,
+                    polygon_mode: wgpu::PolygonMode::Fill,
+                    conservative: false,
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState {
+                    count: 1,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader_module,
+                    entry_point: Some("fragment_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: surface_format,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                multiview: None,
+                cache: None,
             },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader_module,
-                entry_point: Some("fragment_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_format,
-                    blend: None,
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            multiview: None,
-            cache: None,
-        });
+        );
 
         (Arc::new(pipeline), Arc::new(bind_group_layout))
     }
@@ -133,56 +138,54 @@ impl PassNode<crate::pass_configs::PassConfigs> for BlitPass {
         vec!["output"]
     }
 
-    fn invalidate_bind_groups(&mut self) {
-        self.cached_bind_group = None;
+    fn reload_shaders(
+        &mut self,
+        device: &wgpu::Device,
+        pipelines: &mut PipelineRegistry,
+        shaders: &ShaderLibrary,
+        changed: &[String],
+    ) -> bool {
+        let surface_format = self.data.surface_format;
+        let Some((pipeline, bind_group_layout)) =
+            super::reload_pipeline(device, pipelines, SHADER_NAME, changed, |pipelines| {
+                Self::create_pipeline(device, pipelines, surface_format, shaders)
+            })
+        else {
+            return false;
+        };
+
+        self.data.pipeline = pipeline;
+        self.data.bind_group_layout = bind_group_layout;
+        true
     }
 
     fn execute<'r, 'e>(
         &mut self,
-        context: PassExecutionContext<'r, 'e, crate::pass_configs::PassConfigs>,
+        mut context: PassExecutionContext<'r, 'e, crate::pass_configs::PassConfigs>,
     ) -> wgpu_render_graph::Result<Vec<wgpu_render_graph::SubGraphRunCommand<'r>>> {
-        if self.cached_bind_group.is_none() {
-            let input_view = context.get_texture_view("input")?;
-
-            self.cached_bind_group = Some(context.device.create_bind_group(
-                &wgpu::BindGroupDescriptor {
-                    label: Some("Blit Bind Group"),
-                    layout: &self.data.bind_group_layout,
-                    entries: &[
-                        wgpu::BindGroupEntry {
-                            binding: 0,
-                            resource: wgpu::BindingResource::TextureView(input_view),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 1,
-                            resource: wgpu::BindingResource::Sampler(&self.data.sampler),
-                        },
-                    ],
-                },
-            ));
-        }
+        let bind_group = context.bind_group(
+            "Blit Bind Group",
+            &self.data.bind_group_layout,
+            &[
+                BindGroupSource::Slot("input"),
+                BindGroupSource::Sampler(&self.data.sampler),
+            ],
+        )?;
 
-        let (color_view, color_load_op, color_store_op) = context.get_color_attachment("output")?;
+        let color_attachment = context.color_attachment("output")?;
 
         let mut render_pass = context
             .encoder
             .begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Blit Render Pass"),
-                color_attachments: &[Some(RenderPassColorAttachment {
-                    view: color_view,
-                    resolve_target: None,
-                    ops: Operations {
-                        load: color_load_op,
-                        store: color_store_op,
-                    },
-                })],
+                color_attachments: &[Some(color_attachment)],
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
             });
 
         render_pass.set_pipeline(&self.data.pipeline);
-        render_pass.set_bind_group(0, self.cached_bind_group.as_ref().unwrap(), &[]);
+        render_pass.set_bind_group(0, &bind_group, &[]);
         render_pass.draw(0..3, 0..1);
 
         drop(render_pass);
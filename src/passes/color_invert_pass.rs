@@ -1,125 +1,127 @@
+use super::blit_pass::{self, BlitPass};
 use super::shader_common::FULLSCREEN_VERTEX_SHADER;
 use std::sync::Arc;
-use wgpu::{
-    BindGroup, BindGroupLayout, Operations, RenderPassColorAttachment, RenderPipeline, Sampler,
+use wgpu::{BindGroupLayout, RenderPipeline, Sampler};
+use wgpu_render_graph::{
+    BindGroupSource, PassExecutionContext, PassNode, PipelineRegistry, ShaderLibrary,
 };
-use wgpu_render_graph::{PassExecutionContext, PassNode};
 
-const COLOR_INVERT_FRAGMENT_SHADER: &str = "
-@group(0) @binding(0)
-var input_texture: texture_2d<f32>;
-
-@group(0) @binding(1)
-var input_sampler: sampler;
-
-@fragment
-fn fragment_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    let color = textureSample(input_texture, input_sampler, in.uv).rgb;
-    return vec4<f32>(1.0 - color, 1.0);
-}
-";
+const SHADER_NAME: &str = "color_invert";
+const SHADER_PATH: &str = "color_invert.wgsl";
+const COLOR_INVERT_FRAGMENT_SHADER: &str = include_str!("shaders/color_invert.wgsl");
 
+#[derive(Clone)]
 pub struct ColorInvertPassData {
     pub pipeline: Arc<RenderPipeline>,
     pub blit_pipeline: Arc<RenderPipeline>,
+    pub blit_bind_group_layout: Arc<BindGroupLayout>,
     pub bind_group_layout: Arc<BindGroupLayout>,
     pub sampler: Arc<Sampler>,
+    pub surface_format: wgpu::TextureFormat,
 }
 
 pub struct ColorInvertPass {
     pub data: ColorInvertPassData,
-    cached_bind_group_with_invert: Option<BindGroup>,
-    cached_bind_group_without_invert: Option<BindGroup>,
 }
 
 impl ColorInvertPass {
     pub fn new(data: ColorInvertPassData) -> Self {
-        Self {
-            data,
-            cached_bind_group_with_invert: None,
-            cached_bind_group_without_invert: None,
-        }
+        Self { data }
+    }
+
+    pub fn register_shader(shaders: &mut ShaderLibrary) {
+        shaders.register_file_with_prelude(
+            SHADER_NAME,
+            SHADER_PATH,
+            FULLSCREEN_VERTEX_SHADER,
+            COLOR_INVERT_FRAGMENT_SHADER,
+        );
     }
 
     pub fn create_pipeline(
         device: &wgpu::Device,
+        pipelines: &mut PipelineRegistry,
         surface_format: wgpu::TextureFormat,
+        shaders: &ShaderLibrary,
     ) -> (Arc<RenderPipeline>, Arc<BindGroupLayout>) {
-        let shader_source = format!(
-            "{}\n{}",
-            FULLSCREEN_VERTEX_SHADER, COLOR_INVERT_FRAGMENT_SHADER
+        let shader_source = shaders.source(SHADER_NAME).expect("shader not registered");
+
+        let shader_module = pipelines.shader_module(device, "Color Invert Shader", shader_source);
+
+        let bind_group_layout = pipelines.bind_group_layout(
+            device,
+            &wgpu::BindGroupLayoutDescriptor {
+                label: Some("Color Invert Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+            },
         );
 
-        let shader_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Color Invert Shader"),
-            source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Owned(shader_source)),
-        });
-
-        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("Color Invert Bind Group Layout"),
-            entries: &[
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                        multisampled: false,
-                    },
-                    count: None,
+        let pipeline_layout = pipelines.pipeline_layout(
+            device,
+            &wgpu::PipelineLayoutDescriptor {
+                label: Some("Color Invert Pipeline Layout"),
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            },
+        );
+
+        let pipeline = pipelines.render_pipeline(
+            device,
+            &wgpu::RenderPipelineDescriptor {
+                label: Some("Color Invert Pipeline"),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader_module,
+                    entry_point: Some("vertex_main"),
+                    buffers: &[],
+                    compilation_options: Default::default(),
                 },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
-                    count: None,
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: None,
+                    unclipped_depth: false,
+                    polygon_mode: wgpu::PolygonMode::Fill,
+                    conservative: false,
                 },
-            ],
-        });
-
-        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Color Invert Pipeline Layout"),
-            bind_group_layouts: &[&bind_group_layout],
-            push_constant_ranges: &[],
-        });
-
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Color Invert Pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader_module,
-                entry_point: Some("vertex_main"),
-                buffers: &[],
-                compilation_options: Default::default(),
-            },
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                unclipped_depth: false,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState {
+                    count: 1,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader_module,
+                    entry_point: Some("fragment_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: surface_format,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                multiview: None,
+                cache: None,
             },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader_module,
-                entry_point: Some("fragment_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_format,
-                    blend: None,
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            multiview: None,
-            cache: None,
-        });
+        );
 
         (Arc::new(pipeline), Arc::new(bind_group_layout))
     }
@@ -138,59 +140,74 @@ impl PassNode<crate::pass_configs::PassConfigs> for ColorInvertPass {
         vec!["output"]
     }
 
-    fn invalidate_bind_groups(&mut self) {
-        self.cached_bind_group_with_invert = None;
-        self.cached_bind_group_without_invert = None;
+    fn reload_shaders(
+        &mut self,
+        device: &wgpu::Device,
+        pipelines: &mut PipelineRegistry,
+        shaders: &ShaderLibrary,
+        changed: &[String],
+    ) -> bool {
+        let surface_format = self.data.surface_format;
+        let mut reloaded = false;
+
+        if let Some((pipeline, bind_group_layout)) =
+            super::reload_pipeline(device, pipelines, SHADER_NAME, changed, |pipelines| {
+                Self::create_pipeline(device, pipelines, surface_format, shaders)
+            })
+        {
+            self.data.pipeline = pipeline;
+            self.data.bind_group_layout = bind_group_layout;
+            reloaded = true;
+        }
+
+        if let Some((blit_pipeline, blit_bind_group_layout)) = super::reload_pipeline(
+            device,
+            pipelines,
+            blit_pass::SHADER_NAME,
+            changed,
+            |pipelines| BlitPass::create_pipeline(device, pipelines, surface_format, shaders),
+        ) {
+            self.data.blit_pipeline = blit_pipeline;
+            self.data.blit_bind_group_layout = blit_bind_group_layout;
+            reloaded = true;
+        }
+
+        reloaded
     }
 
     fn execute<'r, 'e>(
         &mut self,
-        context: PassExecutionContext<'r, 'e, crate::pass_configs::PassConfigs>,
+        mut context: PassExecutionContext<'r, 'e, crate::pass_configs::PassConfigs>,
     ) -> wgpu_render_graph::Result<Vec<wgpu_render_graph::SubGraphRunCommand<'r>>> {
-        if self.cached_bind_group_with_invert.is_none() {
-            let input_view = context.get_texture_view("input")?;
-
-            self.cached_bind_group_with_invert = Some(context.device.create_bind_group(
-                &wgpu::BindGroupDescriptor {
-                    label: Some("Color Invert Bind Group"),
-                    layout: &self.data.bind_group_layout,
-                    entries: &[
-                        wgpu::BindGroupEntry {
-                            binding: 0,
-                            resource: wgpu::BindingResource::TextureView(input_view),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 1,
-                            resource: wgpu::BindingResource::Sampler(&self.data.sampler),
-                        },
-                    ],
-                },
-            ));
-        }
+        let config = &context.configs.color_invert;
 
-        if self.cached_bind_group_without_invert.is_none() {
-            let input_view = context.get_texture_view("input")?;
-
-            self.cached_bind_group_without_invert = Some(context.device.create_bind_group(
-                &wgpu::BindGroupDescriptor {
-                    label: Some("Blit Bind Group (for disabled color invert)"),
-                    layout: &self.data.blit_pipeline.get_bind_group_layout(0),
-                    entries: &[
-                        wgpu::BindGroupEntry {
-                            binding: 0,
-                            resource: wgpu::BindingResource::TextureView(input_view),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 1,
-                            resource: wgpu::BindingResource::Sampler(&self.data.sampler),
-                        },
+        let (pipeline, bind_group) = if config.enabled {
+            (
+                &self.data.pipeline,
+                context.bind_group(
+                    "Color Invert Bind Group",
+                    &self.data.bind_group_layout,
+                    &[
+                        BindGroupSource::Slot("input"),
+                        BindGroupSource::Sampler(&self.data.sampler),
                     ],
-                },
-            ));
-        }
+                )?,
+            )
+        } else {
+            (
+                &self.data.blit_pipeline,
+                context.bind_group(
+                    "Blit Bind Group (for disabled color invert)",
+                    &self.data.blit_bind_group_layout,
+                    &[
+                        BindGroupSource::Slot("input"),
+                        BindGroupSource::Sampler(&self.data.sampler),
+                    ],
+                )?,
+            )
+        };
 
-        let config = &context.configs.color_invert;
-        let (color_view, color_load_op, color_store_op) = context.get_color_attachment("output")?;
+        let color_attachment = context.color_attachment("output")?;
 
         let mut render_pass = context
             .encoder
@@ -200,33 +217,14 @@ impl PassNode<crate::pass_configs::PassConfigs> for ColorInvertPass {
                 } else {
                     Some("Passthrough Blit Render Pass")
                 },
-                color_attachments: &[Some(RenderPassColorAttachment {
-                    view: color_view,
-                    resolve_target: None,
-                    ops: Operations {
-                        load: color_load_op,
-                        store: color_store_op,
-                    },
-                })],
+                color_attachments: &[Some(color_attachment)],
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
             });
 
-        let (pipeline, bind_group) = if config.enabled {
-            (
-                &self.data.pipeline,
-                self.cached_bind_group_with_invert.as_ref().unwrap(),
-            )
-        } else {
-            (
-                &self.data.blit_pipeline,
-                self.cached_bind_group_without_invert.as_ref().unwrap(),
-            )
-        };
-
         render_pass.set_pipeline(pipeline);
-        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.set_bind_group(0, &bind_group, &[]);
         render_pass.draw(0..3, 0..1);
         drop(render_pass);
 
@@ -1,46 +1,15 @@
 use std::sync::Arc;
-use wgpu::{BindGroup, BindGroupLayout, ComputePipeline};
-use wgpu_render_graph::{PassExecutionContext, PassNode};
-
-const COMPUTE_SHADER: &str = "
-@group(0) @binding(0)
-var input_texture: texture_2d<f32>;
-
-@group(0) @binding(1)
-var output_texture: texture_storage_2d<rgba8unorm, write>;
-
-struct Uniforms {
-    enabled: u32,
-    padding1: u32,
-    padding2: u32,
-    padding3: u32,
+use wgpu::{BindGroupLayout, ComputePipeline};
+use wgpu_render_graph::{
+    BindGroupSource, PassExecutionContext, PassNode, PassPrepareContext, PipelineRegistry,
+    ShaderLibrary, SlotUsage, UploadAllocation,
 };
 
-@group(0) @binding(2)
-var<uniform> uniforms: Uniforms;
-
-@compute @workgroup_size(8, 8)
-fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
-    let texture_size = textureDimensions(input_texture);
-
-    if (global_id.x >= texture_size.x || global_id.y >= texture_size.y) {
-        return;
-    }
-
-    let coords = vec2<i32>(i32(global_id.x), i32(global_id.y));
-    let color = textureLoad(input_texture, coords, 0);
-
-    var output_color = color;
-
-    if (uniforms.enabled != 0u) {
-        let luminance = dot(color.rgb, vec3<f32>(0.299, 0.587, 0.114));
-        output_color = vec4<f32>(luminance, luminance, luminance, color.a);
-    }
-
-    textureStore(output_texture, vec2<i32>(i32(global_id.x), i32(global_id.y)), output_color);
-}
-";
+const SHADER_NAME: &str = "compute_grayscale";
+const SHADER_PATH: &str = "compute_grayscale.wgsl";
+const COMPUTE_SHADER: &str = include_str!("shaders/compute_grayscale.wgsl");
 
+#[derive(Clone)]
 pub struct ComputeGrayscalePassData {
     pub pipeline: Arc<ComputePipeline>,
     pub bind_group_layout: Arc<BindGroupLayout>,
@@ -48,75 +17,90 @@ pub struct ComputeGrayscalePassData {
 
 pub struct ComputeGrayscalePass {
     pub data: ComputeGrayscalePassData,
-    cached_bind_group: Option<BindGroup>,
-    uniform_buffer: Arc<wgpu::Buffer>,
+    uniforms: Option<UploadAllocation>,
 }
 
 impl ComputeGrayscalePass {
-    pub fn new(data: ComputeGrayscalePassData, uniform_buffer: Arc<wgpu::Buffer>) -> Self {
+    pub fn new(data: ComputeGrayscalePassData) -> Self {
         Self {
             data,
-            cached_bind_group: None,
-            uniform_buffer,
+            uniforms: None,
         }
     }
 
-    pub fn create_pipeline(device: &wgpu::Device) -> (Arc<ComputePipeline>, Arc<BindGroupLayout>) {
-        let shader_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Compute Grayscale Shader"),
-            source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(COMPUTE_SHADER)),
-        });
-
-        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("Compute Grayscale Bind Group Layout"),
-            entries: &[
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                        multisampled: false,
+    pub fn register_shader(shaders: &mut ShaderLibrary) {
+        shaders.register_file(SHADER_NAME, SHADER_PATH, COMPUTE_SHADER);
+    }
+
+    pub fn create_pipeline(
+        device: &wgpu::Device,
+        pipelines: &mut PipelineRegistry,
+        shaders: &ShaderLibrary,
+    ) -> (Arc<ComputePipeline>, Arc<BindGroupLayout>) {
+        let shader_source = shaders.source(SHADER_NAME).expect("shader not registered");
+
+        let shader_module =
+            pipelines.shader_module(device, "Compute Grayscale Shader", shader_source);
+
+        let bind_group_layout = pipelines.bind_group_layout(
+            device,
+            &wgpu::BindGroupLayoutDescriptor {
+                label: Some("Compute Grayscale Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
                     },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::StorageTexture {
-                        access: wgpu::StorageTextureAccess::WriteOnly,
-                        format: wgpu::TextureFormat::Rgba8Unorm,
-                        view_dimension: wgpu::TextureViewDimension::D2,
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::StorageTexture {
+                            access: wgpu::StorageTextureAccess::WriteOnly,
+                            format: wgpu::TextureFormat::Rgba8Unorm,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                        },
+                        count: None,
                     },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 2,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: true,
+                            min_binding_size: None,
+                        },
+                        count: None,
                     },
-                    count: None,
-                },
-            ],
-        });
-
-        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Compute Example Pipeline Layout"),
-            bind_group_layouts: &[&bind_group_layout],
-            push_constant_ranges: &[],
-        });
-
-        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-            label: Some("Compute Example Pipeline"),
-            layout: Some(&pipeline_layout),
-            module: &shader_module,
-            entry_point: Some("main"),
-            compilation_options: Default::default(),
-            cache: None,
-        });
+                ],
+            },
+        );
+
+        let pipeline_layout = pipelines.pipeline_layout(
+            device,
+            &wgpu::PipelineLayoutDescriptor {
+                label: Some("Compute Example Pipeline Layout"),
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            },
+        );
+
+        let pipeline = pipelines.compute_pipeline(
+            device,
+            &wgpu::ComputePipelineDescriptor {
+                label: Some("Compute Example Pipeline"),
+                layout: Some(&pipeline_layout),
+                module: &shader_module,
+                entry_point: Some("main"),
+                compilation_options: Default::default(),
+                cache: None,
+            },
+        );
 
         (Arc::new(pipeline), Arc::new(bind_group_layout))
     }
@@ -135,54 +119,55 @@ impl PassNode<crate::pass_configs::PassConfigs> for ComputeGrayscalePass {
         vec!["output"]
     }
 
-    fn prepare(
-        &mut self,
-        _device: &wgpu::Device,
-        queue: &wgpu::Queue,
-        configs: &crate::pass_configs::PassConfigs,
-    ) {
-        let enabled = if configs.compute_grayscale.enabled {
+    fn slot_usage(&self, slot: &str) -> Option<SlotUsage> {
+        (slot == "output").then_some(SlotUsage::Storage)
+    }
+
+    fn prepare(&mut self, context: &mut PassPrepareContext<'_, crate::pass_configs::PassConfigs>) {
+        let enabled = if context.configs.compute_grayscale.enabled {
             1u32
         } else {
             0u32
         };
         let uniform_data = [enabled, 0u32, 0u32, 0u32];
-        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&uniform_data));
+        self.uniforms = Some(context.upload(&uniform_data));
     }
 
-    fn invalidate_bind_groups(&mut self) {
-        self.cached_bind_group = None;
+    fn reload_shaders(
+        &mut self,
+        device: &wgpu::Device,
+        pipelines: &mut PipelineRegistry,
+        shaders: &ShaderLibrary,
+        changed: &[String],
+    ) -> bool {
+        let Some((pipeline, bind_group_layout)) =
+            super::reload_pipeline(device, pipelines, SHADER_NAME, changed, |pipelines| {
+                Self::create_pipeline(device, pipelines, shaders)
+            })
+        else {
+            return false;
+        };
+
+        self.data.pipeline = pipeline;
+        self.data.bind_group_layout = bind_group_layout;
+        true
     }
 
     fn execute<'r, 'e>(
         &mut self,
-        context: PassExecutionContext<'r, 'e, crate::pass_configs::PassConfigs>,
+        mut context: PassExecutionContext<'r, 'e, crate::pass_configs::PassConfigs>,
     ) -> wgpu_render_graph::Result<Vec<wgpu_render_graph::SubGraphRunCommand<'r>>> {
-        if self.cached_bind_group.is_none() {
-            let input_view = context.get_texture_view("input")?;
-            let output_view = context.get_texture_view("output")?;
-
-            self.cached_bind_group = Some(context.device.create_bind_group(
-                &wgpu::BindGroupDescriptor {
-                    label: Some("Compute Grayscale Bind Group"),
-                    layout: &self.data.bind_group_layout,
-                    entries: &[
-                        wgpu::BindGroupEntry {
-                            binding: 0,
-                            resource: wgpu::BindingResource::TextureView(input_view),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 1,
-                            resource: wgpu::BindingResource::TextureView(output_view),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 2,
-                            resource: self.uniform_buffer.as_entire_binding(),
-                        },
-                    ],
-                },
-            ));
-        }
+        let uniforms = self.uniforms.expect("uniforms are uploaded in prepare");
+
+        let bind_group = context.bind_group(
+            "Compute Grayscale Bind Group",
+            &self.data.bind_group_layout,
+            &[
+                BindGroupSource::Slot("input"),
+                BindGroupSource::Slot("output"),
+                BindGroupSource::Upload(uniforms),
+            ],
+        )?;
 
         let texture_size = context.get_texture_size("input")?;
 
@@ -194,7 +179,7 @@ impl PassNode<crate::pass_configs::PassConfigs> for ComputeGrayscalePass {
             });
 
         compute_pass.set_pipeline(&self.data.pipeline);
-        compute_pass.set_bind_group(0, self.cached_bind_group.as_ref().unwrap(), &[]);
+        compute_pass.set_bind_group(0, &bind_group, &[uniforms.offset()]);
 
         let workgroup_count_x = texture_size.0.div_ceil(8);
         let workgroup_count_y = texture_size.1.div_ceil(8);
@@ -1,159 +1,121 @@
+use super::blit_pass::{self, BlitPass};
 use std::sync::Arc;
-use wgpu::{
-    BindGroup, BindGroupLayout, Operations, RenderPassColorAttachment, RenderPipeline, Sampler,
+use wgpu::{BindGroupLayout, RenderPipeline, Sampler};
+use wgpu_render_graph::{
+    BindGroupSource, PassExecutionContext, PassNode, PipelineRegistry, ShaderLibrary,
 };
-use wgpu_render_graph::{PassExecutionContext, PassNode};
 
-const EDGE_DETECTION_SHADER: &str = "
-struct VertexOutput {
-    @builtin(position) position: vec4<f32>,
-    @location(0) uv: vec2<f32>,
-};
-
-@vertex
-fn vertex_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
-    var out: VertexOutput;
-    let x = f32((vertex_index & 1u) << 1u);
-    let y = f32((vertex_index & 2u));
-    out.position = vec4<f32>(x * 2.0 - 1.0, y * 2.0 - 1.0, 0.0, 1.0);
-    out.uv = vec2<f32>(x, 1.0 - y);
-    return out;
-}
-
-@group(0) @binding(0)
-var input_texture: texture_2d<f32>;
-
-@group(0) @binding(1)
-var input_sampler: sampler;
-
-fn luminance(color: vec3<f32>) -> f32 {
-    return dot(color, vec3<f32>(0.299, 0.587, 0.114));
-}
-
-@fragment
-fn fragment_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    let texture_size = textureDimensions(input_texture);
-    let texel_size = vec2<f32>(1.0 / f32(texture_size.x), 1.0 / f32(texture_size.y));
-
-    let tl = luminance(textureSample(input_texture, input_sampler, in.uv + vec2<f32>(-texel_size.x, -texel_size.y)).rgb);
-    let tm = luminance(textureSample(input_texture, input_sampler, in.uv + vec2<f32>(0.0, -texel_size.y)).rgb);
-    let tr = luminance(textureSample(input_texture, input_sampler, in.uv + vec2<f32>(texel_size.x, -texel_size.y)).rgb);
-
-    let ml = luminance(textureSample(input_texture, input_sampler, in.uv + vec2<f32>(-texel_size.x, 0.0)).rgb);
-    let mr = luminance(textureSample(input_texture, input_sampler, in.uv + vec2<f32>(texel_size.x, 0.0)).rgb);
-
-    let bl = luminance(textureSample(input_texture, input_sampler, in.uv + vec2<f32>(-texel_size.x, texel_size.y)).rgb);
-    let bm = luminance(textureSample(input_texture, input_sampler, in.uv + vec2<f32>(0.0, texel_size.y)).rgb);
-    let br = luminance(textureSample(input_texture, input_sampler, in.uv + vec2<f32>(texel_size.x, texel_size.y)).rgb);
-
-    let gx = -tl - 2.0 * ml - bl + tr + 2.0 * mr + br;
-    let gy = -tl - 2.0 * tm - tr + bl + 2.0 * bm + br;
-
-    let edge_strength = sqrt(gx * gx + gy * gy);
-
-    let original = textureSample(input_texture, input_sampler, in.uv).rgb;
-    let edge_color = vec3<f32>(edge_strength);
-
-    let result = mix(original, edge_color, 0.7);
-
-    return vec4<f32>(result, 1.0);
-}
-";
+const SHADER_NAME: &str = "edge_detection";
+const SHADER_PATH: &str = "edge_detection.wgsl";
+const EDGE_DETECTION_SHADER: &str = include_str!("shaders/edge_detection.wgsl");
 
+#[derive(Clone)]
 pub struct EdgeDetectionPassData {
     pub pipeline: Arc<RenderPipeline>,
     pub blit_pipeline: Arc<RenderPipeline>,
+    pub blit_bind_group_layout: Arc<BindGroupLayout>,
     pub bind_group_layout: Arc<BindGroupLayout>,
     pub sampler: Arc<Sampler>,
+    pub surface_format: wgpu::TextureFormat,
 }
 
 pub struct EdgeDetectionPass {
     pub data: EdgeDetectionPassData,
-    cached_bind_group: Option<BindGroup>,
 }
 
 impl EdgeDetectionPass {
     pub fn new(data: EdgeDetectionPassData) -> Self {
-        Self {
-            data,
-            cached_bind_group: None,
-        }
+        Self { data }
+    }
+
+    pub fn register_shader(shaders: &mut ShaderLibrary) {
+        shaders.register_file(SHADER_NAME, SHADER_PATH, EDGE_DETECTION_SHADER);
     }
 
     pub fn create_pipeline(
         device: &wgpu::Device,
+        pipelines: &mut PipelineRegistry,
         surface_format: wgpu::TextureFormat,
+        shaders: &ShaderLibrary,
     ) -> (Arc<RenderPipeline>, Arc<BindGroupLayout>) {
-        let shader_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Edge Detection Shader"),
-            source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(EDGE_DETECTION_SHADER)),
-        });
-
-        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("Edge Detection Bind Group Layout"),
-            entries: &[
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
-                        view_dimension: wgpu::TextureViewDimension::D2,
-                        multisampled: false,
+        let shader_source = shaders.source(SHADER_NAME).expect("shader not registered");
+
+        let shader_module = pipelines.shader_module(device, "Edge Detection Shader", shader_source);
+
+        let bind_group_layout = pipelines.bind_group_layout(
+            device,
+            &wgpu::BindGroupLayoutDescriptor {
+                label: Some("Edge Detection Bind Group Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
                     },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
-                    count: None,
-                },
-            ],
-        });
-
-        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Edge Detection Pipeline Layout"),
-            bind_group_layouts: &[&bind_group_layout],
-            push_constant_ranges: &[],
-        });
-
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Edge Detection Pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader_module,
-                entry_point: Some("vertex_main"),
-                buffers: &[],
-                compilation_options: Default::default(),
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
             },
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                unclipped_depth: false,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                conservative: false,
+        );
+
+        let pipeline_layout = pipelines.pipeline_layout(
+            device,
+            &wgpu::PipelineLayoutDescriptor {
+                label: Some("Edge Detection Pipeline Layout"),
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
             },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
+        );
+
+        let pipeline = pipelines.render_pipeline(
+            device,
+            &wgpu::RenderPipelineDescriptor {
+                label: Some("Edge Detection Pipeline"),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader_module,
+                    entry_point: Some("vertex_main"),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: None,
+                    unclipped_depth: false,
+                    polygon_mode: wgpu::PolygonMode::Fill,
+                    conservative: false,
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState {
+                    count: 1,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader_module,
+                    entry_point: Some("fragment_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: surface_format,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                multiview: None,
+                cache: None,
             },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader_module,
-                entry_point: Some("fragment_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_format,
-                    blend: None,
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            multiview: None,
-            cache: None,
-        });
+        );
 
         (Arc::new(pipeline), Arc::new(bind_group_layout))
     }
@@ -172,37 +134,74 @@ impl PassNode<crate::pass_configs::PassConfigs> for EdgeDetectionPass {
         vec!["output"]
     }
 
-    fn invalidate_bind_groups(&mut self) {
-        self.cached_bind_group = None;
+    fn reload_shaders(
+        &mut self,
+        device: &wgpu::Device,
+        pipelines: &mut PipelineRegistry,
+        shaders: &ShaderLibrary,
+        changed: &[String],
+    ) -> bool {
+        let surface_format = self.data.surface_format;
+        let mut reloaded = false;
+
+        if let Some((pipeline, bind_group_layout)) =
+            super::reload_pipeline(device, pipelines, SHADER_NAME, changed, |pipelines| {
+                Self::create_pipeline(device, pipelines, surface_format, shaders)
+            })
+        {
+            self.data.pipeline = pipeline;
+            self.data.bind_group_layout = bind_group_layout;
+            reloaded = true;
+        }
+
+        if let Some((blit_pipeline, blit_bind_group_layout)) = super::reload_pipeline(
+            device,
+            pipelines,
+            blit_pass::SHADER_NAME,
+            changed,
+            |pipelines| BlitPass::create_pipeline(device, pipelines, surface_format, shaders),
+        ) {
+            self.data.blit_pipeline = blit_pipeline;
+            self.data.blit_bind_group_layout = blit_bind_group_layout;
+            reloaded = true;
+        }
+
+        reloaded
     }
 
     fn execute<'r, 'e>(
         &mut self,
-        context: PassExecutionContext<'r, 'e, crate::pass_configs::PassConfigs>,
+        mut context: PassExecutionContext<'r, 'e, crate::pass_configs::PassConfigs>,
     ) -> wgpu_render_graph::Result<Vec<wgpu_render_graph::SubGraphRunCommand<'r>>> {
-        if self.cached_bind_group.is_none() {
-            let input_view = context.get_texture_view("input")?;
-
-            self.cached_bind_group = Some(context.device.create_bind_group(
-                &wgpu::BindGroupDescriptor {
-                    label: Some("Edge Detection Bind Group"),
-                    layout: &self.data.bind_group_layout,
-                    entries: &[
-                        wgpu::BindGroupEntry {
-                            binding: 0,
-                            resource: wgpu::BindingResource::TextureView(input_view),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 1,
-                            resource: wgpu::BindingResource::Sampler(&self.data.sampler),
-                        },
+        let config = &context.configs.edge_detection;
+
+        let (pipeline, bind_group) = if config.enabled {
+            (
+                &self.data.pipeline,
+                context.bind_group(
+                    "Edge Detection Bind Group",
+                    &self.data.bind_group_layout,
+                    &[
+                        BindGroupSource::Slot("input"),
+                        BindGroupSource::Sampler(&self.data.sampler),
                     ],
-                },
-            ));
-        }
+                )?,
+            )
+        } else {
+            (
+                &self.data.blit_pipeline,
+                context.bind_group(
+                    "Blit Bind Group (for disabled edge detection)",
+                    &self.data.blit_bind_group_layout,
+                    &[
+                        BindGroupSource::Slot("input"),
+                        BindGroupSource::Sampler(&self.data.sampler),
+                    ],
+                )?,
+            )
+        };
 
-        let config = &context.configs.edge_detection;
-        let (color_view, color_load_op, color_store_op) = context.get_color_attachment("output")?;
+        let color_attachment = context.color_attachment("output")?;
 
         let mut render_pass = context
             .encoder
@@ -212,27 +211,14 @@ impl PassNode<crate::pass_configs::PassConfigs> for EdgeDetectionPass {
                 } else {
                     Some("Passthrough Blit Render Pass")
                 },
-                color_attachments: &[Some(RenderPassColorAttachment {
-                    view: color_view,
-                    resolve_target: None,
-                    ops: Operations {
-                        load: color_load_op,
-                        store: color_store_op,
-                    },
-                })],
+                color_attachments: &[Some(color_attachment)],
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
             });
 
-        let pipeline = if config.enabled {
-            &self.data.pipeline
-        } else {
-            &self.data.blit_pipeline
-        };
-
         render_pass.set_pipeline(pipeline);
-        render_pass.set_bind_group(0, self.cached_bind_group.as_ref().unwrap(), &[]);
+        render_pass.set_bind_group(0, &bind_group, &[]);
         render_pass.draw(0..3, 0..1);
         drop(render_pass);
 
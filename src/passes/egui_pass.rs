@@ -1,4 +1,3 @@
-use wgpu::{Operations, RenderPassColorAttachment};
 use wgpu_render_graph::{PassExecutionContext, PassNode};
 
 pub struct EguiPass;
@@ -31,21 +30,13 @@ impl PassNode<crate::pass_configs::PassConfigs> for EguiPass {
         context: PassExecutionContext<'r, 'e, crate::pass_configs::PassConfigs>,
     ) -> wgpu_render_graph::Result<Vec<wgpu_render_graph::SubGraphRunCommand<'r>>> {
         let config = &context.configs.egui;
-        let (color_view, color_load_op, color_store_op) =
-            context.get_color_attachment("color_target")?;
+        let color_attachment = context.color_attachment("color_target")?;
 
         let render_pass = context
             .encoder
             .begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Egui Render Pass"),
-                color_attachments: &[Some(RenderPassColorAttachment {
-                    view: color_view,
-                    resolve_target: None,
-                    ops: Operations {
-                        load: color_load_op,
-                        store: color_store_op,
-                    },
-                })],
+                color_attachments: &[Some(color_attachment)],
                 depth_stencil_attachment: None,
                 timestamp_writes: None,
                 occlusion_query_set: None,
@@ -1,10 +1,8 @@
 use std::sync::Arc;
-use wgpu::{
-    BindGroup, Buffer, IndexFormat, Operations, RenderPassColorAttachment,
-    RenderPassDepthStencilAttachment, RenderPipeline,
-};
+use wgpu::{BindGroup, Buffer, IndexFormat, RenderPipeline};
 use wgpu_render_graph::{PassExecutionContext, PassNode};
 
+#[derive(Clone)]
 pub struct ScenePassData {
     pub pipeline: Arc<RenderPipeline>,
     pub vertex_buffer: Arc<Buffer>,
@@ -41,31 +39,15 @@ impl PassNode<crate::pass_configs::PassConfigs> for ScenePass {
         &mut self,
         context: PassExecutionContext<'r, 'e, crate::pass_configs::PassConfigs>,
     ) -> wgpu_render_graph::Result<Vec<wgpu_render_graph::SubGraphRunCommand<'r>>> {
-        let (color_view, color_load_op, color_store_op) =
-            context.get_color_attachment("color_output")?;
-        let (depth_view, depth_load_op, depth_store_op) =
-            context.get_depth_attachment("depth_output")?;
+        let color_attachment = context.color_attachment("color_output")?;
+        let depth_stencil_attachment = context.depth_stencil_attachment("depth_output")?;
 
         let mut render_pass = context
             .encoder
             .begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("Scene Render Pass"),
-                color_attachments: &[Some(RenderPassColorAttachment {
-                    view: color_view,
-                    resolve_target: None,
-                    ops: Operations {
-                        load: color_load_op,
-                        store: color_store_op,
-                    },
-                })],
-                depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
-                    view: depth_view,
-                    depth_ops: Some(Operations {
-                        load: depth_load_op,
-                        store: depth_store_op,
-                    }),
-                    stencil_ops: None,
-                }),
+                color_attachments: &[Some(color_attachment)],
+                depth_stencil_attachment: Some(depth_stencil_attachment),
                 timestamp_writes: None,
                 occlusion_query_set: None,
             });
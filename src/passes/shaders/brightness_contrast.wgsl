@@ -0,0 +1,39 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vertex_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32((vertex_index & 1u) << 1u);
+    let y = f32((vertex_index & 2u));
+    out.position = vec4<f32>(x * 2.0 - 1.0, y * 2.0 - 1.0, 0.0, 1.0);
+    out.uv = vec2<f32>(x, 1.0 - y);
+    return out;
+}
+
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+
+@group(0) @binding(1)
+var input_sampler: sampler;
+
+struct Uniforms {
+    brightness: f32,
+    contrast: f32,
+};
+
+@group(0) @binding(2)
+var<uniform> uniforms: Uniforms;
+
+@fragment
+fn fragment_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    var color = textureSample(input_texture, input_sampler, in.uv).rgb;
+
+    color = color + uniforms.brightness;
+
+    color = (color - 0.5) * uniforms.contrast + 0.5;
+
+    return vec4<f32>(color, 1.0);
+}
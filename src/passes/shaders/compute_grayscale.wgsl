@@ -0,0 +1,36 @@
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+
+@group(0) @binding(1)
+var output_texture: texture_storage_2d<rgba8unorm, write>;
+
+struct Uniforms {
+    enabled: u32,
+    padding1: u32,
+    padding2: u32,
+    padding3: u32,
+};
+
+@group(0) @binding(2)
+var<uniform> uniforms: Uniforms;
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let texture_size = textureDimensions(input_texture);
+
+    if (global_id.x >= texture_size.x || global_id.y >= texture_size.y) {
+        return;
+    }
+
+    let coords = vec2<i32>(i32(global_id.x), i32(global_id.y));
+    let color = textureLoad(input_texture, coords, 0);
+
+    var output_color = color;
+
+    if (uniforms.enabled != 0u) {
+        let luminance = dot(color.rgb, vec3<f32>(0.299, 0.587, 0.114));
+        output_color = vec4<f32>(luminance, luminance, luminance, color.a);
+    }
+
+    textureStore(output_texture, vec2<i32>(i32(global_id.x), i32(global_id.y)), output_color);
+}
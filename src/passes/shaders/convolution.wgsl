@@ -0,0 +1,58 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vertex_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let x = f32((vertex_index & 1u) << 1u);
+    let y = f32((vertex_index & 2u));
+    out.position = vec4<f32>(x * 2.0 - 1.0, y * 2.0 - 1.0, 0.0, 1.0);
+    out.uv = vec2<f32>(x, 1.0 - y);
+    return out;
+}
+
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+
+@group(0) @binding(1)
+var input_sampler: sampler;
+
+struct ConvolutionKernel {
+    row0: vec4<f32>,
+    row1: vec4<f32>,
+    row2: vec4<f32>,
+};
+
+@group(0) @binding(2)
+var<uniform> kernel: ConvolutionKernel;
+
+@fragment
+fn fragment_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texture_size = textureDimensions(input_texture);
+    let texel_size = 1.0 / vec2<f32>(f32(texture_size.x), f32(texture_size.y));
+
+    var result = vec3<f32>(0.0);
+
+    for (var y: i32 = -1; y <= 1; y++) {
+        for (var x: i32 = -1; x <= 1; x++) {
+            let offset = vec2<f32>(f32(x), f32(y)) * texel_size;
+            let sample_uv = in.uv + offset;
+            let sample_color = textureSample(input_texture, input_sampler, sample_uv).rgb;
+
+            var kernel_value = 0.0;
+            if y == -1 {
+                kernel_value = kernel.row0[x + 1];
+            } else if y == 0 {
+                kernel_value = kernel.row1[x + 1];
+            } else {
+                kernel_value = kernel.row2[x + 1];
+            }
+
+            result += sample_color * kernel_value;
+        }
+    }
+
+    return vec4<f32>(result, 1.0);
+}
@@ -0,0 +1,12 @@
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+
+@group(0) @binding(1)
+var input_sampler: sampler;
+
+@fragment
+fn fragment_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(input_texture, input_sampler, in.uv).rgb;
+    let luminance = dot(color, vec3<f32>(0.299, 0.587, 0.114));
+    return vec4<f32>(vec3<f32>(luminance), 1.0);
+}
@@ -0,0 +1,37 @@
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+
+@group(0) @binding(1)
+var input_sampler: sampler;
+
+struct SharpenUniforms {
+    strength: f32,
+    padding1: f32,
+    padding2: f32,
+    padding3: f32,
+};
+
+@group(0) @binding(2)
+var<uniform> uniforms: SharpenUniforms;
+
+@fragment
+fn fragment_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let tex_size = textureDimensions(input_texture);
+    let texel_size = vec2<f32>(1.0 / f32(tex_size.x), 1.0 / f32(tex_size.y));
+
+    let center = textureSample(input_texture, input_sampler, in.uv).rgb;
+
+    var laplacian = center * 8.0;
+    laplacian -= textureSample(input_texture, input_sampler, in.uv + vec2<f32>(-texel_size.x, 0.0)).rgb;
+    laplacian -= textureSample(input_texture, input_sampler, in.uv + vec2<f32>(texel_size.x, 0.0)).rgb;
+    laplacian -= textureSample(input_texture, input_sampler, in.uv + vec2<f32>(0.0, -texel_size.y)).rgb;
+    laplacian -= textureSample(input_texture, input_sampler, in.uv + vec2<f32>(0.0, texel_size.y)).rgb;
+    laplacian -= textureSample(input_texture, input_sampler, in.uv + vec2<f32>(-texel_size.x, -texel_size.y)).rgb;
+    laplacian -= textureSample(input_texture, input_sampler, in.uv + vec2<f32>(texel_size.x, -texel_size.y)).rgb;
+    laplacian -= textureSample(input_texture, input_sampler, in.uv + vec2<f32>(-texel_size.x, texel_size.y)).rgb;
+    laplacian -= textureSample(input_texture, input_sampler, in.uv + vec2<f32>(texel_size.x, texel_size.y)).rgb;
+
+    let sharpened = center + laplacian * uniforms.strength;
+
+    return vec4<f32>(clamp(sharpened, vec3<f32>(0.0), vec3<f32>(1.0)), 1.0);
+}
@@ -0,0 +1,29 @@
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+
+@group(0) @binding(1)
+var input_sampler: sampler;
+
+struct VignetteUniforms {
+    strength: f32,
+    radius: f32,
+    color_tint: vec3<f32>,
+    padding: f32,
+};
+
+@group(0) @binding(2)
+var<uniform> uniforms: VignetteUniforms;
+
+@fragment
+fn fragment_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(input_texture, input_sampler, in.uv).rgb;
+
+    let center = vec2<f32>(0.5, 0.5);
+    let distance = length(in.uv - center);
+
+    let vignette = 1.0 - smoothstep(uniforms.radius, 1.0, distance * uniforms.strength);
+
+    let tinted_color = mix(uniforms.color_tint, color, vignette);
+
+    return vec4<f32>(tinted_color, 1.0);
+}
@@ -0,0 +1,171 @@
+use std::sync::Mutex;
+use wgpu::{
+    Buffer, BufferDescriptor, BufferUsages, CommandEncoder, Device, Texture, TextureDescriptor,
+    TextureFormat, TextureUsages,
+};
+
+pub trait RenderGraphBackend {
+    fn device(&self) -> &Device;
+
+    fn create_texture(&self, descriptor: &TextureDescriptor) -> Texture {
+        self.device().create_texture(descriptor)
+    }
+
+    fn create_buffer(&self, descriptor: &BufferDescriptor) -> Buffer {
+        self.device().create_buffer(descriptor)
+    }
+
+    fn create_command_encoder(&self, label: &str) -> CommandEncoder {
+        self.device()
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(label) })
+    }
+
+    fn pass_prepared(&self, _pass: &str) {}
+
+    fn pass_executed(&self, _pass: &str) {}
+}
+
+impl RenderGraphBackend for Device {
+    fn device(&self) -> &Device {
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackendEvent {
+    TextureCreated {
+        label: String,
+        format: TextureFormat,
+        width: u32,
+        height: u32,
+        depth_or_array_layers: u32,
+        mip_level_count: u32,
+        sample_count: u32,
+        usage: TextureUsages,
+    },
+    BufferCreated {
+        label: String,
+        size: u64,
+        usage: BufferUsages,
+    },
+    CommandEncoderCreated {
+        label: String,
+    },
+    PassPrepared {
+        pass: String,
+    },
+    PassExecuted {
+        pass: String,
+    },
+}
+
+pub struct RecordingBackend<'a> {
+    device: &'a Device,
+    events: Mutex<Vec<BackendEvent>>,
+}
+
+impl<'a> RecordingBackend<'a> {
+    pub fn new(device: &'a Device) -> Self {
+        Self {
+            device,
+            events: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn events(&self) -> Vec<BackendEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    pub fn take_events(&self) -> Vec<BackendEvent> {
+        std::mem::take(&mut *self.events.lock().unwrap())
+    }
+
+    pub fn executed_passes(&self) -> Vec<String> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                BackendEvent::PassExecuted { pass } => Some(pass.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn created_textures(&self) -> Vec<String> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                BackendEvent::TextureCreated { label, .. } => Some(label.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn created_buffers(&self) -> Vec<String> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                BackendEvent::BufferCreated { label, .. } => Some(label.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn record(&self, event: BackendEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+impl RenderGraphBackend for RecordingBackend<'_> {
+    fn device(&self) -> &Device {
+        self.device
+    }
+
+    fn create_texture(&self, descriptor: &TextureDescriptor) -> Texture {
+        self.record(BackendEvent::TextureCreated {
+            label: descriptor.label.unwrap_or_default().to_string(),
+            format: descriptor.format,
+            width: descriptor.size.width,
+            height: descriptor.size.height,
+            depth_or_array_layers: descriptor.size.depth_or_array_layers,
+            mip_level_count: descriptor.mip_level_count,
+            sample_count: descriptor.sample_count,
+            usage: descriptor.usage,
+        });
+        self.device.create_texture(descriptor)
+    }
+
+    fn create_buffer(&self, descriptor: &BufferDescriptor) -> Buffer {
+        self.record(BackendEvent::BufferCreated {
+            label: descriptor.label.unwrap_or_default().to_string(),
+            size: descriptor.size,
+            usage: descriptor.usage,
+        });
+        self.device.create_buffer(descriptor)
+    }
+
+    fn create_command_encoder(&self, label: &str) -> CommandEncoder {
+        self.record(BackendEvent::CommandEncoderCreated {
+            label: label.to_string(),
+        });
+        self.device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(label) })
+    }
+
+    fn pass_prepared(&self, pass: &str) {
+        self.record(BackendEvent::PassPrepared {
+            pass: pass.to_string(),
+        });
+    }
+
+    fn pass_executed(&self, pass: &str) {
+        self.record(BackendEvent::PassExecuted {
+            pass: pass.to_string(),
+        });
+    }
+}
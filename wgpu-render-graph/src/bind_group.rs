@@ -0,0 +1,194 @@
+use crate::view::SlotView;
+use crate::{
+    PassExecutionContext, RenderGraph, RenderGraphResources, ResourceHandle, ResourceId, Result,
+    UploadAllocation,
+};
+use std::collections::HashMap;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindingResource, Buffer,
+    Sampler,
+};
+
+const MAX_IDLE_FRAMES: u64 = 3;
+
+#[derive(Debug, Clone, Copy)]
+pub enum BindGroupSource<'a> {
+    Slot(&'a str),
+    Sampler(&'a Sampler),
+    Upload(UploadAllocation),
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum BindingKey {
+    Resource {
+        id: ResourceId,
+        version: u64,
+        view: SlotView,
+    },
+    Sampler(Sampler),
+    Upload {
+        buffer: Buffer,
+        size: u64,
+    },
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct BindGroupKey {
+    layout: BindGroupLayout,
+    bindings: Vec<BindingKey>,
+}
+
+impl BindGroupKey {
+    fn is_current(&self, resources: &RenderGraphResources, upload_buffer: Option<&Buffer>) -> bool {
+        self.bindings.iter().all(|binding| match binding {
+            BindingKey::Resource { id, version, .. } => resources.get_version(*id) == *version,
+            BindingKey::Sampler(_) => true,
+            BindingKey::Upload { buffer, .. } => upload_buffer == Some(buffer),
+        })
+    }
+}
+
+struct CachedBindGroup {
+    bind_group: BindGroup,
+    last_used: u64,
+}
+
+#[derive(Default)]
+pub struct BindGroupCache {
+    entries: HashMap<BindGroupKey, CachedBindGroup>,
+    frame: u64,
+    created: u64,
+}
+
+impl BindGroupCache {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn created(&self) -> u64 {
+        self.created
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub(crate) fn end_frame(
+        &mut self,
+        resources: &RenderGraphResources,
+        upload_buffer: Option<&Buffer>,
+    ) {
+        let frame = self.frame;
+        self.entries.retain(|key, entry| {
+            frame - entry.last_used < MAX_IDLE_FRAMES && key.is_current(resources, upload_buffer)
+        });
+        self.frame += 1;
+    }
+}
+
+impl<'r, 'e, C> PassExecutionContext<'r, 'e, C> {
+    pub fn bind_group(
+        &mut self,
+        label: &str,
+        layout: &BindGroupLayout,
+        sources: &[BindGroupSource],
+    ) -> Result<BindGroup> {
+        let key = BindGroupKey {
+            layout: layout.clone(),
+            bindings: sources
+                .iter()
+                .map(|source| self.binding_key(source))
+                .collect::<Result<_>>()?,
+        };
+
+        let frame = self.bind_groups.frame;
+        if let Some(entry) = self.bind_groups.entries.get_mut(&key) {
+            entry.last_used = frame;
+            return Ok(entry.bind_group.clone());
+        }
+
+        let entries = sources
+            .iter()
+            .enumerate()
+            .map(|(binding, source)| {
+                Ok(BindGroupEntry {
+                    binding: binding as u32,
+                    resource: self.binding_resource(source)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let bind_group = self.device.create_bind_group(&BindGroupDescriptor {
+            label: Some(label),
+            layout,
+            entries: &entries,
+        });
+
+        self.bind_groups.created += 1;
+        self.bind_groups.entries.insert(
+            key,
+            CachedBindGroup {
+                bind_group: bind_group.clone(),
+                last_used: frame,
+            },
+        );
+        Ok(bind_group)
+    }
+
+    fn binding_key(&self, source: &BindGroupSource) -> Result<BindingKey> {
+        Ok(match source {
+            BindGroupSource::Slot(slot) => {
+                let id = self.get_slot(slot)?;
+                BindingKey::Resource {
+                    id,
+                    version: self.resources.get_version(id),
+                    view: self.slot_view(slot),
+                }
+            }
+            BindGroupSource::Sampler(sampler) => BindingKey::Sampler((*sampler).clone()),
+            BindGroupSource::Upload(allocation) => {
+                let BindingResource::Buffer(binding) = self.upload_binding(*allocation)? else {
+                    unreachable!("uploads are bound as buffers");
+                };
+                BindingKey::Upload {
+                    buffer: binding.buffer.clone(),
+                    size: allocation.size(),
+                }
+            }
+        })
+    }
+
+    fn binding_resource<'a>(&self, source: &BindGroupSource<'a>) -> Result<BindingResource<'a>>
+    where
+        'r: 'a,
+    {
+        match *source {
+            BindGroupSource::Slot(slot) => {
+                let id = self.get_slot(slot)?;
+                match self.resources.get_handle(id) {
+                    Some(
+                        ResourceHandle::ExternalBuffer { buffer }
+                        | ResourceHandle::TransientBuffer { buffer },
+                    ) => Ok(buffer.as_entire_binding()),
+                    _ => Ok(BindingResource::TextureView(self.get_texture_view(slot)?)),
+                }
+            }
+            BindGroupSource::Sampler(sampler) => Ok(BindingResource::Sampler(sampler)),
+            BindGroupSource::Upload(allocation) => self.upload_binding(allocation),
+        }
+    }
+}
+
+impl<C> RenderGraph<C> {
+    pub fn bind_group_cache(&self) -> &BindGroupCache {
+        &self.bind_groups
+    }
+
+    pub fn bind_group_cache_mut(&mut self) -> &mut BindGroupCache {
+        &mut self.bind_groups
+    }
+}
@@ -0,0 +1,286 @@
+use crate::{RenderGraph, ResourceId};
+#[cfg(feature = "serde")]
+use serde_json::{Map, Value, json};
+use web_time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PassStatus {
+    Executed,
+    Culled,
+    Disabled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CaptureSpan {
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SlotCapture {
+    pub slot: String,
+    pub resource: ResourceId,
+    pub resource_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PassCapture {
+    pub name: String,
+    pub graph: Option<String>,
+    pub status: PassStatus,
+    pub prepare: Option<CaptureSpan>,
+    pub record: Option<CaptureSpan>,
+    pub slots: Vec<SlotCapture>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SubGraphCapture {
+    pub name: String,
+    pub invoked_by: String,
+    pub inputs: usize,
+    pub span: CaptureSpan,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FrameCapture {
+    pub duration: Duration,
+    pub passes: Vec<PassCapture>,
+    pub sub_graphs: Vec<SubGraphCapture>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    start: Instant,
+}
+
+impl FrameCapture {
+    pub(crate) fn begin(start: Instant) -> Self {
+        Self {
+            duration: Duration::ZERO,
+            passes: Vec::new(),
+            sub_graphs: Vec::new(),
+            start,
+        }
+    }
+
+    pub(crate) fn start(&self) -> Instant {
+        self.start
+    }
+
+    pub(crate) fn span(&self, start: Instant) -> CaptureSpan {
+        CaptureSpan {
+            start: start.duration_since(self.start),
+            duration: start.elapsed(),
+        }
+    }
+
+    fn pass_mut(&mut self, name: &str) -> &mut PassCapture {
+        let index = match self
+            .passes
+            .iter()
+            .position(|pass| pass.graph.is_none() && pass.name == name)
+        {
+            Some(index) => index,
+            None => {
+                self.passes.push(PassCapture {
+                    name: name.to_string(),
+                    graph: None,
+                    status: PassStatus::Executed,
+                    prepare: None,
+                    record: None,
+                    slots: Vec::new(),
+                });
+                self.passes.len() - 1
+            }
+        };
+        &mut self.passes[index]
+    }
+
+    pub(crate) fn record_status(&mut self, name: &str, status: PassStatus) {
+        self.pass_mut(name).status = status;
+    }
+
+    pub(crate) fn record_prepare(&mut self, name: &str, start: Instant) {
+        let span = self.span(start);
+        self.pass_mut(name).prepare = Some(span);
+    }
+
+    pub(crate) fn record_execute(&mut self, name: &str, start: Instant, slots: Vec<SlotCapture>) {
+        let span = self.span(start);
+        let pass = self.pass_mut(name);
+        pass.record = Some(span);
+        pass.slots = slots;
+    }
+
+    pub(crate) fn record_sub_graph(
+        &mut self,
+        name: &str,
+        invoked_by: &str,
+        inputs: usize,
+        start: Instant,
+        capture: Option<FrameCapture>,
+    ) {
+        let span = self.span(start);
+        self.sub_graphs.push(SubGraphCapture {
+            name: name.to_string(),
+            invoked_by: invoked_by.to_string(),
+            inputs,
+            span,
+        });
+
+        if let Some(capture) = capture {
+            self.passes
+                .extend(capture.passes.into_iter().map(|mut pass| {
+                    pass.graph = Some(match pass.graph {
+                        Some(graph) => format!("{}/{}", name, graph),
+                        None => name.to_string(),
+                    });
+                    pass
+                }));
+            self.sub_graphs.extend(capture.sub_graphs);
+        }
+    }
+
+    pub(crate) fn finish(mut self) -> Self {
+        self.duration = self.start.elapsed();
+        self
+    }
+
+    pub fn pass(&self, name: &str) -> Option<&PassCapture> {
+        self.passes.iter().find(|pass| pass.name == name)
+    }
+
+    #[cfg(feature = "serde")]
+    fn skipped_pass_time(&self, index: usize) -> Duration {
+        let graph = &self.passes[index].graph;
+        let same_graph = |pass: &&PassCapture| &pass.graph == graph;
+
+        self.passes[index + 1..]
+            .iter()
+            .filter(same_graph)
+            .find_map(|pass| pass.record.map(|record| record.start))
+            .or_else(|| {
+                self.passes[..index]
+                    .iter()
+                    .rev()
+                    .filter(same_graph)
+                    .find_map(|pass| pass.record.map(|record| record.start + record.duration))
+            })
+            .unwrap_or(Duration::ZERO)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn to_chrome_trace(&self) -> serde_json::Result<String> {
+        let mut events = vec![
+            json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": 1,
+                "tid": 1,
+                "args": { "name": "RenderGraph" },
+            }),
+            json!({
+                "name": "frame",
+                "cat": "frame",
+                "ph": "X",
+                "ts": 0.0,
+                "dur": microseconds(self.duration),
+                "pid": 1,
+                "tid": 1,
+            }),
+        ];
+
+        for (index, pass) in self.passes.iter().enumerate() {
+            let mut args = Map::new();
+            args.insert("status".to_string(), json!(pass.status));
+            if let Some(graph) = &pass.graph {
+                args.insert("graph".to_string(), json!(graph));
+            }
+
+            match pass.status {
+                PassStatus::Executed => {
+                    if let Some(prepare) = &pass.prepare {
+                        events.push(complete_event(&pass.name, "prepare", prepare, args.clone()));
+                    }
+                    if let Some(record) = &pass.record {
+                        let slots: Map<String, Value> = pass
+                            .slots
+                            .iter()
+                            .map(|slot| (slot.slot.clone(), json!(slot.resource_name)))
+                            .collect();
+                        let mut args = args.clone();
+                        args.insert("slots".to_string(), Value::Object(slots));
+                        events.push(complete_event(&pass.name, "record", record, args));
+                    }
+                }
+                PassStatus::Culled | PassStatus::Disabled => {
+                    events.push(json!({
+                        "name": pass.name,
+                        "cat": pass.status,
+                        "ph": "i",
+                        "s": "t",
+                        "ts": microseconds(self.skipped_pass_time(index)),
+                        "pid": 1,
+                        "tid": 1,
+                        "args": args,
+                    }));
+                }
+            }
+        }
+
+        for sub_graph in &self.sub_graphs {
+            let mut args = Map::new();
+            args.insert("invoked_by".to_string(), json!(sub_graph.invoked_by));
+            args.insert("inputs".to_string(), json!(sub_graph.inputs));
+            events.push(complete_event(
+                &sub_graph.name,
+                "sub_graph",
+                &sub_graph.span,
+                args,
+            ));
+        }
+
+        serde_json::to_string(&json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        }))
+    }
+}
+
+#[cfg(feature = "serde")]
+fn complete_event(
+    name: &str,
+    category: &str,
+    span: &CaptureSpan,
+    args: Map<String, Value>,
+) -> Value {
+    json!({
+        "name": name,
+        "cat": category,
+        "ph": "X",
+        "ts": microseconds(span.start),
+        "dur": microseconds(span.duration),
+        "pid": 1,
+        "tid": 1,
+        "args": args,
+    })
+}
+
+#[cfg(feature = "serde")]
+fn microseconds(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000.0
+}
+
+impl<C> RenderGraph<C> {
+    pub fn capture_next_frame(&mut self) {
+        self.capture_requested = true;
+    }
+
+    pub fn take_frame_capture(&mut self) -> Option<FrameCapture> {
+        self.frame_capture.take()
+    }
+}
@@ -0,0 +1,391 @@
+use crate::history::previous_name;
+use crate::{PassNode, RenderGraph, RenderGraphError, ResourceId, Result};
+use petgraph::stable_graph::NodeIndex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use wgpu::{BufferUsages, TextureFormat, TextureUsages};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceKind {
+    #[default]
+    Color,
+    Depth,
+    Buffer,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceDescription {
+    pub name: String,
+    pub kind: ResourceKind,
+    pub external: bool,
+    pub history: bool,
+    pub format: Option<TextureFormat>,
+    pub size: Option<[u32; 2]>,
+    pub relative_size: Option<f32>,
+    pub usage: Option<TextureUsages>,
+    pub sample_count: Option<u32>,
+    pub mip_levels: Option<u32>,
+    pub view_formats: Option<Vec<TextureFormat>>,
+    pub clear_color: Option<[f64; 4]>,
+    pub resolve_to: Option<String>,
+    pub clear_depth: Option<f32>,
+    pub clear_stencil: Option<u32>,
+    pub no_store: bool,
+    pub buffer_size: Option<u64>,
+    pub buffer_usage: Option<BufferUsages>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PassDescription {
+    #[serde(rename = "type")]
+    pub pass_type: String,
+    pub label: Option<String>,
+    pub slots: BTreeMap<String, String>,
+    pub parameters: PassParameters,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphDescription {
+    pub resources: Vec<ResourceDescription>,
+    pub passes: Vec<PassDescription>,
+}
+
+impl GraphDescription {
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|error| RenderGraphError::InvalidDescription {
+            message: error.to_string(),
+        })
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PassParameters(pub serde_json::Value);
+
+impl PassParameters {
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.0
+            .get(key)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    pub fn get_or<T: DeserializeOwned>(&self, key: &str, default: T) -> T {
+        self.get(key).unwrap_or(default)
+    }
+
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T> {
+        let value = match &self.0 {
+            serde_json::Value::Null => serde_json::Value::Object(Default::default()),
+            value => value.clone(),
+        };
+        serde_json::from_value(value).map_err(|error| RenderGraphError::InvalidDescription {
+            message: error.to_string(),
+        })
+    }
+}
+
+type PassFactory<C> = Box<dyn Fn(&PassParameters) -> Result<Box<dyn PassNode<C>>>>;
+
+pub struct PassRegistry<C = ()> {
+    factories: HashMap<String, PassFactory<C>>,
+}
+
+impl<C> PassRegistry<C> {
+    pub fn new() -> Self {
+        Self {
+            factories: HashMap::new(),
+        }
+    }
+
+    pub fn register<F>(&mut self, pass_type: &str, factory: F) -> &mut Self
+    where
+        F: Fn(&PassParameters) -> Result<Box<dyn PassNode<C>>> + 'static,
+    {
+        self.factories
+            .insert(pass_type.to_string(), Box::new(factory));
+        self
+    }
+
+    pub fn contains(&self, pass_type: &str) -> bool {
+        self.factories.contains_key(pass_type)
+    }
+
+    pub fn create(
+        &self,
+        pass_type: &str,
+        parameters: &PassParameters,
+    ) -> Result<Box<dyn PassNode<C>>> {
+        let factory =
+            self.factories
+                .get(pass_type)
+                .ok_or_else(|| RenderGraphError::UnknownPassType {
+                    pass_type: pass_type.to_string(),
+                })?;
+        factory(parameters)
+    }
+}
+
+impl<C> Default for PassRegistry<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> RenderGraph<C> {
+    pub fn from_description(
+        description: &GraphDescription,
+        registry: &PassRegistry<C>,
+    ) -> Result<(Self, HashMap<String, ResourceId>)> {
+        let mut graph = Self::new();
+        let resources = graph.load_description(description, registry)?;
+        Ok((graph, resources))
+    }
+
+    pub fn load_description(
+        &mut self,
+        description: &GraphDescription,
+        registry: &PassRegistry<C>,
+    ) -> Result<HashMap<String, ResourceId>> {
+        for pass in &description.passes {
+            if !registry.contains(&pass.pass_type) {
+                return Err(RenderGraphError::UnknownPassType {
+                    pass_type: pass.pass_type.clone(),
+                });
+            }
+
+            for (slot, resource) in &pass.slots {
+                if !description.resources.iter().any(|description| {
+                    description.name == *resource
+                        || (description.history && previous_name(&description.name) == *resource)
+                }) {
+                    return Err(RenderGraphError::UnknownResource {
+                        pass: pass.label.clone().unwrap_or(pass.pass_type.clone()),
+                        slot: slot.clone(),
+                        resource: resource.clone(),
+                    });
+                }
+            }
+        }
+
+        for resource in &description.resources {
+            if let Some(target) = &resource.resolve_to
+                && !description
+                    .resources
+                    .iter()
+                    .any(|description| description.name == *target)
+            {
+                return Err(RenderGraphError::InvalidDescription {
+                    message: format!(
+                        "resource '{}' resolves to unknown resource '{}'",
+                        resource.name, target
+                    ),
+                });
+            }
+        }
+
+        let previous_names: HashSet<String> = description
+            .resources
+            .iter()
+            .filter(|resource| resource.history)
+            .map(|resource| previous_name(&resource.name))
+            .collect();
+        let mut labels = HashSet::new();
+        let mut nodes = Vec::with_capacity(description.passes.len());
+        for pass in &description.passes {
+            let node = registry.create(&pass.pass_type, &pass.parameters)?;
+            let label = pass.label.clone().unwrap_or(node.name().to_string());
+
+            let clashes = match pass.label {
+                Some(_) => self.pass_nodes.contains_key(&label) || !labels.insert(label.clone()),
+                None => {
+                    labels.insert(label.clone());
+                    false
+                }
+            };
+            if clashes {
+                return Err(RenderGraphError::DuplicatePassName { name: label });
+            }
+
+            for slot in node
+                .reads()
+                .into_iter()
+                .chain(node.writes())
+                .chain(node.reads_writes())
+            {
+                if !pass.slots.contains_key(slot) {
+                    return Err(RenderGraphError::SlotNotMapped {
+                        pass: label,
+                        slot: slot.to_string(),
+                    });
+                }
+            }
+
+            for slot in node.writes().into_iter().chain(node.reads_writes()) {
+                let resource = &pass.slots[slot];
+                if previous_names.contains(resource) {
+                    return Err(RenderGraphError::PreviousFrameWrite {
+                        pass: label,
+                        slot: slot.to_string(),
+                        resource: resource.clone(),
+                    });
+                }
+            }
+
+            nodes.push(node);
+        }
+
+        let first_resource = self.resources.next_id;
+        let mut added = Vec::with_capacity(nodes.len());
+        let result = self.add_described(description, nodes, &mut added);
+        if result.is_err() {
+            for index in added.into_iter().rev() {
+                self.detach_pass(index);
+            }
+            self.resources.discard_resources_from(first_resource);
+        }
+        result
+    }
+
+    fn add_described(
+        &mut self,
+        description: &GraphDescription,
+        nodes: Vec<Box<dyn PassNode<C>>>,
+        added: &mut Vec<NodeIndex>,
+    ) -> Result<HashMap<String, ResourceId>> {
+        let mut resources = HashMap::new();
+        for resource in &description.resources {
+            let id = self.add_described_resource(resource);
+            resources.insert(resource.name.clone(), id);
+            if let Some(previous) = self.resources.previous(id) {
+                resources.insert(previous_name(&resource.name), previous);
+            }
+        }
+
+        for resource in &description.resources {
+            if let Some(target) = &resource.resolve_to {
+                self.resources
+                    .set_resolve_target(resources[&resource.name], Some(resources[target]));
+            }
+        }
+
+        for (pass, node) in description.passes.iter().zip(nodes) {
+            let slot_mappings: Vec<(&str, ResourceId)> = pass
+                .slots
+                .iter()
+                .map(|(slot, resource)| (slot.as_str(), resources[resource]))
+                .collect();
+
+            added.push(match &pass.label {
+                Some(label) => self.add_labeled_pass(label, node, &slot_mappings)?,
+                None => self.add_pass(node, &slot_mappings)?,
+            });
+        }
+
+        Ok(resources)
+    }
+
+    fn add_described_resource(&mut self, resource: &ResourceDescription) -> ResourceId {
+        match resource.kind {
+            ResourceKind::Color => {
+                let mut builder = self.add_color_texture(&resource.name);
+                if let Some(format) = resource.format {
+                    builder = builder.format(format);
+                }
+                if let Some([width, height]) = resource.size {
+                    builder = builder.size(width, height);
+                }
+                if let Some(scale) = resource.relative_size {
+                    builder = builder.relative_to_output(scale);
+                }
+                if let Some(usage) = resource.usage {
+                    builder = builder.usage(usage);
+                }
+                if let Some(sample_count) = resource.sample_count {
+                    builder = builder.sample_count(sample_count);
+                }
+                if let Some(mip_levels) = resource.mip_levels {
+                    builder = builder.mip_levels(mip_levels);
+                }
+                if let Some(view_formats) = &resource.view_formats {
+                    builder = builder.view_formats(view_formats);
+                }
+                if let Some([r, g, b, a]) = resource.clear_color {
+                    builder = builder.clear_color(wgpu::Color { r, g, b, a });
+                }
+                if resource.no_store {
+                    builder = builder.no_store();
+                }
+                if resource.external {
+                    builder.external()
+                } else if resource.history {
+                    builder.history()
+                } else {
+                    builder.transient()
+                }
+            }
+            ResourceKind::Depth => {
+                let mut builder = self.add_depth_texture(&resource.name);
+                if let Some(format) = resource.format {
+                    builder = builder.format(format);
+                }
+                if let Some([width, height]) = resource.size {
+                    builder = builder.size(width, height);
+                }
+                if let Some(scale) = resource.relative_size {
+                    builder = builder.relative_to_output(scale);
+                }
+                if let Some(usage) = resource.usage {
+                    builder = builder.usage(usage);
+                }
+                if let Some(sample_count) = resource.sample_count {
+                    builder = builder.sample_count(sample_count);
+                }
+                if let Some(mip_levels) = resource.mip_levels {
+                    builder = builder.mip_levels(mip_levels);
+                }
+                if let Some(view_formats) = &resource.view_formats {
+                    builder = builder.view_formats(view_formats);
+                }
+                if let Some(clear_depth) = resource.clear_depth {
+                    builder = builder.clear_depth(clear_depth);
+                }
+                if let Some(clear_stencil) = resource.clear_stencil {
+                    builder = builder.clear_stencil(clear_stencil);
+                }
+                if resource.no_store {
+                    builder = builder.no_store();
+                }
+                if resource.external {
+                    builder.external()
+                } else if resource.history {
+                    builder.history()
+                } else {
+                    builder.transient()
+                }
+            }
+            ResourceKind::Buffer => {
+                let mut builder = self.add_buffer(&resource.name);
+                if let Some(size) = resource.buffer_size {
+                    builder = builder.size(size);
+                }
+                if let Some(usage) = resource.buffer_usage {
+                    builder = builder.usage(usage);
+                }
+                if resource.external {
+                    builder.external()
+                } else {
+                    builder.transient()
+                }
+            }
+        }
+    }
+}
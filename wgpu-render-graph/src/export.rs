@@ -0,0 +1,240 @@
+use crate::{RenderGraph, ResourceId, ResourceType};
+use std::fmt::Write;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PassExport {
+    pub index: usize,
+    pub name: String,
+    pub reads: Vec<String>,
+    pub writes: Vec<String>,
+    pub reads_writes: Vec<String>,
+    pub execution_index: Option<usize>,
+    pub culled: bool,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ResourceExport {
+    pub id: ResourceId,
+    pub name: String,
+    pub kind: String,
+    pub external: bool,
+    pub history: bool,
+    pub pool_slot: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EdgeExport {
+    pub from: usize,
+    pub to: usize,
+    pub resource: ResourceId,
+    pub resource_name: String,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PoolExport {
+    pub index: usize,
+    pub resources: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GraphExport {
+    pub passes: Vec<PassExport>,
+    pub resources: Vec<ResourceExport>,
+    pub edges: Vec<EdgeExport>,
+    pub pools: Vec<PoolExport>,
+}
+
+fn resource_kind(resource_type: &ResourceType) -> &'static str {
+    match resource_type {
+        ResourceType::ExternalColor { .. } => "external_color",
+        ResourceType::TransientColor { .. } => "transient_color",
+        ResourceType::ExternalDepth { .. } => "external_depth",
+        ResourceType::TransientDepth { .. } => "transient_depth",
+        ResourceType::ExternalBuffer => "external_buffer",
+        ResourceType::TransientBuffer { .. } => "transient_buffer",
+    }
+}
+
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl<C> RenderGraph<C> {
+    pub fn export(&self) -> GraphExport {
+        let names = |ids: &[ResourceId]| -> Vec<String> {
+            ids.iter().map(|&id| self.resource_name(id)).collect()
+        };
+
+        let passes = self
+            .graph
+            .node_indices()
+            .map(|node_index| {
+                let node = &self.graph[node_index];
+                PassExport {
+                    index: node_index.index(),
+                    name: node.name.clone(),
+                    reads: names(&node.reads),
+                    writes: names(&node.writes),
+                    reads_writes: names(&node.reads_writes),
+                    execution_index: self
+                        .execution_order
+                        .iter()
+                        .position(|&ordered| ordered == node_index)
+                        .filter(|_| !self.needs_recompile),
+                    culled: !self.needs_recompile && self.culled_passes.contains(&node_index),
+                }
+            })
+            .collect();
+
+        let mut resources = self
+            .resources
+            .descriptors
+            .iter()
+            .map(|(&id, descriptor)| ResourceExport {
+                id,
+                name: descriptor.name.clone(),
+                kind: resource_kind(&descriptor.resource_type).to_string(),
+                external: descriptor.is_external,
+                history: self.resources.is_history(id),
+                pool_slot: self
+                    .aliasing_info
+                    .as_ref()
+                    .filter(|_| !self.needs_recompile)
+                    .and_then(|aliasing_info| aliasing_info.aliases.get(&id).copied()),
+            })
+            .collect::<Vec<_>>();
+        resources.sort_by_key(|resource| resource.id.0);
+
+        let mut edges = self
+            .dependency_edges()
+            .into_iter()
+            .map(|(from, to, resource)| EdgeExport {
+                from: from.index(),
+                to: to.index(),
+                resource,
+                resource_name: self.resource_name(resource),
+            })
+            .collect::<Vec<_>>();
+        edges.sort_by_key(|edge| (edge.from, edge.to));
+
+        let pools = self
+            .aliasing_info
+            .as_ref()
+            .filter(|_| !self.needs_recompile)
+            .map(|aliasing_info| {
+                (0..aliasing_info.pools.len())
+                    .map(|index| PoolExport {
+                        index,
+                        resources: resources
+                            .iter()
+                            .filter(|resource| resource.pool_slot == Some(index))
+                            .map(|resource| resource.name.clone())
+                            .collect(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        GraphExport {
+            passes,
+            resources,
+            edges,
+            pools,
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&self.export())
+    }
+
+    pub fn to_dot(&self) -> String {
+        let export = self.export();
+        let mut dot = String::new();
+
+        writeln!(dot, "digraph RenderGraph {{").unwrap();
+        writeln!(dot, "    rankdir=LR;").unwrap();
+        writeln!(dot, "    node [fontname=\"Helvetica\"];").unwrap();
+
+        for pass in &export.passes {
+            let label = match pass.execution_index {
+                Some(execution_index) => format!("{}. {}", execution_index, pass.name),
+                None => pass.name.clone(),
+            };
+            if pass.culled {
+                writeln!(
+                    dot,
+                    "    pass_{} [label=\"{}\", shape=box, style=\"filled,dashed\", fillcolor=\"#dddddd\", fontcolor=\"#888888\", color=\"#888888\"];",
+                    pass.index,
+                    escape_dot(&label)
+                )
+                .unwrap();
+            } else {
+                writeln!(
+                    dot,
+                    "    pass_{} [label=\"{}\", shape=box, style=filled, fillcolor=\"#cfe2ff\"];",
+                    pass.index,
+                    escape_dot(&label)
+                )
+                .unwrap();
+            }
+        }
+
+        for edge in &export.edges {
+            writeln!(
+                dot,
+                "    pass_{} -> pass_{} [label=\"{}\"];",
+                edge.from,
+                edge.to,
+                escape_dot(&edge.resource_name)
+            )
+            .unwrap();
+        }
+
+        for pool in &export.pools {
+            writeln!(dot, "    subgraph cluster_pool_{} {{", pool.index).unwrap();
+            writeln!(dot, "        label=\"pool_{}\";", pool.index).unwrap();
+            writeln!(dot, "        style=rounded;").unwrap();
+            for resource in export
+                .resources
+                .iter()
+                .filter(|resource| resource.pool_slot == Some(pool.index))
+            {
+                writeln!(
+                    dot,
+                    "        resource_{} [label=\"{}\", shape=note];",
+                    resource.id.0,
+                    escape_dot(&resource.name)
+                )
+                .unwrap();
+            }
+            writeln!(dot, "    }}").unwrap();
+        }
+
+        for node_index in self.graph.node_indices() {
+            let node = &self.graph[node_index];
+            for resource in node.writes.iter().chain(&node.reads_writes).filter(|id| {
+                export
+                    .resources
+                    .iter()
+                    .any(|resource| resource.id == **id && resource.pool_slot.is_some())
+            }) {
+                writeln!(
+                    dot,
+                    "    pass_{} -> resource_{} [style=dotted, arrowhead=none];",
+                    node_index.index(),
+                    resource.0
+                )
+                .unwrap();
+            }
+        }
+
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+}
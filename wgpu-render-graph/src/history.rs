@@ -0,0 +1,199 @@
+use crate::backend::RenderGraphBackend;
+use crate::{
+    ColorTextureBuilder, DepthTextureBuilder, PassExecutionContext, RenderGraph,
+    RenderGraphResources, ResourceHandle, ResourceId, ResourceType, SizeMode,
+};
+use std::sync::Arc;
+use wgpu::{StoreOp, Texture, TextureViewDescriptor};
+
+pub(crate) struct HistoryTextures {
+    textures: [Arc<Texture>; 2],
+    current: usize,
+    frames: u64,
+}
+
+pub(crate) fn previous_name(name: &str) -> String {
+    format!("{} (previous)", name)
+}
+
+impl RenderGraphResources {
+    pub fn register_history_resource(
+        &mut self,
+        name: String,
+        resource_type: ResourceType,
+    ) -> ResourceId {
+        let previous = previous_name(&name);
+        let current = self.register_transient_resource(name, resource_type.clone());
+        let previous = self.register_transient_resource(previous, resource_type);
+        self.set_size_mode(previous, SizeMode::RelativeToResource(current, 1.0));
+        self.history_pairs.insert(current, previous);
+        current
+    }
+
+    pub fn previous(&self, id: ResourceId) -> Option<ResourceId> {
+        self.history_pairs.get(&id).copied()
+    }
+
+    pub fn is_history(&self, id: ResourceId) -> bool {
+        self.history_pairs.contains_key(&id) || self.is_previous(id)
+    }
+
+    pub fn is_previous(&self, id: ResourceId) -> bool {
+        self.history_pairs.values().any(|&previous| previous == id)
+    }
+
+    pub fn is_history_valid(&self, id: ResourceId) -> bool {
+        self.history_pair(id)
+            .and_then(|(current, _)| self.histories.get(&current))
+            .is_some_and(|history| history.frames > 0)
+    }
+
+    pub(crate) fn history_pair(&self, id: ResourceId) -> Option<(ResourceId, ResourceId)> {
+        self.history_pairs
+            .iter()
+            .find(|&(&current, &previous)| current == id || previous == id)
+            .map(|(&current, &previous)| (current, previous))
+    }
+
+    pub(crate) fn discard_history(&mut self, id: ResourceId) {
+        let pairs = &self.history_pairs;
+        self.histories
+            .retain(|current, _| *current != id && pairs.get(current) != Some(&id));
+    }
+
+    pub(crate) fn allocate_history_resources<B: RenderGraphBackend + ?Sized>(
+        &mut self,
+        backend: &B,
+    ) {
+        let mut pairs: Vec<(ResourceId, ResourceId)> = self
+            .history_pairs
+            .iter()
+            .map(|(&current, &previous)| (current, previous))
+            .collect();
+        pairs.sort_by_key(|(current, _)| current.0);
+
+        let mut allocated = Vec::new();
+
+        for (current, previous) in pairs {
+            if self.handles.contains_key(&current) {
+                continue;
+            }
+
+            let Some(descriptor) = self.descriptors.get(&current) else {
+                continue;
+            };
+            let (ResourceType::TransientColor {
+                descriptor: texture_desc,
+                ..
+            }
+            | ResourceType::TransientDepth {
+                descriptor: texture_desc,
+                ..
+            }) = &descriptor.resource_type
+            else {
+                continue;
+            };
+
+            let history = self.histories.entry(current).or_insert_with(|| {
+                let textures = [0, 1].map(|index| {
+                    let label = format!("{} [{}]", descriptor.name, index);
+                    Arc::new(backend.create_texture(&texture_desc.to_wgpu_descriptor(Some(&label))))
+                });
+                HistoryTextures {
+                    textures,
+                    current: 0,
+                    frames: 0,
+                }
+            });
+
+            for (id, texture) in [
+                (current, &history.textures[history.current]),
+                (previous, &history.textures[1 - history.current]),
+            ] {
+                let view = texture.create_view(&TextureViewDescriptor {
+                    label: self
+                        .descriptors
+                        .get(&id)
+                        .map(|descriptor| descriptor.name.as_str()),
+                    ..Default::default()
+                });
+                self.handles.insert(
+                    id,
+                    ResourceHandle::TransientTexture {
+                        texture: Arc::clone(texture),
+                        view,
+                        store_op: StoreOp::Store,
+                    },
+                );
+                allocated.push(id);
+            }
+        }
+
+        for id in allocated {
+            self.increment_version(id);
+        }
+    }
+
+    pub(crate) fn advance_history(&mut self) {
+        for (current, previous) in &self.history_pairs {
+            if !self.handles.contains_key(current) {
+                continue;
+            }
+
+            if let Some(history) = self.histories.get_mut(current) {
+                history.current = 1 - history.current;
+                history.frames += 1;
+            }
+
+            self.handles.remove(current);
+            self.handles.remove(previous);
+            self.views
+                .retain(|(id, _), _| id != current && id != previous);
+        }
+    }
+}
+
+impl<'a, C> ColorTextureBuilder<'a, C> {
+    pub fn history(self) -> ResourceId {
+        let id = self.graph.resources.register_history_resource(
+            self.name,
+            ResourceType::TransientColor {
+                descriptor: self.descriptor,
+                clear_color: self.clear_color,
+            },
+        );
+        self.graph.resources.set_size_mode(id, self.size_mode);
+        self.graph
+            .resources
+            .set_resolve_target(id, self.resolve_target);
+        id
+    }
+}
+
+impl<'a, C> DepthTextureBuilder<'a, C> {
+    pub fn history(self) -> ResourceId {
+        let id = self.graph.resources.register_history_resource(
+            self.name,
+            ResourceType::TransientDepth {
+                descriptor: self.descriptor,
+                clear_depth: self.clear_depth,
+                clear_stencil: self.clear_stencil,
+            },
+        );
+        self.graph.resources.set_size_mode(id, self.size_mode);
+        id
+    }
+}
+
+impl<C> RenderGraph<C> {
+    pub fn previous(&self, id: ResourceId) -> Option<ResourceId> {
+        self.resources.previous(id)
+    }
+}
+
+impl<'r, 'e, C> PassExecutionContext<'r, 'e, C> {
+    pub fn is_history_valid(&self, slot: &str) -> bool {
+        self.get_slot(slot)
+            .is_ok_and(|resource_id| self.resources.is_history_valid(resource_id))
+    }
+}
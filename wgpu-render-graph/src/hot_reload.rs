@@ -0,0 +1,304 @@
+use crate::RenderGraph;
+use naga::valid::Capabilities;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use wgpu::Device;
+
+#[derive(Debug, Default)]
+pub struct FileWatcher {
+    files: HashMap<PathBuf, Option<SystemTime>>,
+}
+
+impl FileWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn watch(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        let modified = modified_time(&path);
+        self.files.insert(path, modified);
+    }
+
+    pub fn unwatch(&mut self, path: &Path) {
+        self.files.remove(path);
+    }
+
+    pub fn is_watching(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+
+        for (path, last_modified) in &mut self.files {
+            let modified = modified_time(path);
+            if modified.is_some() && modified != *last_modified {
+                *last_modified = modified;
+                changed.push(path.clone());
+            }
+        }
+
+        changed.sort();
+        changed
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Shader '{name}' failed to compile:\n{message}")]
+pub struct ShaderError {
+    pub name: String,
+    pub path: Option<PathBuf>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShaderReload {
+    pub reloaded: Vec<String>,
+    pub failed: Vec<ShaderError>,
+}
+
+impl ShaderReload {
+    pub fn is_empty(&self) -> bool {
+        self.reloaded.is_empty() && self.failed.is_empty()
+    }
+}
+
+#[derive(Debug)]
+struct ShaderEntry {
+    path: Option<PathBuf>,
+    prelude: Option<String>,
+    source: String,
+    generation: u64,
+}
+
+impl ShaderEntry {
+    fn compose(&self, body: &str) -> String {
+        match &self.prelude {
+            Some(prelude) => format!("{}\n{}", prelude, body),
+            None => body.to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ShaderLibrary {
+    shaders: HashMap<String, ShaderEntry>,
+    errors: HashMap<String, ShaderError>,
+    watcher: FileWatcher,
+    root: Option<PathBuf>,
+    capabilities: Capabilities,
+}
+
+impl Default for ShaderLibrary {
+    fn default() -> Self {
+        Self {
+            shaders: HashMap::new(),
+            errors: HashMap::new(),
+            watcher: FileWatcher::new(),
+            root: None,
+            capabilities: Capabilities::all(),
+        }
+    }
+}
+
+impl ShaderLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+
+    pub fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    pub fn root(&self) -> Option<&Path> {
+        self.root.as_deref()
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+
+    pub fn register_embedded(&mut self, name: &str, source: &str) {
+        self.insert(name, None, None, source);
+    }
+
+    pub fn register_file(&mut self, name: &str, path: impl Into<PathBuf>, embedded: &str) {
+        self.insert(name, Some(path.into()), None, embedded);
+    }
+
+    pub fn register_file_with_prelude(
+        &mut self,
+        name: &str,
+        path: impl Into<PathBuf>,
+        prelude: &str,
+        embedded: &str,
+    ) {
+        self.insert(name, Some(path.into()), Some(prelude.to_string()), embedded);
+    }
+
+    fn insert(
+        &mut self,
+        name: &str,
+        path: Option<PathBuf>,
+        prelude: Option<String>,
+        embedded: &str,
+    ) {
+        let mut entry = ShaderEntry {
+            path: path.and_then(|path| Some(self.root.as_ref()?.join(path))),
+            prelude,
+            source: String::new(),
+            generation: 0,
+        };
+        entry.source = entry.compose(embedded);
+
+        if let Some(path) = &entry.path {
+            self.watcher.watch(path.clone());
+        }
+
+        self.shaders.insert(name.to_string(), entry);
+        self.reload(name);
+    }
+
+    pub fn source(&self, name: &str) -> Option<&str> {
+        self.shaders.get(name).map(|entry| entry.source.as_str())
+    }
+
+    pub fn generation(&self, name: &str) -> Option<u64> {
+        self.shaders.get(name).map(|entry| entry.generation)
+    }
+
+    pub fn error(&self, name: &str) -> Option<&ShaderError> {
+        self.errors.get(name)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ShaderError> {
+        self.errors.values()
+    }
+
+    pub fn poll(&mut self) -> ShaderReload {
+        let changed_paths = self.watcher.poll();
+        let mut reload = ShaderReload::default();
+        if changed_paths.is_empty() {
+            return reload;
+        }
+
+        let mut names: Vec<String> = self
+            .shaders
+            .iter()
+            .filter(|(_, entry)| {
+                entry
+                    .path
+                    .as_ref()
+                    .is_some_and(|path| changed_paths.contains(path))
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+
+        for name in names {
+            if self.reload(&name) {
+                reload.reloaded.push(name);
+            } else if let Some(error) = self.errors.get(&name) {
+                reload.failed.push(error.clone());
+            }
+        }
+
+        reload
+    }
+
+    fn reload(&mut self, name: &str) -> bool {
+        let Some(entry) = self.shaders.get_mut(name) else {
+            return false;
+        };
+        let Some(path) = entry.path.clone() else {
+            return false;
+        };
+
+        let body = match std::fs::read_to_string(&path) {
+            Ok(body) => body,
+            Err(_) => return false,
+        };
+
+        let source = entry.compose(&body);
+        if source == entry.source {
+            self.errors.remove(name);
+            return false;
+        }
+
+        match validate_wgsl(&source, self.capabilities) {
+            Ok(()) => {
+                entry.source = source;
+                entry.generation += 1;
+                self.errors.remove(name);
+                true
+            }
+            Err(message) => {
+                self.errors.insert(
+                    name.to_string(),
+                    ShaderError {
+                        name: name.to_string(),
+                        path: Some(path),
+                        message,
+                    },
+                );
+                false
+            }
+        }
+    }
+}
+
+pub fn validate_wgsl(source: &str, capabilities: Capabilities) -> Result<(), String> {
+    let module =
+        naga::front::wgsl::parse_str(source).map_err(|error| error.emit_to_string(source))?;
+
+    naga::valid::Validator::new(naga::valid::ValidationFlags::all(), capabilities)
+        .validate(&module)
+        .map_err(|error| error.emit_to_string(source))?;
+
+    Ok(())
+}
+
+impl<C> RenderGraph<C> {
+    pub fn reload_shaders(
+        &mut self,
+        device: &Device,
+        shaders: &ShaderLibrary,
+        changed: &[String],
+    ) -> Vec<String> {
+        if changed.is_empty() {
+            return Vec::new();
+        }
+
+        let mut reloaded = Vec::new();
+
+        for node in self.graph.node_weights_mut() {
+            if node
+                .pass
+                .reload_shaders(device, &mut self.pipelines, shaders, changed)
+            {
+                node.pass.invalidate_bind_groups();
+                reloaded.push(node.name.clone());
+            }
+        }
+
+        for sub_graph in self.sub_graphs.values_mut() {
+            reloaded.extend(sub_graph.reload_shaders(device, shaders, changed));
+        }
+
+        reloaded
+    }
+}
@@ -1,9 +1,61 @@
-use petgraph::graph::{DiGraph, NodeIndex};
+mod backend;
+mod bind_group;
+mod capture;
+#[cfg(feature = "serde")]
+mod description;
+mod export;
+mod history;
+mod hot_reload;
+mod memory;
+mod pipeline;
+mod profiler;
+mod readback;
+mod report;
+mod sizing;
+mod subresource;
+mod upload;
+mod validation;
+mod view;
+
+pub use backend::{BackendEvent, RecordingBackend, RenderGraphBackend};
+pub use bind_group::{BindGroupCache, BindGroupSource};
+pub use capture::{
+    CaptureSpan, FrameCapture, PassCapture, PassStatus, SlotCapture, SubGraphCapture,
+};
+#[cfg(feature = "serde")]
+pub use description::{
+    GraphDescription, PassDescription, PassParameters, PassRegistry, ResourceDescription,
+    ResourceKind,
+};
+pub use export::{EdgeExport, GraphExport, PassExport, PoolExport, ResourceExport};
+pub use hot_reload::{FileWatcher, ShaderError, ShaderLibrary, ShaderReload, validate_wgsl};
+pub use memory::{AliasingMode, BudgetPolicy, MemoryBudget};
+pub use pipeline::{PipelineRegistry, PipelineRegistryStats};
+pub use profiler::{FrameTimings, PassTiming};
+pub use readback::{Readback, ReadbackData};
+pub use report::{
+    AliasAssignment, CompiledGraphReport, CullReason, CulledPass, PassLoadOp, PassStoreOp,
+    PoolSlotReport, ResourceStoreOp,
+};
+pub use sizing::SizeMode;
+pub use subresource::{AccessRange, SubresourceRange};
+pub use upload::UploadAllocation;
+pub use validation::{Severity, ValidationIssue};
+pub use view::TextureViewSpec;
+
+use history::HistoryTextures;
+use petgraph::stable_graph::{NodeIndex, StableDiGraph};
+use profiler::Profiler;
+use readback::ReadbackQueue;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::sync::Arc;
+use subresource::{format_subresource_span, ranges_cover, ranges_overlap, restrict_ranges};
+use upload::UploadRing;
+use view::SlotView;
+use web_time::Instant;
 use wgpu::{
     Buffer, BufferDescriptor, BufferUsages, CommandBuffer, CommandEncoder, Device, Extent3d,
-    StoreOp, Texture, TextureDescriptor, TextureFormat, TextureUsages, TextureView,
+    StoreOp, Texture, TextureAspect, TextureDescriptor, TextureFormat, TextureUsages, TextureView,
     TextureViewDescriptor,
 };
 
@@ -37,6 +89,9 @@ pub enum RenderGraphError {
     #[error("Cannot resize non-transient resource '{resource}'")]
     CannotResizeNonTransient { resource: String },
 
+    #[error("Invalid size mode for resource '{resource}': {reason}")]
+    InvalidSizeMode { resource: String, reason: String },
+
     #[error("Render graph contains cycles")]
     CyclicDependency,
 
@@ -52,17 +107,159 @@ pub enum RenderGraphError {
 
     #[error("Resource '{resource}' (id: {id:?}) not found")]
     ResourceNotFound { resource: String, id: ResourceId },
+
+    #[error(
+        "Pass '{pass}' writes over version {expected} of '{resource}', but it was already overwritten to version {latest}"
+    )]
+    VersionConflict {
+        pass: String,
+        resource: String,
+        expected: u32,
+        latest: u32,
+    },
+
+    #[error(
+        "Pass '{pass}' reads version {version} of '{resource}', which has not been written (latest is {latest})"
+    )]
+    VersionNotWritten {
+        pass: String,
+        resource: String,
+        version: u32,
+        latest: u32,
+    },
+
+    #[error(
+        "Pass '{pass}' binds version {first} of '{resource}' through slot '{first_slot}' and version {second} through slot '{second_slot}'"
+    )]
+    ConflictingVersions {
+        pass: String,
+        resource: String,
+        first_slot: String,
+        first: u32,
+        second_slot: String,
+        second: u32,
+    },
+
+    #[error("A pass labelled '{name}' already exists in the graph")]
+    DuplicatePassName { name: String },
+
+    #[error("Pass '{pass}' not found")]
+    PassNotFound { pass: String },
+
+    #[error("No pass factory registered for pass type '{pass_type}'")]
+    UnknownPassType { pass_type: String },
+
+    #[error("Pass '{pass}': slot '{slot}' refers to unknown resource '{resource}'")]
+    UnknownResource {
+        pass: String,
+        slot: String,
+        resource: String,
+    },
+
+    #[error("Pass '{pass}': slot '{slot}' has an invalid subresource of '{resource}': {reason}")]
+    InvalidSubresource {
+        pass: String,
+        slot: String,
+        resource: String,
+        reason: String,
+    },
+
+    #[error("Pass '{pass}': slot '{slot}' has an invalid view of '{resource}': {reason}")]
+    InvalidTextureView {
+        pass: String,
+        slot: String,
+        resource: String,
+        reason: String,
+    },
+
+    #[error("Invalid graph description: {message}")]
+    InvalidDescription { message: String },
+
+    #[error(
+        "Transient resources need an estimated {estimated} bytes, which exceeds the memory budget of {budget} bytes"
+    )]
+    MemoryBudgetExceeded { estimated: u64, budget: u64 },
+
+    #[error("Pass '{pass}': slot '{slot}' writes the previous-frame history resource '{resource}'")]
+    PreviousFrameWrite {
+        pass: String,
+        slot: String,
+        resource: String,
+    },
+
+    #[error("Resource '{resource}' cannot be read back: {reason}")]
+    ReadbackUnsupported { resource: String, reason: String },
+
+    #[error("Reading back resource '{resource}' failed: {reason}")]
+    ReadbackFailed { resource: String, reason: String },
+
+    #[error("Upload at offset {offset} ({size} bytes) was not made during this frame")]
+    StaleUpload { offset: u32, size: u64 },
 }
 
 pub type Result<T> = std::result::Result<T, RenderGraphError>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ResourceId(pub u32);
 
 impl ResourceId {
     pub fn new(id: u32) -> Self {
         Self(id)
     }
+
+    pub fn version(self, version: u32) -> VersionedResourceId {
+        VersionedResourceId { id: self, version }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VersionedResourceId {
+    pub id: ResourceId,
+    pub version: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotBinding {
+    pub resource: ResourceId,
+    pub version: Option<u32>,
+    pub subresource: Option<SubresourceRange>,
+    pub view: Option<TextureViewSpec>,
+}
+
+impl From<ResourceId> for SlotBinding {
+    fn from(resource: ResourceId) -> Self {
+        Self {
+            resource,
+            version: None,
+            subresource: None,
+            view: None,
+        }
+    }
+}
+
+impl From<VersionedResourceId> for SlotBinding {
+    fn from(versioned: VersionedResourceId) -> Self {
+        Self {
+            resource: versioned.id,
+            version: Some(versioned.version),
+            subresource: None,
+            view: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PassOutputs {
+    pub node: NodeIndex,
+    outputs: HashMap<String, VersionedResourceId>,
+}
+
+impl PassOutputs {
+    pub fn get(&self, slot: &str) -> Option<VersionedResourceId> {
+        self.outputs.get(slot).copied()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -75,10 +272,11 @@ pub struct RenderGraphTextureDescriptor {
     pub mip_level_count: u32,
     pub dimension: wgpu::TextureDimension,
     pub depth_or_array_layers: u32,
+    pub view_formats: Vec<TextureFormat>,
 }
 
 impl RenderGraphTextureDescriptor {
-    pub fn to_wgpu_descriptor<'a>(&self, label: Option<&'a str>) -> TextureDescriptor<'a> {
+    pub fn to_wgpu_descriptor<'a>(&'a self, label: Option<&'a str>) -> TextureDescriptor<'a> {
         TextureDescriptor {
             label,
             size: Extent3d {
@@ -91,7 +289,7 @@ impl RenderGraphTextureDescriptor {
             dimension: self.dimension,
             format: self.format,
             usage: self.usage,
-            view_formats: &[],
+            view_formats: &self.view_formats,
         }
     }
 }
@@ -125,12 +323,15 @@ pub enum ResourceType {
         clear_color: Option<wgpu::Color>,
     },
     ExternalDepth {
+        format: TextureFormat,
         clear_depth: Option<f32>,
+        clear_stencil: Option<u32>,
         force_store: bool,
     },
     TransientDepth {
         descriptor: RenderGraphTextureDescriptor,
         clear_depth: Option<f32>,
+        clear_stencil: Option<u32>,
     },
     ExternalBuffer,
     TransientBuffer {
@@ -147,6 +348,7 @@ pub struct ResourceDescriptor {
 
 pub enum ResourceHandle {
     ExternalTexture {
+        texture: Option<Texture>,
         view: TextureView,
         store_op: StoreOp,
     },
@@ -181,10 +383,23 @@ impl ResourceHandle {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentLoadOp {
+    Clear,
+    Load,
+    DontCare,
+}
+
 pub struct RenderGraphResources {
     descriptors: HashMap<ResourceId, ResourceDescriptor>,
     handles: HashMap<ResourceId, ResourceHandle>,
     versions: HashMap<ResourceId, u64>,
+    size_modes: HashMap<ResourceId, SizeMode>,
+    resolve_targets: HashMap<ResourceId, ResourceId>,
+    views: HashMap<(ResourceId, SlotView), TextureView>,
+    placements: HashMap<ResourceId, SubresourceRange>,
+    history_pairs: HashMap<ResourceId, ResourceId>,
+    histories: HashMap<ResourceId, HistoryTextures>,
     next_id: u32,
 }
 
@@ -194,14 +409,54 @@ impl RenderGraphResources {
             descriptors: HashMap::new(),
             handles: HashMap::new(),
             versions: HashMap::new(),
+            size_modes: HashMap::new(),
+            resolve_targets: HashMap::new(),
+            views: HashMap::new(),
+            placements: HashMap::new(),
+            history_pairs: HashMap::new(),
+            histories: HashMap::new(),
             next_id: 0,
         }
     }
 
+    pub fn size_mode(&self, id: ResourceId) -> SizeMode {
+        self.size_modes.get(&id).copied().unwrap_or_default()
+    }
+
+    pub fn set_size_mode(&mut self, id: ResourceId, size_mode: SizeMode) {
+        if size_mode == SizeMode::Fixed {
+            self.size_modes.remove(&id);
+        } else {
+            self.size_modes.insert(id, size_mode);
+        }
+    }
+
+    pub fn resolve_target(&self, id: ResourceId) -> Option<ResourceId> {
+        self.resolve_targets.get(&id).copied()
+    }
+
+    pub fn set_resolve_target(&mut self, id: ResourceId, target: Option<ResourceId>) {
+        match target {
+            Some(target) => self.resolve_targets.insert(id, target),
+            None => self.resolve_targets.remove(&id),
+        };
+    }
+
     pub fn get_version(&self, id: ResourceId) -> u64 {
         *self.versions.get(&id).unwrap_or(&0)
     }
 
+    fn release_transient_handles(&mut self) {
+        let descriptors = &self.descriptors;
+        self.handles.retain(|id, _| {
+            descriptors
+                .get(id)
+                .is_some_and(|descriptor| descriptor.is_external)
+        });
+        self.views.clear();
+        self.placements.clear();
+    }
+
     fn increment_version(&mut self, id: ResourceId) {
         let version = self.versions.entry(id).or_insert(0);
         *version += 1;
@@ -226,9 +481,26 @@ impl RenderGraphResources {
     }
 
     pub fn set_external_texture(&mut self, id: ResourceId, view: TextureView) {
-        self.handles.insert(
+        self.set_external_handle(
+            id,
+            ResourceHandle::ExternalTexture {
+                texture: None,
+                view,
+                store_op: StoreOp::Store,
+            },
+        );
+    }
+
+    pub fn set_external_texture_from(
+        &mut self,
+        id: ResourceId,
+        texture: &Texture,
+        view: TextureView,
+    ) {
+        self.set_external_handle(
             id,
             ResourceHandle::ExternalTexture {
+                texture: Some(texture.clone()),
                 view,
                 store_op: StoreOp::Store,
             },
@@ -236,8 +508,26 @@ impl RenderGraphResources {
     }
 
     pub fn set_external_buffer(&mut self, id: ResourceId, buffer: Arc<Buffer>) {
-        self.handles
-            .insert(id, ResourceHandle::ExternalBuffer { buffer });
+        self.set_external_handle(id, ResourceHandle::ExternalBuffer { buffer });
+    }
+
+    fn set_external_handle(&mut self, id: ResourceId, handle: ResourceHandle) {
+        let replaced = match (self.handles.get(&id), &handle) {
+            (None, _) => false,
+            (
+                Some(ResourceHandle::ExternalTexture { view: previous, .. }),
+                ResourceHandle::ExternalTexture { view, .. },
+            ) => previous != view,
+            (
+                Some(ResourceHandle::ExternalBuffer { buffer: previous }),
+                ResourceHandle::ExternalBuffer { buffer },
+            ) => previous != buffer,
+            (Some(_), _) => true,
+        };
+        if replaced {
+            self.increment_version(id);
+        }
+        self.handles.insert(id, handle);
     }
 
     pub fn register_transient_resource(
@@ -258,6 +548,22 @@ impl RenderGraphResources {
         id
     }
 
+    #[cfg(feature = "serde")]
+    pub(crate) fn discard_resources_from(&mut self, first: u32) {
+        let kept = |id: &ResourceId| id.0 < first;
+        self.descriptors.retain(|id, _| kept(id));
+        self.handles.retain(|id, _| kept(id));
+        self.versions.retain(|id, _| kept(id));
+        self.size_modes.retain(|id, _| kept(id));
+        self.resolve_targets
+            .retain(|id, target| kept(id) && kept(target));
+        self.views.retain(|(id, _), _| kept(id));
+        self.placements.retain(|id, _| kept(id));
+        self.history_pairs.retain(|id, _| kept(id));
+        self.histories.retain(|id, _| kept(id));
+        self.next_id = first;
+    }
+
     pub fn get_handle(&self, id: ResourceId) -> Option<&ResourceHandle> {
         self.handles.get(&id)
     }
@@ -356,10 +662,136 @@ impl RenderGraphResources {
         Ok((handle.view(), load_op, handle.store_op()))
     }
 
+    pub fn get_stencil_attachment(
+        &self,
+        id: ResourceId,
+    ) -> Result<(&TextureView, wgpu::LoadOp<u32>, StoreOp)> {
+        let handle = self
+            .get_handle(id)
+            .ok_or_else(|| RenderGraphError::ResourceNotBound {
+                resource: format!("stencil_attachment_{:?}", id),
+                id,
+            })?;
+        let descriptor =
+            self.get_descriptor(id)
+                .ok_or_else(|| RenderGraphError::DescriptorNotFound {
+                    resource: format!("stencil_attachment_{:?}", id),
+                    id,
+                })?;
+
+        let load_op = match &descriptor.resource_type {
+            ResourceType::ExternalDepth { clear_stencil, .. }
+            | ResourceType::TransientDepth { clear_stencil, .. } => {
+                if let Some(stencil) = clear_stencil {
+                    wgpu::LoadOp::Clear(*stencil)
+                } else {
+                    wgpu::LoadOp::Load
+                }
+            }
+            _ => {
+                return Err(RenderGraphError::TypeMismatch {
+                    operation: "get_stencil_attachment".to_string(),
+                    actual_type: match &descriptor.resource_type {
+                        ResourceType::ExternalColor { .. }
+                        | ResourceType::TransientColor { .. } => "color".to_string(),
+                        ResourceType::ExternalBuffer | ResourceType::TransientBuffer { .. } => {
+                            "buffer".to_string()
+                        }
+                        _ => "unknown".to_string(),
+                    },
+                    resource: descriptor.name.clone(),
+                });
+            }
+        };
+
+        Ok((handle.view(), load_op, handle.store_op()))
+    }
+
     pub fn get_texture_view(&self, id: ResourceId) -> Option<&TextureView> {
         self.get_handle(id).map(|handle| handle.view())
     }
 
+    pub fn get_pool_placement(&self, id: ResourceId) -> Option<SubresourceRange> {
+        self.placements.get(&id).copied()
+    }
+
+    pub fn get_resolve_target_view(&self, id: ResourceId) -> Option<&TextureView> {
+        self.resolve_target(id)
+            .and_then(|target| self.get_texture_view(target))
+    }
+
+    pub fn get_subresource_view(
+        &self,
+        id: ResourceId,
+        range: SubresourceRange,
+    ) -> Option<&TextureView> {
+        self.get_view(id, range, TextureViewSpec::default())
+    }
+
+    pub fn get_view(
+        &self,
+        id: ResourceId,
+        range: SubresourceRange,
+        spec: TextureViewSpec,
+    ) -> Option<&TextureView> {
+        let view = SlotView {
+            subresource: range,
+            spec,
+        };
+        if view.is_default() {
+            self.get_texture_view(id)
+        } else {
+            self.views.get(&(id, view))
+        }
+    }
+
+    fn create_views(&mut self, views: &[(ResourceId, SlotView)]) {
+        for &(id, view) in views {
+            if view.is_default() || self.views.contains_key(&(id, view)) {
+                continue;
+            }
+
+            let (Some(descriptor), Some(ResourceHandle::TransientTexture { texture, .. })) =
+                (self.descriptors.get(&id), self.handles.get(&id))
+            else {
+                continue;
+            };
+
+            let (ResourceType::TransientColor {
+                descriptor: texture_desc,
+                ..
+            }
+            | ResourceType::TransientDepth {
+                descriptor: texture_desc,
+                ..
+            }) = &descriptor.resource_type
+            else {
+                continue;
+            };
+
+            let label = view.label(&descriptor.name);
+            let range = match self.placements.get(&id) {
+                Some(placement) => view.subresource.within(placement),
+                None => view.subresource,
+            };
+            let texture_view = texture.create_view(&TextureViewDescriptor {
+                label: Some(&label),
+                format: view.spec.format,
+                dimension: view
+                    .spec
+                    .dimension
+                    .or_else(|| view.subresource.view_dimension(texture_desc)),
+                aspect: view.spec.aspect,
+                base_mip_level: range.base_mip_level,
+                mip_level_count: range.mip_level_count,
+                base_array_layer: range.base_array_layer,
+                array_layer_count: range.array_layer_count,
+                ..Default::default()
+            });
+            self.views.insert((id, view), texture_view);
+        }
+    }
+
     pub fn update_transient_descriptor(
         &mut self,
         id: ResourceId,
@@ -396,6 +828,7 @@ impl RenderGraphResources {
             ResourceType::TransientDepth {
                 descriptor: tex_desc,
                 clear_depth,
+                clear_stencil,
             } => ResourceType::TransientDepth {
                 descriptor: RenderGraphTextureDescriptor {
                     width,
@@ -403,6 +836,7 @@ impl RenderGraphResources {
                     ..tex_desc.clone()
                 },
                 clear_depth: *clear_depth,
+                clear_stencil: *clear_stencil,
             },
             ResourceType::TransientBuffer { .. } => {
                 return Err(RenderGraphError::CannotResizeBuffer {
@@ -424,13 +858,14 @@ impl RenderGraphResources {
                 is_external: false,
             },
         );
+        self.discard_history(id);
 
         Ok(())
     }
 
-    pub fn allocate_transient_resources_with_aliasing(
+    pub fn allocate_transient_resources_with_aliasing<B: RenderGraphBackend + ?Sized>(
         &mut self,
-        device: &Device,
+        backend: &B,
         store_ops: &HashMap<ResourceId, StoreOp>,
         aliasing_info: &mut ResourceAliasingInfo,
     ) {
@@ -440,17 +875,34 @@ impl RenderGraphResources {
             }
 
             if let Some(descriptor_info) = &pool_slot.descriptor_info {
-                let label = format!("pool_{}", pool_index);
+                let mut aliased: Vec<(ResourceId, &str)> = aliasing_info
+                    .aliases
+                    .iter()
+                    .filter(|(_, pool)| **pool == pool_index)
+                    .filter_map(|(id, _)| {
+                        self.descriptors
+                            .get(id)
+                            .map(|descriptor| (*id, descriptor.name.as_str()))
+                    })
+                    .collect();
+                aliased.sort_by_key(|(id, _)| id.0);
+
+                let label = if aliased.is_empty() {
+                    format!("pool_{}", pool_index)
+                } else {
+                    let names: Vec<&str> = aliased.iter().map(|(_, name)| *name).collect();
+                    format!("pool_{} ({})", pool_index, names.join(", "))
+                };
 
                 match descriptor_info {
                     PoolDescriptorInfo::Texture(tex_desc) => {
                         let texture_descriptor = tex_desc.to_wgpu_descriptor(Some(&label));
-                        let texture = Arc::new(device.create_texture(&texture_descriptor));
+                        let texture = Arc::new(backend.create_texture(&texture_descriptor));
                         pool_slot.resource = Some(PooledResource::Texture { texture });
                     }
                     PoolDescriptorInfo::Buffer(buf_desc) => {
                         let buffer_descriptor = buf_desc.to_wgpu_descriptor(Some(&label));
-                        let buffer = Arc::new(device.create_buffer(&buffer_descriptor));
+                        let buffer = Arc::new(backend.create_buffer(&buffer_descriptor));
                         pool_slot.resource = Some(PooledResource::Buffer { buffer });
                     }
                 }
@@ -469,7 +921,51 @@ impl RenderGraphResources {
             {
                 match &pool_slot.resource {
                     Some(PooledResource::Texture { texture, .. }) => {
-                        let view = texture.create_view(&TextureViewDescriptor::default());
+                        let placement =
+                            match (&pool_slot.descriptor_info, &descriptor.resource_type) {
+                                (
+                                    Some(PoolDescriptorInfo::Texture(pool_desc)),
+                                    ResourceType::TransientColor {
+                                        descriptor: res_desc,
+                                        ..
+                                    }
+                                    | ResourceType::TransientDepth {
+                                        descriptor: res_desc,
+                                        ..
+                                    },
+                                ) => memory::pool_placement(
+                                    pool_desc,
+                                    res_desc,
+                                    aliasing_info
+                                        .base_mip_levels
+                                        .get(resource_id)
+                                        .copied()
+                                        .unwrap_or(0),
+                                )
+                                .map(|range| {
+                                    (range, SubresourceRange::ALL.view_dimension(res_desc))
+                                }),
+                                _ => None,
+                            };
+
+                        let view = match placement {
+                            Some((range, dimension)) => {
+                                self.placements.insert(*resource_id, range);
+                                texture.create_view(&TextureViewDescriptor {
+                                    label: Some(&descriptor.name),
+                                    dimension,
+                                    base_mip_level: range.base_mip_level,
+                                    mip_level_count: range.mip_level_count,
+                                    base_array_layer: range.base_array_layer,
+                                    array_layer_count: range.array_layer_count,
+                                    ..Default::default()
+                                })
+                            }
+                            None => texture.create_view(&TextureViewDescriptor {
+                                label: Some(&descriptor.name),
+                                ..Default::default()
+                            }),
+                        };
                         let store_op = *store_ops.get(resource_id).unwrap_or(&StoreOp::Store);
 
                         self.handles.insert(
@@ -513,11 +1009,25 @@ pub struct SubGraphRunCommand<'a> {
     pub inputs: Vec<SlotValue<'a>>,
 }
 
+pub struct PassPrepareContext<'a, C = ()> {
+    pub device: &'a Device,
+    pub queue: &'a wgpu::Queue,
+    pub configs: &'a C,
+    uploads: &'a mut UploadRing,
+}
+
 pub struct PassExecutionContext<'r, 'e, C = ()> {
     pub encoder: &'e mut CommandEncoder,
     pub resources: &'r RenderGraphResources,
     pub device: &'r Device,
     slot_mappings: &'r HashMap<String, ResourceId>,
+    slot_views: Option<&'r HashMap<String, SlotView>>,
+    load_ops: Option<&'r HashMap<ResourceId, AttachmentLoadOp>>,
+    store_ops: Option<&'r HashMap<ResourceId, StoreOp>>,
+    stencil_load_ops: Option<&'r HashMap<ResourceId, AttachmentLoadOp>>,
+    stencil_store_ops: Option<&'r HashMap<ResourceId, StoreOp>>,
+    uploads: &'r UploadRing,
+    bind_groups: &'e mut BindGroupCache,
     pub configs: &'r C,
     pub(crate) sub_graph_commands: Vec<SubGraphRunCommand<'r>>,
 }
@@ -533,14 +1043,41 @@ impl<'r, 'e, C> PassExecutionContext<'r, 'e, C> {
             })
     }
 
+    fn slot_view(&self, slot: &str) -> SlotView {
+        self.slot_views
+            .and_then(|slot_views| slot_views.get(slot))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn get_subresource(&self, slot: &str) -> SubresourceRange {
+        self.slot_view(slot).subresource
+    }
+
+    pub fn get_view_spec(&self, slot: &str) -> TextureViewSpec {
+        self.slot_view(slot).spec
+    }
+
     pub fn get_texture_view(&self, slot: &str) -> Result<&'r wgpu::TextureView> {
         let resource_id = self.get_slot(slot)?;
-        self.resources.get_texture_view(resource_id).ok_or_else(|| {
-            RenderGraphError::ResourceNotBound {
+        let view = self.slot_view(slot);
+        self.resources
+            .get_view(resource_id, view.subresource, view.spec)
+            .ok_or_else(|| RenderGraphError::ResourceNotBound {
                 resource: slot.to_string(),
                 id: resource_id,
-            }
-        })
+            })
+    }
+
+    fn get_attachment_view(&self, slot: &str) -> Result<&'r wgpu::TextureView> {
+        let resource_id = self.get_slot(slot)?;
+        let view = self.slot_view(slot).attachment();
+        self.resources
+            .get_view(resource_id, view.subresource, view.spec)
+            .ok_or_else(|| RenderGraphError::ResourceNotBound {
+                resource: slot.to_string(),
+                id: resource_id,
+            })
     }
 
     pub fn get_color_attachment(
@@ -552,7 +1089,48 @@ impl<'r, 'e, C> PassExecutionContext<'r, 'e, C> {
         wgpu::StoreOp,
     )> {
         let resource_id = self.get_slot(slot)?;
-        self.resources.get_color_attachment(resource_id)
+        let (_, load_op, store_op) = self.resources.get_color_attachment(resource_id)?;
+        let view = self.get_texture_view(slot)?;
+        let load_op = match (self.load_op(resource_id), load_op) {
+            (AttachmentLoadOp::Load, _) => wgpu::LoadOp::Load,
+            (AttachmentLoadOp::DontCare, wgpu::LoadOp::Load) => {
+                wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT)
+            }
+            (_, load_op) => load_op,
+        };
+        Ok((
+            view,
+            load_op,
+            self.store_op(resource_id).unwrap_or(store_op),
+        ))
+    }
+
+    pub fn get_resolve_target(&self, slot: &str) -> Result<Option<&'r wgpu::TextureView>> {
+        let resource_id = self.get_slot(slot)?;
+        let Some(target) = self.resources.resolve_target(resource_id) else {
+            return Ok(None);
+        };
+
+        self.resources
+            .get_texture_view(target)
+            .map(Some)
+            .ok_or_else(|| RenderGraphError::ResourceNotBound {
+                resource: self
+                    .resources
+                    .get_descriptor(target)
+                    .map(|descriptor| descriptor.name.clone())
+                    .unwrap_or_else(|| format!("{:?}", target)),
+                id: target,
+            })
+    }
+
+    pub fn color_attachment(&self, slot: &str) -> Result<wgpu::RenderPassColorAttachment<'r>> {
+        let (view, load, store) = self.get_color_attachment(slot)?;
+        Ok(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: self.get_resolve_target(slot)?,
+            ops: wgpu::Operations { load, store },
+        })
     }
 
     pub fn get_depth_attachment(
@@ -560,7 +1138,99 @@ impl<'r, 'e, C> PassExecutionContext<'r, 'e, C> {
         slot: &str,
     ) -> Result<(&'r wgpu::TextureView, wgpu::LoadOp<f32>, wgpu::StoreOp)> {
         let resource_id = self.get_slot(slot)?;
-        self.resources.get_depth_attachment(resource_id)
+        let (_, load_op, store_op) = self.resources.get_depth_attachment(resource_id)?;
+        let view = self.get_attachment_view(slot)?;
+        let load_op = match (self.load_op(resource_id), load_op) {
+            (AttachmentLoadOp::Load, _) => wgpu::LoadOp::Load,
+            (AttachmentLoadOp::DontCare, wgpu::LoadOp::Load) => wgpu::LoadOp::Clear(1.0),
+            (_, load_op) => load_op,
+        };
+        Ok((
+            view,
+            load_op,
+            self.store_op(resource_id).unwrap_or(store_op),
+        ))
+    }
+
+    pub fn get_stencil_attachment(
+        &self,
+        slot: &str,
+    ) -> Result<(&'r wgpu::TextureView, wgpu::LoadOp<u32>, wgpu::StoreOp)> {
+        let resource_id = self.get_slot(slot)?;
+        let (_, load_op, store_op) = self.resources.get_stencil_attachment(resource_id)?;
+        let view = self.get_attachment_view(slot)?;
+        let load_op = match (self.stencil_load_op(resource_id), load_op) {
+            (AttachmentLoadOp::Load, _) => wgpu::LoadOp::Load,
+            (AttachmentLoadOp::DontCare, wgpu::LoadOp::Load) => wgpu::LoadOp::Clear(0),
+            (_, load_op) => load_op,
+        };
+        Ok((
+            view,
+            load_op,
+            self.stencil_store_op(resource_id).unwrap_or(store_op),
+        ))
+    }
+
+    pub fn depth_stencil_attachment(
+        &self,
+        slot: &str,
+    ) -> Result<wgpu::RenderPassDepthStencilAttachment<'r>> {
+        let resource_id = self.get_slot(slot)?;
+        let aspect = self.get_view_spec(slot).aspect;
+
+        let writes_depth = aspect != TextureAspect::StencilOnly
+            && self
+                .load_ops
+                .is_none_or(|load_ops| load_ops.contains_key(&resource_id));
+        let writes_stencil = aspect != TextureAspect::DepthOnly
+            && self
+                .stencil_load_ops
+                .is_none_or(|load_ops| load_ops.contains_key(&resource_id));
+
+        let depth_ops = if writes_depth {
+            let (_, load, store) = self.get_depth_attachment(slot)?;
+            Some(wgpu::Operations { load, store })
+        } else {
+            None
+        };
+        let stencil_ops = if writes_stencil {
+            let (_, load, store) = self.get_stencil_attachment(slot)?;
+            Some(wgpu::Operations { load, store })
+        } else {
+            None
+        };
+
+        Ok(wgpu::RenderPassDepthStencilAttachment {
+            view: self.get_attachment_view(slot)?,
+            depth_ops,
+            stencil_ops,
+        })
+    }
+
+    pub fn load_op(&self, resource_id: ResourceId) -> AttachmentLoadOp {
+        self.load_ops
+            .and_then(|load_ops| load_ops.get(&resource_id))
+            .copied()
+            .unwrap_or(AttachmentLoadOp::Clear)
+    }
+
+    pub fn store_op(&self, resource_id: ResourceId) -> Option<StoreOp> {
+        self.store_ops
+            .and_then(|store_ops| store_ops.get(&resource_id))
+            .copied()
+    }
+
+    pub fn stencil_load_op(&self, resource_id: ResourceId) -> AttachmentLoadOp {
+        self.stencil_load_ops
+            .and_then(|load_ops| load_ops.get(&resource_id))
+            .copied()
+            .unwrap_or(AttachmentLoadOp::Clear)
+    }
+
+    pub fn stencil_store_op(&self, resource_id: ResourceId) -> Option<StoreOp> {
+        self.stencil_store_ops
+            .and_then(|store_ops| store_ops.get(&resource_id))
+            .copied()
     }
 
     pub fn get_buffer(&self, slot: &str) -> Result<&'r std::sync::Arc<wgpu::Buffer>> {
@@ -600,7 +1270,13 @@ impl<'r, 'e, C> PassExecutionContext<'r, 'e, C> {
             | ResourceType::TransientDepth {
                 descriptor: texture_desc,
                 ..
-            } => Ok((texture_desc.width, texture_desc.height)),
+            } => {
+                let mip_level = self.get_subresource(slot).base_mip_level;
+                Ok((
+                    (texture_desc.width >> mip_level).max(1),
+                    (texture_desc.height >> mip_level).max(1),
+                ))
+            }
             ResourceType::ExternalColor { .. } | ResourceType::ExternalDepth { .. } => {
                 let handle = self.resources.get_handle(resource_id).ok_or_else(|| {
                     RenderGraphError::ResourceNotBound {
@@ -647,11 +1323,23 @@ pub trait PassNode<C = ()>: Send + Sync {
     fn reads_writes(&self) -> Vec<&str> {
         Vec::new()
     }
+    fn slot_usage(&self, _slot: &str) -> Option<SlotUsage> {
+        None
+    }
     fn is_enabled(&self, _configs: &C) -> bool {
         true
     }
-    fn prepare(&mut self, _device: &Device, _queue: &wgpu::Queue, _configs: &C) {}
+    fn prepare(&mut self, _context: &mut PassPrepareContext<'_, C>) {}
     fn invalidate_bind_groups(&mut self) {}
+    fn reload_shaders(
+        &mut self,
+        _device: &Device,
+        _pipelines: &mut PipelineRegistry,
+        _shaders: &ShaderLibrary,
+        _changed: &[String],
+    ) -> bool {
+        false
+    }
     fn execute<'r, 'e>(
         &mut self,
         context: PassExecutionContext<'r, 'e, C>,
@@ -663,39 +1351,172 @@ pub struct GraphNode<C> {
     pub reads: Vec<ResourceId>,
     pub writes: Vec<ResourceId>,
     pub reads_writes: Vec<ResourceId>,
+    pub read_versions: HashMap<ResourceId, u32>,
+    pub write_versions: HashMap<ResourceId, u32>,
+    pub read_ranges: HashMap<ResourceId, Vec<AccessRange>>,
+    pub write_ranges: HashMap<ResourceId, Vec<AccessRange>>,
+    pub resolves: Vec<ResourceId>,
     pub pass: Box<dyn PassNode<C>>,
 }
 
-pub enum SlotValue<'a> {
-    TextureView(&'a TextureView),
-    Buffer(&'a Arc<Buffer>),
-}
-
-#[derive(Clone)]
-pub struct SubGraphInputSlot {
-    pub name: String,
-}
-
-pub struct ColorTextureBuilder<'a, C = ()> {
-    graph: &'a mut RenderGraph<C>,
-    name: String,
-    descriptor: RenderGraphTextureDescriptor,
-    clear_color: Option<wgpu::Color>,
-    force_store: bool,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotUsage {
+    Sampled,
+    Storage,
+    Attachment,
+    Uniform,
+    Vertex,
+    Index,
+    Indirect,
+    QueryResolve,
+    CopySrc,
+    CopyDst,
 }
 
-impl<'a, C> ColorTextureBuilder<'a, C> {
-    pub fn format(mut self, format: TextureFormat) -> Self {
-        self.descriptor.format = format;
-        self
+impl SlotUsage {
+    pub fn texture_usage(self) -> TextureUsages {
+        match self {
+            SlotUsage::Sampled => TextureUsages::TEXTURE_BINDING,
+            SlotUsage::Storage => TextureUsages::STORAGE_BINDING,
+            SlotUsage::Attachment => TextureUsages::RENDER_ATTACHMENT,
+            SlotUsage::CopySrc => TextureUsages::COPY_SRC,
+            SlotUsage::CopyDst => TextureUsages::COPY_DST,
+            _ => TextureUsages::empty(),
+        }
+    }
+
+    pub fn buffer_usage(self) -> BufferUsages {
+        match self {
+            SlotUsage::Storage => BufferUsages::STORAGE,
+            SlotUsage::Uniform => BufferUsages::UNIFORM,
+            SlotUsage::Vertex => BufferUsages::VERTEX,
+            SlotUsage::Index => BufferUsages::INDEX,
+            SlotUsage::Indirect => BufferUsages::INDIRECT,
+            SlotUsage::QueryResolve => BufferUsages::QUERY_RESOLVE,
+            SlotUsage::CopySrc => BufferUsages::COPY_SRC,
+            SlotUsage::CopyDst => BufferUsages::COPY_DST,
+            _ => BufferUsages::empty(),
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            SlotUsage::Sampled => "a sampled texture",
+            SlotUsage::Storage => "storage",
+            SlotUsage::Attachment => "a render attachment",
+            SlotUsage::Uniform => "a uniform buffer",
+            SlotUsage::Vertex => "a vertex buffer",
+            SlotUsage::Index => "an index buffer",
+            SlotUsage::Indirect => "an indirect buffer",
+            SlotUsage::QueryResolve => "a query resolve target",
+            SlotUsage::CopySrc => "a copy source",
+            SlotUsage::CopyDst => "a copy destination",
+        }
+    }
+}
+
+struct BoundSlots {
+    reads: Vec<ResourceId>,
+    writes: Vec<ResourceId>,
+    reads_writes: Vec<ResourceId>,
+    read_versions: HashMap<ResourceId, u32>,
+    write_versions: HashMap<ResourceId, u32>,
+    read_ranges: HashMap<ResourceId, Vec<AccessRange>>,
+    write_ranges: HashMap<ResourceId, Vec<AccessRange>>,
+    resolves: Vec<ResourceId>,
+    mappings: HashMap<String, ResourceId>,
+    slot_views: HashMap<String, SlotView>,
+    outputs: HashMap<String, VersionedResourceId>,
+}
+
+impl BoundSlots {
+    fn into_node<C>(
+        self,
+        name: String,
+        pass: Box<dyn PassNode<C>>,
+    ) -> (
+        GraphNode<C>,
+        HashMap<String, ResourceId>,
+        HashMap<String, SlotView>,
+    ) {
+        let mut node = GraphNode {
+            name,
+            reads: Vec::new(),
+            writes: Vec::new(),
+            reads_writes: Vec::new(),
+            read_versions: HashMap::new(),
+            write_versions: HashMap::new(),
+            read_ranges: HashMap::new(),
+            write_ranges: HashMap::new(),
+            resolves: Vec::new(),
+            pass,
+        };
+        let (mappings, slot_views) = self.apply(&mut node);
+        (node, mappings, slot_views)
+    }
+
+    fn apply<C>(
+        self,
+        node: &mut GraphNode<C>,
+    ) -> (HashMap<String, ResourceId>, HashMap<String, SlotView>) {
+        node.reads = self.reads;
+        node.writes = self.writes;
+        node.reads_writes = self.reads_writes;
+        node.read_versions = self.read_versions;
+        node.write_versions = self.write_versions;
+        node.read_ranges = self.read_ranges;
+        node.write_ranges = self.write_ranges;
+        node.resolves = self.resolves;
+        (self.mappings, self.slot_views)
+    }
+}
+
+pub enum SlotValue<'a> {
+    TextureView(&'a TextureView),
+    Buffer(&'a Arc<Buffer>),
+}
+
+#[derive(Clone)]
+pub struct SubGraphInputSlot {
+    pub name: String,
+}
+
+pub struct ColorTextureBuilder<'a, C = ()> {
+    graph: &'a mut RenderGraph<C>,
+    name: String,
+    descriptor: RenderGraphTextureDescriptor,
+    size_mode: SizeMode,
+    clear_color: Option<wgpu::Color>,
+    resolve_target: Option<ResourceId>,
+    force_store: bool,
+}
+
+impl<'a, C> ColorTextureBuilder<'a, C> {
+    pub fn format(mut self, format: TextureFormat) -> Self {
+        self.descriptor.format = format;
+        self
     }
 
     pub fn size(mut self, width: u32, height: u32) -> Self {
         self.descriptor.width = width;
         self.descriptor.height = height;
+        self.size_mode = SizeMode::Fixed;
         self
     }
 
+    pub fn size_mode(mut self, size_mode: SizeMode) -> Self {
+        self.size_mode = size_mode;
+        self
+    }
+
+    pub fn relative_to_output(self, scale: f32) -> Self {
+        self.size_mode(SizeMode::RelativeToOutput(scale))
+    }
+
+    pub fn relative_to(self, resource: ResourceId, scale: f32) -> Self {
+        self.size_mode(SizeMode::RelativeToResource(resource, scale))
+    }
+
     pub fn usage(mut self, usage: TextureUsages) -> Self {
         self.descriptor.usage = usage;
         self
@@ -711,34 +1532,53 @@ impl<'a, C> ColorTextureBuilder<'a, C> {
         self
     }
 
+    pub fn view_formats(mut self, formats: &[TextureFormat]) -> Self {
+        self.descriptor.view_formats = formats.to_vec();
+        self
+    }
+
     pub fn clear_color(mut self, color: wgpu::Color) -> Self {
         self.clear_color = Some(color);
         self
     }
 
+    pub fn resolve_to(mut self, target: ResourceId) -> Self {
+        self.resolve_target = Some(target);
+        self
+    }
+
     pub fn no_store(mut self) -> Self {
         self.force_store = false;
         self
     }
 
     pub fn external(self) -> ResourceId {
-        self.graph.resources.register_external_resource(
+        let id = self.graph.resources.register_external_resource(
             self.name,
             ResourceType::ExternalColor {
                 clear_color: self.clear_color,
                 force_store: self.force_store,
             },
-        )
+        );
+        self.graph
+            .resources
+            .set_resolve_target(id, self.resolve_target);
+        id
     }
 
     pub fn transient(self) -> ResourceId {
-        self.graph.resources.register_transient_resource(
+        let id = self.graph.resources.register_transient_resource(
             self.name,
             ResourceType::TransientColor {
                 descriptor: self.descriptor,
                 clear_color: self.clear_color,
             },
-        )
+        );
+        self.graph.resources.set_size_mode(id, self.size_mode);
+        self.graph
+            .resources
+            .set_resolve_target(id, self.resolve_target);
+        id
     }
 }
 
@@ -746,7 +1586,9 @@ pub struct DepthTextureBuilder<'a, C = ()> {
     graph: &'a mut RenderGraph<C>,
     name: String,
     descriptor: RenderGraphTextureDescriptor,
+    size_mode: SizeMode,
     clear_depth: Option<f32>,
+    clear_stencil: Option<u32>,
     force_store: bool,
 }
 
@@ -759,9 +1601,23 @@ impl<'a, C> DepthTextureBuilder<'a, C> {
     pub fn size(mut self, width: u32, height: u32) -> Self {
         self.descriptor.width = width;
         self.descriptor.height = height;
+        self.size_mode = SizeMode::Fixed;
         self
     }
 
+    pub fn size_mode(mut self, size_mode: SizeMode) -> Self {
+        self.size_mode = size_mode;
+        self
+    }
+
+    pub fn relative_to_output(self, scale: f32) -> Self {
+        self.size_mode(SizeMode::RelativeToOutput(scale))
+    }
+
+    pub fn relative_to(self, resource: ResourceId, scale: f32) -> Self {
+        self.size_mode(SizeMode::RelativeToResource(resource, scale))
+    }
+
     pub fn usage(mut self, usage: TextureUsages) -> Self {
         self.descriptor.usage = usage;
         self
@@ -777,11 +1633,21 @@ impl<'a, C> DepthTextureBuilder<'a, C> {
         self
     }
 
+    pub fn view_formats(mut self, formats: &[TextureFormat]) -> Self {
+        self.descriptor.view_formats = formats.to_vec();
+        self
+    }
+
     pub fn clear_depth(mut self, depth: f32) -> Self {
         self.clear_depth = Some(depth);
         self
     }
 
+    pub fn clear_stencil(mut self, stencil: u32) -> Self {
+        self.clear_stencil = Some(stencil);
+        self
+    }
+
     pub fn no_store(mut self) -> Self {
         self.force_store = false;
         self
@@ -791,20 +1657,25 @@ impl<'a, C> DepthTextureBuilder<'a, C> {
         self.graph.resources.register_external_resource(
             self.name,
             ResourceType::ExternalDepth {
+                format: self.descriptor.format,
                 clear_depth: self.clear_depth,
+                clear_stencil: self.clear_stencil,
                 force_store: self.force_store,
             },
         )
     }
 
     pub fn transient(self) -> ResourceId {
-        self.graph.resources.register_transient_resource(
+        let id = self.graph.resources.register_transient_resource(
             self.name,
             ResourceType::TransientDepth {
                 descriptor: self.descriptor,
                 clear_depth: self.clear_depth,
+                clear_stencil: self.clear_stencil,
             },
-        )
+        );
+        self.graph.resources.set_size_mode(id, self.size_mode);
+        id
     }
 }
 
@@ -856,6 +1727,8 @@ pub struct ResourceTemplate {
     mip_level_count: u32,
     dimension: wgpu::TextureDimension,
     depth_or_array_layers: u32,
+    view_formats: Vec<TextureFormat>,
+    size_mode: SizeMode,
 }
 
 impl ResourceTemplate {
@@ -869,6 +1742,8 @@ impl ResourceTemplate {
             mip_level_count: 1,
             dimension: wgpu::TextureDimension::D2,
             depth_or_array_layers: 1,
+            view_formats: Vec::new(),
+            size_mode: SizeMode::Fixed,
         }
     }
 
@@ -877,6 +1752,15 @@ impl ResourceTemplate {
         self
     }
 
+    pub fn size_mode(mut self, size_mode: SizeMode) -> Self {
+        self.size_mode = size_mode;
+        self
+    }
+
+    pub fn relative_to_output(self, scale: f32) -> Self {
+        self.size_mode(SizeMode::RelativeToOutput(scale))
+    }
+
     pub fn sample_count(mut self, count: u32) -> Self {
         self.sample_count = count;
         self
@@ -887,6 +1771,11 @@ impl ResourceTemplate {
         self
     }
 
+    pub fn view_formats(mut self, formats: &[TextureFormat]) -> Self {
+        self.view_formats = formats.to_vec();
+        self
+    }
+
     pub fn cube_map(mut self) -> Self {
         self.dimension = wgpu::TextureDimension::D2;
         self.depth_or_array_layers = 6;
@@ -908,30 +1797,43 @@ impl ResourceTemplate {
 pub struct PassBuilder<'a, C = ()> {
     graph: &'a mut RenderGraph<C>,
     pass: Option<Box<dyn PassNode<C>>>,
-    slots: Vec<(&'static str, ResourceId)>,
+    label: Option<String>,
+    slots: Vec<(&'static str, SlotBinding)>,
 }
 
 impl<'a, C> PassBuilder<'a, C> {
-    pub fn read(mut self, slot: &'static str, resource: ResourceId) -> Self {
-        self.slots.push((slot, resource));
+    pub fn read(mut self, slot: &'static str, resource: impl Into<SlotBinding>) -> Self {
+        self.slots.push((slot, resource.into()));
+        self
+    }
+
+    pub fn write(mut self, slot: &'static str, resource: impl Into<SlotBinding>) -> Self {
+        self.slots.push((slot, resource.into()));
         self
     }
 
-    pub fn write(mut self, slot: &'static str, resource: ResourceId) -> Self {
-        self.slots.push((slot, resource));
+    pub fn slot(mut self, slot: &'static str, resource: impl Into<SlotBinding>) -> Self {
+        self.slots.push((slot, resource.into()));
         self
     }
 
-    pub fn slot(mut self, slot: &'static str, resource: ResourceId) -> Self {
-        self.slots.push((slot, resource));
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
         self
     }
+
+    pub fn build(mut self) -> Result<PassOutputs> {
+        let pass = self.pass.take().expect("pass already added");
+        let label = self.label.take();
+        let slots = std::mem::take(&mut self.slots);
+        self.graph.insert_pass(label, pass, &slots)
+    }
 }
 
 impl<'a, C> Drop for PassBuilder<'a, C> {
     fn drop(&mut self) {
         if let Some(pass) = self.pass.take() {
-            let result = self.graph.add_pass(pass, &self.slots);
+            let result = self.graph.insert_pass(self.label.take(), pass, &self.slots);
             if let Err(e) = result {
                 panic!("Failed to add render pass: {}", e);
             }
@@ -946,7 +1848,8 @@ pub struct ResourcePool<'a, C = ()> {
 
 impl<'a, C> ResourcePool<'a, C> {
     pub fn transient(&mut self, name: &str) -> ResourceId {
-        self.graph.transient_color_from_template(name, &self.template)
+        self.graph
+            .transient_color_from_template(name, &self.template)
     }
 
     pub fn transient_many(&mut self, names: &[&str]) -> Vec<ResourceId> {
@@ -954,42 +1857,79 @@ impl<'a, C> ResourcePool<'a, C> {
     }
 
     pub fn external(&mut self, name: &str) -> ResourceId {
-        self.graph.external_color_from_template(name, &self.template)
+        self.graph
+            .external_color_from_template(name, &self.template)
     }
 }
 
 pub struct RenderGraph<C = ()> {
-    graph: DiGraph<GraphNode<C>, ResourceId>,
+    graph: StableDiGraph<GraphNode<C>, ResourceId>,
     pass_nodes: HashMap<String, NodeIndex>,
-    pass_resource_mappings: HashMap<String, HashMap<String, ResourceId>>,
+    pass_resource_mappings: HashMap<NodeIndex, HashMap<String, ResourceId>>,
+    pass_slot_views: HashMap<NodeIndex, HashMap<String, SlotView>>,
     sub_graphs: HashMap<String, RenderGraph<C>>,
     sub_graph_inputs: HashMap<String, Vec<SubGraphInputSlot>>,
     resources: RenderGraphResources,
     execution_order: Vec<NodeIndex>,
     store_ops: HashMap<ResourceId, StoreOp>,
+    pass_store_ops: HashMap<NodeIndex, HashMap<ResourceId, StoreOp>>,
+    pass_stencil_store_ops: HashMap<NodeIndex, HashMap<ResourceId, StoreOp>>,
+    load_ops: HashMap<NodeIndex, HashMap<ResourceId, AttachmentLoadOp>>,
+    stencil_load_ops: HashMap<NodeIndex, HashMap<ResourceId, AttachmentLoadOp>>,
     aliasing_info: Option<ResourceAliasingInfo>,
+    aliasing_mode: AliasingMode,
+    memory_budget: Option<MemoryBudget>,
+    budget_warning: Option<u64>,
     needs_recompile: bool,
     needs_resource_reallocation: bool,
     culled_passes: std::collections::HashSet<NodeIndex>,
     resource_versions: HashMap<ResourceId, u64>,
+    latest_versions: HashMap<ResourceId, u32>,
+    profiler: Option<Profiler>,
+    readbacks: ReadbackQueue,
+    uploads: UploadRing,
+    bind_groups: BindGroupCache,
+    pipelines: PipelineRegistry,
+    output_size: Option<(u32, u32)>,
+    capture_requested: bool,
+    capture: Option<FrameCapture>,
+    frame_capture: Option<FrameCapture>,
 }
 
 impl<C> RenderGraph<C> {
     pub fn new() -> Self {
         Self {
-            graph: DiGraph::new(),
+            graph: StableDiGraph::new(),
             pass_nodes: HashMap::new(),
             pass_resource_mappings: HashMap::new(),
+            pass_slot_views: HashMap::new(),
             sub_graphs: HashMap::new(),
             sub_graph_inputs: HashMap::new(),
             resources: RenderGraphResources::new(),
             execution_order: Vec::new(),
             store_ops: HashMap::new(),
+            pass_store_ops: HashMap::new(),
+            pass_stencil_store_ops: HashMap::new(),
+            load_ops: HashMap::new(),
+            stencil_load_ops: HashMap::new(),
             aliasing_info: None,
+            aliasing_mode: AliasingMode::Exact,
+            memory_budget: None,
+            budget_warning: None,
             needs_recompile: true,
             needs_resource_reallocation: false,
             culled_passes: std::collections::HashSet::new(),
             resource_versions: HashMap::new(),
+            latest_versions: HashMap::new(),
+            profiler: None,
+            readbacks: ReadbackQueue::default(),
+            uploads: UploadRing::new(),
+            bind_groups: BindGroupCache::default(),
+            pipelines: PipelineRegistry::new(),
+            output_size: None,
+            capture_requested: false,
+            capture: None,
+            frame_capture: None,
         }
     }
 
@@ -998,68 +1938,661 @@ impl<C> RenderGraph<C> {
         pass: Box<dyn PassNode<C>>,
         slot_mappings: &[(&str, ResourceId)],
     ) -> Result<NodeIndex> {
-        let name = pass.name().to_string();
+        let bindings: Vec<(&str, SlotBinding)> = slot_mappings
+            .iter()
+            .map(|(slot, resource_id)| (*slot, SlotBinding::from(*resource_id)))
+            .collect();
+        self.add_pass_with_bindings(pass, &bindings)
+            .map(|outputs| outputs.node)
+    }
+
+    pub fn add_labeled_pass(
+        &mut self,
+        label: &str,
+        pass: Box<dyn PassNode<C>>,
+        slot_mappings: &[(&str, ResourceId)],
+    ) -> Result<NodeIndex> {
+        let bindings: Vec<(&str, SlotBinding)> = slot_mappings
+            .iter()
+            .map(|(slot, resource_id)| (*slot, SlotBinding::from(*resource_id)))
+            .collect();
+        self.insert_pass(Some(label.to_string()), pass, &bindings)
+            .map(|outputs| outputs.node)
+    }
+
+    pub fn add_pass_with_bindings(
+        &mut self,
+        pass: Box<dyn PassNode<C>>,
+        bindings: &[(&str, SlotBinding)],
+    ) -> Result<PassOutputs> {
+        self.insert_pass(None, pass, bindings)
+    }
+
+    pub fn pass_index(&self, label: &str) -> Option<NodeIndex> {
+        self.pass_nodes.get(label).copied()
+    }
+
+    pub fn pass_label(&self, index: NodeIndex) -> Option<&str> {
+        self.graph.node_weight(index).map(|node| node.name.as_str())
+    }
+
+    fn unique_pass_label(&self, base: &str) -> String {
+        if !self.pass_nodes.contains_key(base) {
+            return base.to_string();
+        }
+
+        (2..)
+            .map(|suffix| format!("{}#{}", base, suffix))
+            .find(|label| !self.pass_nodes.contains_key(label))
+            .unwrap()
+    }
+
+    fn insert_pass(
+        &mut self,
+        label: Option<String>,
+        pass: Box<dyn PassNode<C>>,
+        bindings: &[(&str, SlotBinding)],
+    ) -> Result<PassOutputs> {
+        let name = match label {
+            Some(label) => {
+                if self.pass_nodes.contains_key(&label) {
+                    return Err(RenderGraphError::DuplicatePassName { name: label });
+                }
+                label
+            }
+            None => self.unique_pass_label(pass.name()),
+        };
+        let bound = self.bind_slots(&name, pass.as_ref(), bindings, None)?;
+
+        for (&resource_id, &version) in &bound.write_versions {
+            self.latest_versions.insert(resource_id, version);
+        }
+
+        let outputs = bound.outputs.clone();
+        let (node, mappings, slot_views) = bound.into_node(name.clone(), pass);
+        let index = self.graph.add_node(node);
+        self.pass_nodes.insert(name, index);
+        self.pass_resource_mappings.insert(index, mappings);
+        if !slot_views.is_empty() {
+            self.pass_slot_views.insert(index, slot_views);
+        }
+        self.needs_recompile = true;
+        Ok(PassOutputs {
+            node: index,
+            outputs,
+        })
+    }
+
+    fn bind_slots(
+        &self,
+        name: &str,
+        pass: &dyn PassNode<C>,
+        bindings: &[(&str, SlotBinding)],
+        previous: Option<&GraphNode<C>>,
+    ) -> Result<BoundSlots> {
         let slot_names_reads = pass.reads();
         let slot_names_writes = pass.writes();
         let slot_names_reads_writes = pass.reads_writes();
 
+        let slot_bindings: HashMap<String, SlotBinding> = bindings
+            .iter()
+            .map(|(slot, binding)| (slot.to_string(), *binding))
+            .collect();
+
+        let mappings: HashMap<String, ResourceId> = slot_bindings
+            .iter()
+            .map(|(slot, binding)| (slot.clone(), binding.resource))
+            .collect();
+
+        let resolve = |slots: &[&str]| -> Result<Vec<(String, SlotBinding)>> {
+            slots
+                .iter()
+                .map(|slot| {
+                    slot_bindings
+                        .get(*slot)
+                        .map(|binding| (slot.to_string(), *binding))
+                        .ok_or_else(|| RenderGraphError::SlotNotMapped {
+                            pass: name.to_string(),
+                            slot: slot.to_string(),
+                        })
+                })
+                .collect()
+        };
+
+        let read_bindings = resolve(&slot_names_reads)?;
+        let write_bindings = resolve(&slot_names_writes)?;
+        let read_write_bindings = resolve(&slot_names_reads_writes)?;
+
+        if let Some((slot, binding)) = write_bindings
+            .iter()
+            .chain(&read_write_bindings)
+            .find(|(_, binding)| self.resources.is_previous(binding.resource))
+        {
+            return Err(RenderGraphError::PreviousFrameWrite {
+                pass: name.to_string(),
+                slot: slot.clone(),
+                resource: self.resource_name(binding.resource),
+            });
+        }
+
+        let mut slot_views = HashMap::new();
+        for (slot, binding) in &slot_bindings {
+            let view = SlotView {
+                subresource: binding.subresource.unwrap_or_default(),
+                spec: binding.view.unwrap_or_default(),
+            };
+            if !view.is_default() {
+                self.check_slot_view(name, slot, binding.resource, &view)?;
+            }
+
+            let is_attachment = slot_names_writes
+                .iter()
+                .chain(&slot_names_reads_writes)
+                .any(|written| *written == slot.as_str())
+                && pass.slot_usage(slot).unwrap_or(SlotUsage::Attachment) == SlotUsage::Attachment;
+            if is_attachment {
+                self.check_attachment_view(name, slot, binding.resource, &view)?;
+            }
+
+            if !view.is_default() {
+                slot_views.insert(slot.clone(), view);
+            }
+        }
+
+        let range_of = |slot: &str| {
+            slot_views
+                .get(slot)
+                .map(|view: &SlotView| AccessRange::new(view.subresource, view.spec.aspect))
+                .unwrap_or_default()
+        };
+
+        let mut read_ranges: HashMap<ResourceId, Vec<AccessRange>> = HashMap::new();
+        for (slot, binding) in read_bindings.iter().chain(&read_write_bindings) {
+            read_ranges
+                .entry(binding.resource)
+                .or_default()
+                .push(range_of(slot));
+        }
+
+        let mut write_ranges: HashMap<ResourceId, Vec<AccessRange>> = HashMap::new();
+        for (slot, binding) in read_write_bindings.iter().chain(&write_bindings) {
+            write_ranges
+                .entry(binding.resource)
+                .or_default()
+                .push(range_of(slot));
+        }
+
+        let mut read_versions = HashMap::new();
+        let mut read_slots: HashMap<ResourceId, &str> = HashMap::new();
+        for (slot, binding) in read_bindings.iter().chain(&read_write_bindings) {
+            let latest = self.latest_version(binding.resource).version;
+            let version = binding
+                .version
+                .or_else(|| {
+                    previous.and_then(|node| node.read_versions.get(&binding.resource).copied())
+                })
+                .or_else(|| {
+                    previous
+                        .and_then(|node| node.write_versions.get(&binding.resource))
+                        .map(|version| version - 1)
+                })
+                .unwrap_or(latest);
+            if version > latest {
+                return Err(RenderGraphError::VersionNotWritten {
+                    pass: name.to_string(),
+                    resource: self.resource_name(binding.resource),
+                    version,
+                    latest,
+                });
+            }
+            if let Some(&first) = read_versions.get(&binding.resource)
+                && first != version
+            {
+                return Err(RenderGraphError::ConflictingVersions {
+                    pass: name.to_string(),
+                    resource: self.resource_name(binding.resource),
+                    first_slot: read_slots[&binding.resource].to_string(),
+                    first,
+                    second_slot: slot.clone(),
+                    second: version,
+                });
+            }
+            read_versions.insert(binding.resource, version);
+            read_slots.insert(binding.resource, slot);
+        }
+
+        let mut write_versions = HashMap::new();
+        let mut outputs = HashMap::new();
+        for (slot, binding) in read_write_bindings.iter().chain(&write_bindings) {
+            let latest = self.latest_version(binding.resource).version;
+            if let Some(expected) = binding.version
+                && expected != latest
+            {
+                return Err(RenderGraphError::VersionConflict {
+                    pass: name.to_string(),
+                    resource: self.resource_name(binding.resource),
+                    expected,
+                    latest,
+                });
+            }
+            let version = *write_versions
+                .entry(binding.resource)
+                .or_insert_with(|| self.write_slot(binding.resource, previous));
+            outputs.insert(slot.clone(), binding.resource.version(version));
+        }
+
+        let mut resolves: Vec<ResourceId> = write_versions
+            .keys()
+            .filter_map(|&resource_id| self.resources.resolve_target(resource_id))
+            .filter(|target| !write_versions.contains_key(target))
+            .collect();
+        resolves.sort_by_key(|id| id.0);
+        resolves.dedup();
+
+        for &target in &resolves {
+            write_versions.insert(target, self.write_slot(target, previous));
+            write_ranges.insert(target, vec![AccessRange::ALL]);
+        }
+
+        Ok(BoundSlots {
+            reads: read_bindings
+                .iter()
+                .map(|(_, binding)| binding.resource)
+                .collect(),
+            writes: write_bindings
+                .iter()
+                .map(|(_, binding)| binding.resource)
+                .collect(),
+            reads_writes: read_write_bindings
+                .iter()
+                .map(|(_, binding)| binding.resource)
+                .collect(),
+            read_versions,
+            write_versions,
+            read_ranges,
+            write_ranges,
+            resolves,
+            mappings,
+            slot_views,
+            outputs,
+        })
+    }
+
+    fn write_slot(&self, resource_id: ResourceId, previous: Option<&GraphNode<C>>) -> u32 {
+        let previous_write = previous.and_then(|node| {
+            node.write_versions.get(&resource_id).copied().or_else(|| {
+                node.read_versions
+                    .get(&resource_id)
+                    .map(|version| version + 1)
+            })
+        });
+        previous_write.unwrap_or_else(|| self.latest_version(resource_id).version + 1)
+    }
+
+    fn check_slot_view(
+        &self,
+        pass: &str,
+        slot: &str,
+        resource: ResourceId,
+        view: &SlotView,
+    ) -> Result<()> {
+        let subresource_error = |reason: String| RenderGraphError::InvalidSubresource {
+            pass: pass.to_string(),
+            slot: slot.to_string(),
+            resource: self.resource_name(resource),
+            reason,
+        };
+        let view_error = |reason: String| RenderGraphError::InvalidTextureView {
+            pass: pass.to_string(),
+            slot: slot.to_string(),
+            resource: self.resource_name(resource),
+            reason,
+        };
+
+        let descriptor = match self
+            .resources
+            .get_descriptor(resource)
+            .map(|descriptor| &descriptor.resource_type)
+        {
+            Some(
+                ResourceType::TransientColor { descriptor, .. }
+                | ResourceType::TransientDepth { descriptor, .. },
+            ) => descriptor,
+            Some(ResourceType::ExternalDepth { .. })
+                if view.subresource.is_all()
+                    && view.spec.format.is_none()
+                    && view.spec.dimension.is_none() =>
+            {
+                return Ok(());
+            }
+            _ if view.subresource.is_all() => {
+                return Err(view_error(
+                    "custom views are only supported on transient textures".to_string(),
+                ));
+            }
+            _ => {
+                return Err(subresource_error(
+                    "subresource views are only supported on transient textures".to_string(),
+                ));
+            }
+        };
+
+        let range = view.subresource;
+        if !range.is_within(descriptor) {
+            let layers = match descriptor.dimension {
+                wgpu::TextureDimension::D3 => 1,
+                _ => descriptor.depth_or_array_layers,
+            };
+            return Err(subresource_error(format!(
+                "mips {} and layers {} are outside the texture's {} mip levels and {} array layers",
+                format_subresource_span(range.base_mip_level, range.mip_level_count),
+                format_subresource_span(range.base_array_layer, range.array_layer_count),
+                descriptor.mip_level_count,
+                layers,
+            )));
+        }
+
+        view.spec.check(descriptor, &range).map_err(view_error)
+    }
+
+    fn check_attachment_view(
+        &self,
+        pass: &str,
+        slot: &str,
+        resource: ResourceId,
+        view: &SlotView,
+    ) -> Result<()> {
+        let Some(
+            ResourceType::TransientColor { descriptor, .. }
+            | ResourceType::TransientDepth { descriptor, .. },
+        ) = self
+            .resources
+            .get_descriptor(resource)
+            .map(|descriptor| &descriptor.resource_type)
+        else {
+            return Ok(());
+        };
+
+        let range = view.subresource;
+        let layers = match descriptor.dimension {
+            wgpu::TextureDimension::D3 => 1,
+            _ => descriptor.depth_or_array_layers,
+        };
+        let mip_count = range.mip_level_count.unwrap_or(
+            descriptor
+                .mip_level_count
+                .saturating_sub(range.base_mip_level),
+        );
+        let layer_count = range
+            .array_layer_count
+            .unwrap_or(layers.saturating_sub(range.base_array_layer));
+
+        if mip_count != 1 || layer_count != 1 {
+            return Err(RenderGraphError::InvalidSubresource {
+                pass: pass.to_string(),
+                slot: slot.to_string(),
+                resource: self.resource_name(resource),
+                reason: format!(
+                    "render attachments must cover exactly one mip level and one array layer, but mips {} and layers {} cover {} and {}",
+                    format_subresource_span(range.base_mip_level, range.mip_level_count),
+                    format_subresource_span(range.base_array_layer, range.array_layer_count),
+                    mip_count,
+                    layer_count,
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn remove_pass(&mut self, label: &str) -> Result<Box<dyn PassNode<C>>> {
+        let index = self.require_pass(label)?;
+        self.remove_pass_by_index(index)
+    }
+
+    pub fn remove_pass_by_index(&mut self, index: NodeIndex) -> Result<Box<dyn PassNode<C>>> {
+        self.detach_pass(index)
+            .map(|(node, _)| node.pass)
+            .ok_or_else(|| RenderGraphError::PassNotFound {
+                pass: format!("{:?}", index),
+            })
+    }
+
+    pub fn replace_pass(
+        &mut self,
+        label: &str,
+        pass: Box<dyn PassNode<C>>,
+    ) -> Result<Box<dyn PassNode<C>>> {
+        let index = self.require_pass(label)?;
+        let mappings = &self.pass_resource_mappings[&index];
+        let node = &self.graph[index];
+
+        let resolve = |slots: Vec<&str>| -> Option<Vec<ResourceId>> {
+            slots
+                .iter()
+                .map(|slot| mappings.get(*slot).copied())
+                .collect()
+        };
+
+        let same_bindings = resolve(pass.reads()).as_ref() == Some(&node.reads)
+            && resolve(pass.writes()).as_ref() == Some(&node.writes)
+            && resolve(pass.reads_writes()).as_ref() == Some(&node.reads_writes);
+
+        if same_bindings {
+            self.needs_recompile = true;
+            return Ok(std::mem::replace(&mut self.graph[index].pass, pass));
+        }
+
+        let mappings = mappings.clone();
+        Self::check_slots_mapped(label, pass.as_ref(), &mappings)?;
+
+        let slot_views = self
+            .pass_slot_views
+            .get(&index)
+            .cloned()
+            .unwrap_or_default();
+        let bindings = Self::mapping_bindings(&mappings, &slot_views);
+        self.rebind_pass(index, Some(pass), &bindings)?
+            .ok_or_else(|| RenderGraphError::PassNotFound {
+                pass: label.to_string(),
+            })
+    }
+
+    pub fn set_pass_slots(
+        &mut self,
+        label: &str,
+        slot_mappings: &[(&str, ResourceId)],
+    ) -> Result<NodeIndex> {
+        let index = self.require_pass(label)?;
         let mappings: HashMap<String, ResourceId> = slot_mappings
             .iter()
             .map(|(slot, resource_id)| (slot.to_string(), *resource_id))
             .collect();
+        Self::check_slots_mapped(label, self.graph[index].pass.as_ref(), &mappings)?;
 
-        let reads: Vec<ResourceId> = slot_names_reads
+        let previous_mappings = &self.pass_resource_mappings[&index];
+        let slot_views: HashMap<String, SlotView> = self
+            .pass_slot_views
+            .get(&index)
+            .into_iter()
+            .flatten()
+            .filter(|(slot, _)| previous_mappings.get(*slot) == mappings.get(*slot))
+            .map(|(slot, view)| (slot.clone(), *view))
+            .collect();
+
+        let bindings = Self::mapping_bindings(&mappings, &slot_views);
+        self.rebind_pass(index, None, &bindings)?;
+        Ok(index)
+    }
+
+    fn check_slots_mapped(
+        label: &str,
+        pass: &dyn PassNode<C>,
+        mappings: &HashMap<String, ResourceId>,
+    ) -> Result<()> {
+        let slots = pass
+            .reads()
+            .into_iter()
+            .chain(pass.writes())
+            .chain(pass.reads_writes());
+
+        for slot in slots {
+            if !mappings.contains_key(slot) {
+                return Err(RenderGraphError::SlotNotMapped {
+                    pass: label.to_string(),
+                    slot: slot.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn mapping_bindings<'m>(
+        mappings: &'m HashMap<String, ResourceId>,
+        slot_views: &HashMap<String, SlotView>,
+    ) -> Vec<(&'m str, SlotBinding)> {
+        mappings
             .iter()
-            .map(|slot| {
-                mappings
-                    .get(*slot)
-                    .copied()
-                    .ok_or_else(|| RenderGraphError::SlotNotMapped {
-                        pass: name.clone(),
-                        slot: slot.to_string(),
-                    })
+            .map(|(slot, resource_id)| {
+                let view = slot_views.get(slot);
+                let binding = SlotBinding {
+                    subresource: view.map(|view| view.subresource),
+                    view: view.map(|view| view.spec),
+                    ..SlotBinding::from(*resource_id)
+                };
+                (slot.as_str(), binding)
             })
-            .collect::<Result<Vec<_>>>()?;
+            .collect()
+    }
+
+    fn rebind_pass(
+        &mut self,
+        index: NodeIndex,
+        pass: Option<Box<dyn PassNode<C>>>,
+        bindings: &[(&str, SlotBinding)],
+    ) -> Result<Option<Box<dyn PassNode<C>>>> {
+        let previous = &self.graph[index];
+        let bound = self.bind_slots(
+            &previous.name,
+            pass.as_deref().unwrap_or(previous.pass.as_ref()),
+            bindings,
+            Some(previous),
+        )?;
+
+        let retired: Vec<(ResourceId, u32)> = previous
+            .write_versions
+            .iter()
+            .filter(|(resource_id, _)| !bound.write_versions.contains_key(resource_id))
+            .map(|(&resource_id, &version)| (resource_id, version))
+            .collect();
+        let added: Vec<(ResourceId, u32)> = bound
+            .write_versions
+            .iter()
+            .filter(|(resource_id, _)| !previous.write_versions.contains_key(resource_id))
+            .map(|(&resource_id, &version)| (resource_id, version))
+            .collect();
+
+        for (resource_id, version) in retired {
+            self.retire_version(resource_id, version);
+        }
+        for (resource_id, version) in added {
+            if version > self.latest_version(resource_id).version {
+                self.latest_versions.insert(resource_id, version);
+            } else {
+                self.insert_version(resource_id, version);
+            }
+        }
+
+        let node = &mut self.graph[index];
+        let (mappings, slot_views) = bound.apply(node);
+        let previous_pass = pass.map(|pass| std::mem::replace(&mut node.pass, pass));
+
+        self.pass_resource_mappings.insert(index, mappings);
+        if slot_views.is_empty() {
+            self.pass_slot_views.remove(&index);
+        } else {
+            self.pass_slot_views.insert(index, slot_views);
+        }
+        self.needs_recompile = true;
+        Ok(previous_pass)
+    }
+
+    fn detach_pass(
+        &mut self,
+        index: NodeIndex,
+    ) -> Option<(GraphNode<C>, HashMap<String, ResourceId>)> {
+        let node = self.graph.remove_node(index)?;
+        self.pass_nodes.remove(&node.name);
+        let mappings = self
+            .pass_resource_mappings
+            .remove(&index)
+            .unwrap_or_default();
+        self.pass_slot_views.remove(&index);
+
+        for (&resource_id, &removed_version) in &node.write_versions {
+            self.retire_version(resource_id, removed_version);
+        }
+
+        self.needs_recompile = true;
+        Some((node, mappings))
+    }
+
+    fn retire_version(&mut self, resource_id: ResourceId, removed_version: u32) {
+        for other in self.graph.node_weights_mut() {
+            if let Some(version) = other.read_versions.get_mut(&resource_id)
+                && *version >= removed_version
+            {
+                *version -= 1;
+            }
+            if let Some(version) = other.write_versions.get_mut(&resource_id)
+                && *version > removed_version
+            {
+                *version -= 1;
+            }
+        }
+
+        if let Some(latest) = self.latest_versions.get_mut(&resource_id) {
+            *latest -= 1;
+            if *latest == 0 {
+                self.latest_versions.remove(&resource_id);
+            }
+        }
+    }
 
-        let writes: Vec<ResourceId> = slot_names_writes
-            .iter()
-            .map(|slot| {
-                mappings
-                    .get(*slot)
-                    .copied()
-                    .ok_or_else(|| RenderGraphError::SlotNotMapped {
-                        pass: name.clone(),
-                        slot: slot.to_string(),
-                    })
-            })
-            .collect::<Result<Vec<_>>>()?;
+    fn insert_version(&mut self, resource_id: ResourceId, inserted_version: u32) {
+        for other in self.graph.node_weights_mut() {
+            if let Some(version) = other.read_versions.get_mut(&resource_id)
+                && *version >= inserted_version
+            {
+                *version += 1;
+            }
+            if let Some(version) = other.write_versions.get_mut(&resource_id)
+                && *version >= inserted_version
+            {
+                *version += 1;
+            }
+        }
 
-        let reads_writes: Vec<ResourceId> = slot_names_reads_writes
-            .iter()
-            .map(|slot| {
-                mappings
-                    .get(*slot)
-                    .copied()
-                    .ok_or_else(|| RenderGraphError::SlotNotMapped {
-                        pass: name.clone(),
-                        slot: slot.to_string(),
-                    })
+        *self.latest_versions.entry(resource_id).or_insert(0) += 1;
+    }
+
+    fn require_pass(&self, label: &str) -> Result<NodeIndex> {
+        self.pass_index(label)
+            .ok_or_else(|| RenderGraphError::PassNotFound {
+                pass: label.to_string(),
             })
-            .collect::<Result<Vec<_>>>()?;
+    }
 
-        let graph_node = GraphNode {
-            name: name.clone(),
-            reads,
-            writes,
-            reads_writes,
-            pass,
-        };
+    pub fn latest_version(&self, id: ResourceId) -> VersionedResourceId {
+        id.version(self.latest_versions.get(&id).copied().unwrap_or(0))
+    }
 
-        let index = self.graph.add_node(graph_node);
-        self.pass_nodes.insert(name.clone(), index);
-        self.pass_resource_mappings.insert(name, mappings);
-        self.needs_recompile = true;
-        Ok(index)
+    fn resource_name(&self, id: ResourceId) -> String {
+        self.resources
+            .get_descriptor(id)
+            .map(|descriptor| descriptor.name.clone())
+            .unwrap_or_else(|| format!("{:?}", id))
     }
 
     pub fn add_sub_graph(
@@ -1093,8 +2626,11 @@ impl<C> RenderGraph<C> {
                 mip_level_count: 1,
                 dimension: wgpu::TextureDimension::D2,
                 depth_or_array_layers: 1,
+                view_formats: Vec::new(),
             },
+            size_mode: SizeMode::Fixed,
             clear_color: None,
+            resolve_target: None,
             force_store: true,
         }
     }
@@ -1112,8 +2648,11 @@ impl<C> RenderGraph<C> {
                 mip_level_count: 1,
                 dimension: wgpu::TextureDimension::D2,
                 depth_or_array_layers: 1,
+                view_formats: Vec::new(),
             },
+            size_mode: SizeMode::Fixed,
             clear_depth: None,
+            clear_stencil: None,
             force_store: true,
         }
     }
@@ -1135,7 +2674,7 @@ impl<C> RenderGraph<C> {
         name: &str,
         template: &ResourceTemplate,
     ) -> ResourceId {
-        self.resources.register_transient_resource(
+        let id = self.resources.register_transient_resource(
             name.to_string(),
             ResourceType::TransientColor {
                 descriptor: RenderGraphTextureDescriptor {
@@ -1147,10 +2686,13 @@ impl<C> RenderGraph<C> {
                     mip_level_count: template.mip_level_count,
                     dimension: template.dimension,
                     depth_or_array_layers: template.depth_or_array_layers,
+                    view_formats: template.view_formats.clone(),
                 },
                 clear_color: None,
             },
-        )
+        );
+        self.resources.set_size_mode(id, template.size_mode);
+        id
     }
 
     pub fn transient_color_from_template_with_clear(
@@ -1159,7 +2701,7 @@ impl<C> RenderGraph<C> {
         template: &ResourceTemplate,
         clear_color: wgpu::Color,
     ) -> ResourceId {
-        self.resources.register_transient_resource(
+        let id = self.resources.register_transient_resource(
             name.to_string(),
             ResourceType::TransientColor {
                 descriptor: RenderGraphTextureDescriptor {
@@ -1171,10 +2713,13 @@ impl<C> RenderGraph<C> {
                     mip_level_count: template.mip_level_count,
                     dimension: template.dimension,
                     depth_or_array_layers: template.depth_or_array_layers,
+                    view_formats: template.view_formats.clone(),
                 },
                 clear_color: Some(clear_color),
             },
-        )
+        );
+        self.resources.set_size_mode(id, template.size_mode);
+        id
     }
 
     pub fn external_color_from_template(
@@ -1203,6 +2748,7 @@ impl<C> RenderGraph<C> {
         PassBuilder {
             graph: self,
             pass: Some(pass),
+            label: None,
             slots: Vec::new(),
         }
     }
@@ -1214,45 +2760,193 @@ impl<C> RenderGraph<C> {
         }
     }
 
-    fn build_dependency_edges(&mut self) {
-        let mut resource_writers: HashMap<ResourceId, NodeIndex> = HashMap::new();
+    fn version_producers(&self) -> HashMap<(ResourceId, u32), NodeIndex> {
+        let mut producers = HashMap::new();
+        for node_index in self.graph.node_indices() {
+            for (&resource_id, &version) in &self.graph[node_index].write_versions {
+                producers.insert((resource_id, version), node_index);
+            }
+        }
+        producers
+    }
+
+    fn overlapping_producers(
+        &self,
+        producers: &HashMap<(ResourceId, u32), NodeIndex>,
+        resource_id: ResourceId,
+        ranges: &[AccessRange],
+        versions: impl Iterator<Item = u32>,
+    ) -> Vec<(u32, NodeIndex)> {
+        let mut overlapping = Vec::new();
+
+        for version in versions {
+            let Some(&producer) = producers.get(&(resource_id, version)) else {
+                continue;
+            };
+
+            let written = &self.graph[producer].write_ranges[&resource_id];
+            if ranges_overlap(written, ranges) {
+                overlapping.push((version, producer));
+                if ranges_cover(written, ranges) {
+                    break;
+                }
+            }
+        }
+
+        overlapping
+    }
+
+    fn effective_read_version(
+        &self,
+        producers: &HashMap<(ResourceId, u32), NodeIndex>,
+        resource_id: ResourceId,
+        version: u32,
+        ranges: &[AccessRange],
+    ) -> u32 {
+        let is_transient = self
+            .resources
+            .get_descriptor(resource_id)
+            .is_some_and(|descriptor| !descriptor.is_external);
+
+        if version == 0 && is_transient {
+            let latest = self.latest_version(resource_id).version;
+            self.overlapping_producers(producers, resource_id, ranges, 1..=latest)
+                .first()
+                .map_or(0, |&(version, _)| version)
+        } else {
+            version
+        }
+    }
+
+    fn consumed_versions<'a>(
+        &self,
+        producers: &HashMap<(ResourceId, u32), NodeIndex>,
+        node: &'a GraphNode<C>,
+    ) -> Vec<(ResourceId, u32, &'a [AccessRange])> {
+        let mut consumed = Vec::new();
+
+        for (&resource_id, &version) in &node.read_versions {
+            let ranges = &node.read_ranges[&resource_id];
+            let version = self.effective_read_version(producers, resource_id, version, ranges);
+            consumed.extend(
+                self.overlapping_producers(producers, resource_id, ranges, (1..=version).rev())
+                    .into_iter()
+                    .map(|(version, _)| (resource_id, version, ranges.as_slice())),
+            );
+        }
+
+        for (&resource_id, &version) in &node.write_versions {
+            let ranges = &node.write_ranges[&resource_id];
+            consumed.extend(
+                self.overlapping_producers(producers, resource_id, ranges, (1..version).rev())
+                    .into_iter()
+                    .map(|(version, _)| (resource_id, version, ranges.as_slice())),
+            );
+        }
+
+        consumed
+    }
+
+    pub(crate) fn dependency_edges(&self) -> Vec<(NodeIndex, NodeIndex, ResourceId)> {
+        let producers = self.version_producers();
 
         let node_indices: Vec<NodeIndex> = self.graph.node_indices().collect();
-        let mut edges_to_add: Vec<(NodeIndex, NodeIndex, ResourceId)> = Vec::new();
+        let mut edges: Vec<(NodeIndex, NodeIndex, ResourceId)> = Vec::new();
+
+        let mut add_edge = |from: NodeIndex, to: NodeIndex, resource: ResourceId| {
+            if from != to
+                && !edges
+                    .iter()
+                    .any(|&(edge_from, edge_to, _)| edge_from == from && edge_to == to)
+            {
+                edges.push((from, to, resource));
+            }
+        };
 
         for &node_index in &node_indices {
             let node = &self.graph[node_index];
-            let reads = node.reads.clone();
-            let writes = node.writes.clone();
-            let reads_writes = node.reads_writes.clone();
 
-            for &read_resource in &reads {
-                if let Some(&writer_index) = resource_writers.get(&read_resource)
-                    && !self.graph.contains_edge(writer_index, node_index)
+            for (&resource_id, &version) in &node.read_versions {
+                let ranges = &node.read_ranges[&resource_id];
+                let version = self.effective_read_version(&producers, resource_id, version, ranges);
+                let latest = self.latest_version(resource_id).version;
+
+                for (_, writer_index) in
+                    self.overlapping_producers(&producers, resource_id, ranges, (1..=version).rev())
                 {
-                    edges_to_add.push((writer_index, node_index, read_resource));
+                    add_edge(writer_index, node_index, resource_id);
+                }
+
+                for (_, overwriter_index) in self.overlapping_producers(
+                    &producers,
+                    resource_id,
+                    ranges,
+                    version + 1..=latest,
+                ) {
+                    add_edge(node_index, overwriter_index, resource_id);
                 }
             }
 
-            for &rw_resource in &reads_writes {
-                if let Some(&writer_index) = resource_writers.get(&rw_resource)
-                    && !self.graph.contains_edge(writer_index, node_index)
+            for (&resource_id, &version) in &node.write_versions {
+                let ranges = &node.write_ranges[&resource_id];
+                for (_, previous_writer) in
+                    self.overlapping_producers(&producers, resource_id, ranges, (1..version).rev())
                 {
-                    edges_to_add.push((writer_index, node_index, rw_resource));
+                    add_edge(previous_writer, node_index, resource_id);
                 }
             }
+        }
 
-            for &write_resource in &writes {
-                resource_writers.insert(write_resource, node_index);
+        edges
+    }
+
+    fn build_dependency_edges(&mut self) {
+        for (from, to, resource) in self.dependency_edges() {
+            if !self.graph.contains_edge(from, to) {
+                self.graph.add_edge(from, to, resource);
             }
+        }
+    }
 
-            for &rw_resource in &reads_writes {
-                resource_writers.insert(rw_resource, node_index);
+    fn observed_versions(
+        &self,
+        execution_order: &[NodeIndex],
+    ) -> HashMap<(ResourceId, u32), Vec<AccessRange>> {
+        let producers = self.version_producers();
+        let mut observed: HashMap<(ResourceId, u32), Vec<AccessRange>> = HashMap::new();
+
+        for &node_index in execution_order {
+            for (resource_id, version, ranges) in
+                self.consumed_versions(&producers, &self.graph[node_index])
+            {
+                observed
+                    .entry((resource_id, version))
+                    .or_default()
+                    .extend(ranges);
             }
         }
 
-        for (from, to, resource) in edges_to_add {
-            self.graph.add_edge(from, to, resource);
+        observed
+    }
+
+    fn attachment_aspects(&self, resource_id: ResourceId) -> &'static [TextureAspect] {
+        match self
+            .resources
+            .get_descriptor(resource_id)
+            .map(|descriptor| &descriptor.resource_type)
+        {
+            Some(
+                ResourceType::TransientDepth {
+                    descriptor: RenderGraphTextureDescriptor { format, .. },
+                    ..
+                }
+                | ResourceType::ExternalDepth { format, .. },
+            ) => match (format.has_depth_aspect(), format.has_stencil_aspect()) {
+                (true, false) => &[TextureAspect::DepthOnly],
+                (false, true) => &[TextureAspect::StencilOnly],
+                _ => &[TextureAspect::DepthOnly, TextureAspect::StencilOnly],
+            },
+            _ => &[TextureAspect::All],
         }
     }
 
@@ -1260,9 +2954,13 @@ impl<C> RenderGraph<C> {
         let mut lifetimes: HashMap<ResourceId, ResourceLifetime> = HashMap::new();
 
         for (pass_index, &node_index) in execution_order.iter().enumerate() {
+            if self.culled_passes.contains(&node_index) {
+                continue;
+            }
+
             let node = &self.graph[node_index];
 
-            for &resource_id in &node.writes {
+            for &resource_id in node.writes.iter().chain(&node.resolves) {
                 lifetimes.entry(resource_id).or_insert(ResourceLifetime {
                     resource_id,
                     first_use: pass_index,
@@ -1293,7 +2991,7 @@ impl<C> RenderGraph<C> {
             .into_iter()
             .filter(|(id, _)| {
                 if let Some(desc) = self.resources.get_descriptor(*id) {
-                    !desc.is_external
+                    !desc.is_external && !self.resources.is_history(*id)
                 } else {
                     false
                 }
@@ -1311,7 +3009,13 @@ impl<C> RenderGraph<C> {
             && desc1.height == desc2.height
             && desc1.sample_count == desc2.sample_count
             && desc1.mip_level_count == desc2.mip_level_count
+            && desc1.dimension == desc2.dimension
+            && desc1.depth_or_array_layers == desc2.depth_or_array_layers
             && desc1.usage.contains(desc2.usage)
+            && desc2
+                .view_formats
+                .iter()
+                .all(|format| desc1.view_formats.contains(format))
     }
 
     fn can_alias_buffers(
@@ -1329,6 +3033,7 @@ impl<C> RenderGraph<C> {
 
         let mut aliasing_info = ResourceAliasingInfo {
             aliases: HashMap::new(),
+            base_mip_levels: HashMap::new(),
             pools: Vec::new(),
         };
 
@@ -1349,7 +3054,7 @@ impl<C> RenderGraph<C> {
             let mut assigned_slot = None;
 
             for candidate in reused_candidates.iter_mut() {
-                let can_reuse = match (&candidate.descriptor_info, &descriptor.resource_type) {
+                let base_mip_level = match (&candidate.descriptor_info, &descriptor.resource_type) {
                     (
                         PoolDescriptorInfo::Texture(pool_desc),
                         ResourceType::TransientColor {
@@ -1360,17 +3065,17 @@ impl<C> RenderGraph<C> {
                             descriptor: res_desc,
                             ..
                         },
-                    ) => Self::can_alias_textures(pool_desc, res_desc),
+                    ) => self.texture_pool_mip(pool_desc, res_desc),
                     (
                         PoolDescriptorInfo::Buffer(pool_desc),
                         ResourceType::TransientBuffer {
                             descriptor: res_desc,
                         },
-                    ) => Self::can_alias_buffers(pool_desc, res_desc),
-                    _ => false,
+                    ) => self.buffer_pool_fits(pool_desc, res_desc).then_some(0),
+                    _ => None,
                 };
 
-                if can_reuse {
+                if let Some(base_mip_level) = base_mip_level {
                     let needs_new_resource =
                         match (&mut candidate.descriptor_info, &descriptor.resource_type) {
                             (
@@ -1383,30 +3088,22 @@ impl<C> RenderGraph<C> {
                                     descriptor: res_desc,
                                     ..
                                 },
-                            ) => {
-                                if !pool_desc.usage.contains(res_desc.usage) {
-                                    pool_desc.usage = pool_desc.usage | res_desc.usage;
-                                    true
-                                } else {
-                                    false
-                                }
-                            }
+                            ) => Self::grow_texture_pool(pool_desc, res_desc, base_mip_level),
                             (
                                 PoolDescriptorInfo::Buffer(pool_desc),
                                 ResourceType::TransientBuffer {
                                     descriptor: res_desc,
                                 },
-                            ) => {
-                                if res_desc.size > pool_desc.size {
-                                    *pool_desc = res_desc.clone();
-                                    true
-                                } else {
-                                    false
-                                }
-                            }
+                            ) => Self::grow_buffer_pool(pool_desc, res_desc),
                             _ => false,
                         };
 
+                    if base_mip_level > 0 {
+                        aliasing_info
+                            .base_mip_levels
+                            .insert(lifetime.resource_id, base_mip_level);
+                    }
+
                     let pool_slot = &mut aliasing_info.pools[candidate.pool_index];
                     pool_slot.lifetime_end = lifetime.last_use;
                     pool_slot.descriptor_info = Some(candidate.descriptor_info.clone());
@@ -1466,57 +3163,72 @@ impl<C> RenderGraph<C> {
         aliasing_info
     }
 
-    fn compute_store_ops(&self, execution_order: &[NodeIndex]) -> HashMap<ResourceId, StoreOp> {
-        let mut last_read: HashMap<ResourceId, usize> = HashMap::new();
-
-        for (index, &node_index) in execution_order.iter().enumerate().rev() {
-            let node = &self.graph[node_index];
+    fn write_store_op(&self, resource_id: ResourceId, is_observed: bool) -> StoreOp {
+        let descriptor = self.resources.get_descriptor(resource_id).unwrap();
 
-            for &resource_id in node.reads.iter().chain(&node.reads_writes) {
-                last_read.entry(resource_id).or_insert(index);
+        match &descriptor.resource_type {
+            ResourceType::ExternalColor { force_store, .. }
+            | ResourceType::ExternalDepth { force_store, .. } => {
+                if *force_store || is_observed {
+                    StoreOp::Store
+                } else {
+                    StoreOp::Discard
+                }
+            }
+            ResourceType::ExternalBuffer => StoreOp::Store,
+            _ => {
+                if is_observed
+                    || self.resources.is_history(resource_id)
+                    || self.readbacks.is_marked(resource_id)
+                {
+                    StoreOp::Store
+                } else {
+                    StoreOp::Discard
+                }
             }
         }
+    }
+
+    fn compute_store_ops(&self, execution_order: &[NodeIndex]) -> StoreOps {
+        let observed = self.observed_versions(execution_order);
 
         let mut store_ops = HashMap::new();
+        let mut pass_store_ops = HashMap::new();
+        let mut pass_stencil_store_ops = HashMap::new();
 
-        for (index, &node_index) in execution_order.iter().enumerate() {
+        for &node_index in execution_order {
             let node = &self.graph[node_index];
+            let mut node_store_ops = HashMap::new();
+            let mut node_stencil_store_ops = HashMap::new();
 
-            for &resource_id in node.writes.iter().chain(&node.reads_writes) {
-                let descriptor = self.resources.get_descriptor(resource_id).unwrap();
+            for (&resource_id, &version) in &node.write_versions {
+                let ranges = &node.write_ranges[&resource_id];
+                let consumed = observed.get(&(resource_id, version));
 
-                if descriptor.is_external {
-                    let store_op = match &descriptor.resource_type {
-                        ResourceType::ExternalColor { force_store, .. }
-                        | ResourceType::ExternalDepth { force_store, .. } => {
-                            if *force_store {
-                                StoreOp::Store
-                            } else if last_read
-                                .get(&resource_id)
-                                .is_some_and(|&last| last > index)
-                            {
-                                StoreOp::Store
-                            } else {
-                                StoreOp::Discard
-                            }
-                        }
-                        ResourceType::ExternalBuffer => StoreOp::Store,
-                        _ => StoreOp::Store,
-                    };
-                    store_ops.insert(resource_id, store_op);
-                    continue;
-                }
+                for &aspect in self.attachment_aspects(resource_id) {
+                    let written = restrict_ranges(ranges, aspect);
+                    if written.is_empty() {
+                        continue;
+                    }
 
-                let store_op = if last_read
-                    .get(&resource_id)
-                    .is_some_and(|&last| last > index)
-                {
-                    StoreOp::Store
-                } else {
-                    StoreOp::Discard
-                };
-                store_ops.insert(resource_id, store_op);
+                    let is_observed =
+                        consumed.is_some_and(|consumed| ranges_overlap(consumed, &written));
+                    let store_op = self.write_store_op(resource_id, is_observed);
+                    if aspect == TextureAspect::StencilOnly {
+                        node_stencil_store_ops.insert(resource_id, store_op);
+                    } else {
+                        node_store_ops.insert(resource_id, store_op);
+                    }
+
+                    let entry = store_ops.entry(resource_id).or_insert(StoreOp::Discard);
+                    if store_op == StoreOp::Store {
+                        *entry = StoreOp::Store;
+                    }
+                }
             }
+
+            pass_store_ops.insert(node_index, node_store_ops);
+            pass_stencil_store_ops.insert(node_index, node_stencil_store_ops);
         }
 
         for &resource_id in self.resources.descriptors.keys() {
@@ -1534,23 +3246,115 @@ impl<C> RenderGraph<C> {
                         }
                         _ => StoreOp::Store,
                     }
+                } else if self.resources.is_history(resource_id)
+                    || self.readbacks.is_marked(resource_id)
+                {
+                    StoreOp::Store
                 } else {
                     StoreOp::Discard
                 }
             });
         }
 
-        store_ops
+        StoreOps {
+            resources: store_ops,
+            passes: pass_store_ops,
+            stencil: pass_stencil_store_ops,
+        }
+    }
+
+    fn compute_load_ops(&self, execution_order: &[NodeIndex]) -> LoadOps {
+        let mut written: HashMap<ResourceId, Vec<AccessRange>> = HashMap::new();
+        let mut load_ops = HashMap::new();
+        let mut stencil_load_ops = HashMap::new();
+
+        for &node_index in execution_order {
+            if self.culled_passes.contains(&node_index) {
+                continue;
+            }
+
+            let node = &self.graph[node_index];
+            let mut pass_load_ops = HashMap::new();
+            let mut pass_stencil_load_ops = HashMap::new();
+
+            for &resource_id in node.writes.iter().chain(&node.reads_writes) {
+                let Some(descriptor) = self.resources.get_descriptor(resource_id) else {
+                    continue;
+                };
+
+                let ranges = &node.write_ranges[&resource_id];
+                let written_ranges = written.entry(resource_id).or_default();
+
+                for &aspect in self.attachment_aspects(resource_id) {
+                    let has_clear_value = match (&descriptor.resource_type, aspect) {
+                        (
+                            ResourceType::ExternalColor { clear_color, .. }
+                            | ResourceType::TransientColor { clear_color, .. },
+                            _,
+                        ) => clear_color.is_some(),
+                        (
+                            ResourceType::ExternalDepth { clear_stencil, .. }
+                            | ResourceType::TransientDepth { clear_stencil, .. },
+                            TextureAspect::StencilOnly,
+                        ) => clear_stencil.is_some(),
+                        (
+                            ResourceType::ExternalDepth { clear_depth, .. }
+                            | ResourceType::TransientDepth { clear_depth, .. },
+                            _,
+                        ) => clear_depth.is_some(),
+                        _ => continue,
+                    };
+
+                    let aspect_ranges = restrict_ranges(ranges, aspect);
+                    if aspect_ranges.is_empty() {
+                        continue;
+                    }
+
+                    let load_op = if ranges_overlap(written_ranges, &aspect_ranges) {
+                        AttachmentLoadOp::Load
+                    } else if has_clear_value {
+                        AttachmentLoadOp::Clear
+                    } else if descriptor.is_external {
+                        AttachmentLoadOp::Load
+                    } else {
+                        AttachmentLoadOp::DontCare
+                    };
+
+                    if aspect == TextureAspect::StencilOnly {
+                        pass_stencil_load_ops.insert(resource_id, load_op);
+                    } else {
+                        pass_load_ops.insert(resource_id, load_op);
+                    }
+                }
+
+                written_ranges.extend(ranges);
+            }
+
+            for &target in &node.resolves {
+                written.entry(target).or_default().push(AccessRange::ALL);
+            }
+
+            load_ops.insert(node_index, pass_load_ops);
+            stencil_load_ops.insert(node_index, pass_stencil_load_ops);
+        }
+
+        LoadOps {
+            passes: load_ops,
+            stencil: stencil_load_ops,
+        }
     }
 
     fn compute_dead_passes(&self, execution_order: &[NodeIndex]) -> HashSet<NodeIndex> {
-        let mut required_resources: HashSet<ResourceId> = HashSet::new();
+        let producers = self.version_producers();
+        let mut required_versions: HashSet<(ResourceId, u32)> = HashSet::new();
         let mut required_passes: HashSet<NodeIndex> = HashSet::new();
 
-        for &resource_id in self.resources.descriptors.keys() {
-            let descriptor = self.resources.get_descriptor(resource_id).unwrap();
-            if descriptor.is_external {
-                required_resources.insert(resource_id);
+        for (&resource_id, descriptor) in &self.resources.descriptors {
+            if descriptor.is_external
+                || self.resources.previous(resource_id).is_some()
+                || self.readbacks.is_marked(resource_id)
+            {
+                required_versions.insert((resource_id, self.latest_version(resource_id).version));
             }
         }
 
@@ -1559,17 +3363,18 @@ impl<C> RenderGraph<C> {
 
             let has_side_effects = node.writes.is_empty() && node.reads_writes.is_empty();
 
-            let writes_required_resource =
-                node.writes.iter().any(|r| required_resources.contains(r))
-                    || node
-                        .reads_writes
-                        .iter()
-                        .any(|r| required_resources.contains(r));
+            let writes_required_version =
+                node.write_versions.iter().any(|(&resource_id, &version)| {
+                    required_versions.contains(&(resource_id, version))
+                });
 
-            if writes_required_resource || has_side_effects {
+            if writes_required_version || has_side_effects {
                 required_passes.insert(node_index);
-                required_resources.extend(&node.reads);
-                required_resources.extend(&node.reads_writes);
+                required_versions.extend(
+                    self.consumed_versions(&producers, node)
+                        .into_iter()
+                        .map(|(resource_id, version, _)| (resource_id, version)),
+                );
             }
         }
 
@@ -1578,93 +3383,149 @@ impl<C> RenderGraph<C> {
     }
 
     pub fn compile(&mut self) -> Result<()> {
+        self.apply_size_modes()?;
+
+        self.graph.clear_edges();
         self.build_dependency_edges();
 
         self.execution_order = petgraph::algo::toposort(&self.graph, None)
             .map_err(|_| RenderGraphError::CyclicDependency)?;
 
-        self.store_ops = self.compute_store_ops(&self.execution_order);
-
-        let lifetimes = self.compute_resource_lifetimes(&self.execution_order);
-        self.aliasing_info = Some(self.compute_resource_aliasing(lifetimes));
+        let store_ops = self.compute_store_ops(&self.execution_order);
+        self.store_ops = store_ops.resources;
+        self.pass_store_ops = store_ops.passes;
+        self.pass_stencil_store_ops = store_ops.stencil;
 
         self.culled_passes = self.compute_dead_passes(&self.execution_order);
 
+        let load_ops = self.compute_load_ops(&self.execution_order);
+        self.load_ops = load_ops.passes;
+        self.stencil_load_ops = load_ops.stencil;
+
+        self.update_aliasing();
+        self.resources.release_transient_handles();
+        self.check_memory_budget()?;
+
         self.needs_recompile = false;
+        self.needs_resource_reallocation = false;
         Ok(())
     }
 
-    fn recompile_if_needed(&mut self) -> Result<()> {
-        if self.needs_resource_reallocation {
-            let lifetimes = self.compute_resource_lifetimes(&self.execution_order);
-            self.aliasing_info = Some(self.compute_resource_aliasing(lifetimes));
-            self.needs_resource_reallocation = false;
-            return Ok(());
-        }
+    fn update_aliasing(&mut self) {
+        let lifetimes = self.compute_resource_lifetimes(&self.execution_order);
+        self.aliasing_info = Some(self.compute_resource_aliasing(lifetimes));
+    }
 
-        if !self.needs_recompile {
-            return Ok(());
+    fn recompile_if_needed(&mut self) -> Result<()> {
+        if self.needs_recompile {
+            return self.compile();
         }
 
-        let edge_indices: Vec<_> = self.graph.edge_indices().collect();
-        for edge_index in edge_indices {
-            self.graph.remove_edge(edge_index);
+        if self.needs_resource_reallocation {
+            self.update_aliasing();
+            self.needs_resource_reallocation = false;
         }
 
-        self.build_dependency_edges();
-
-        self.execution_order = petgraph::algo::toposort(&self.graph, None)
-            .map_err(|_| RenderGraphError::CyclicDependency)?;
-
-        self.store_ops = self.compute_store_ops(&self.execution_order);
-
-        let lifetimes = self.compute_resource_lifetimes(&self.execution_order);
-        self.aliasing_info = Some(self.compute_resource_aliasing(lifetimes));
-
-        self.culled_passes = self.compute_dead_passes(&self.execution_order);
-
-        self.needs_recompile = false;
         Ok(())
     }
 
-    pub fn execute(
+    pub fn execute<B: RenderGraphBackend + ?Sized>(
         &mut self,
-        device: &Device,
+        backend: &B,
         queue: &wgpu::Queue,
         configs: &C,
     ) -> Result<Vec<CommandBuffer>> {
+        if std::mem::take(&mut self.capture_requested) {
+            self.capture = Some(FrameCapture::begin(Instant::now()));
+        }
+
+        self.readbacks.map();
         self.recompile_if_needed()?;
 
         if self.aliasing_info.is_none() {
-            let lifetimes = self.compute_resource_lifetimes(&self.execution_order);
-            self.aliasing_info = Some(self.compute_resource_aliasing(lifetimes));
+            self.update_aliasing();
         }
+        self.check_memory_budget()?;
 
+        self.resources.allocate_history_resources(backend);
         if let Some(aliasing_info) = &mut self.aliasing_info {
             self.resources.allocate_transient_resources_with_aliasing(
-                device,
+                backend,
                 &self.store_ops,
                 aliasing_info,
             );
         }
 
+        let slot_views: Vec<(ResourceId, SlotView)> = self
+            .pass_slot_views
+            .iter()
+            .flat_map(|(node_index, slot_views)| {
+                let mappings = &self.pass_resource_mappings[node_index];
+                slot_views.iter().flat_map(|(slot, view)| {
+                    [(mappings[slot], *view), (mappings[slot], view.attachment())]
+                })
+            })
+            .collect();
+        self.resources.create_views(&slot_views);
+
         self.invalidate_bind_groups_for_changed_resources();
 
+        if let Some(profiler) = &mut self.profiler {
+            profiler.begin_frame(backend.device(), queue, self.execution_order.len());
+        }
+
+        self.uploads.begin_frame(backend.device());
         for &node_index in &self.execution_order {
             if self.culled_passes.contains(&node_index) {
+                if let Some(capture) = &mut self.capture {
+                    capture.record_status(&self.graph[node_index].name, PassStatus::Culled);
+                }
                 continue;
             }
 
             let node = &mut self.graph[node_index];
 
             if !node.pass.is_enabled(configs) {
+                if let Some(capture) = &mut self.capture {
+                    capture.record_status(&node.name, PassStatus::Disabled);
+                }
                 continue;
             }
 
-            node.pass.prepare(device, queue, configs);
+            let prepare_start = Instant::now();
+            node.pass.prepare(&mut PassPrepareContext {
+                device: backend.device(),
+                queue,
+                configs,
+                uploads: &mut self.uploads,
+            });
+            if let Some(profiler) = &mut self.profiler {
+                profiler.record_prepare(&node.name, prepare_start.elapsed());
+            }
+            if let Some(capture) = &mut self.capture {
+                capture.record_prepare(&node.name, prepare_start);
+            }
+            backend.pass_prepared(&node.name);
+        }
+
+        if self.uploads.flush(backend, queue) {
+            for node in self.graph.node_weights_mut() {
+                node.pass.invalidate_bind_groups();
+            }
+        }
+
+        let command_buffers = self.execute_serial(backend, queue, configs);
+        if command_buffers.is_ok() {
+            self.resources.advance_history();
+        }
+        self.bind_groups
+            .end_frame(&self.resources, self.uploads.buffer());
+
+        if let Some(capture) = self.capture.take() {
+            self.frame_capture = Some(capture.finish());
         }
 
-        self.execute_serial(device, queue, configs)
+        command_buffers
     }
 
     fn invalidate_bind_groups_for_changed_resources(&mut self) {
@@ -1698,6 +3559,7 @@ impl<C> RenderGraph<C> {
                 .iter()
                 .chain(&node.writes)
                 .chain(&node.reads_writes)
+                .chain(&node.resolves)
             {
                 if dirty_resources.contains(&resource_id) {
                     passes_to_invalidate.insert(node_index);
@@ -1712,17 +3574,16 @@ impl<C> RenderGraph<C> {
         }
     }
 
-    fn execute_serial(
+    fn execute_serial<B: RenderGraphBackend + ?Sized>(
         &mut self,
-        device: &Device,
+        backend: &B,
         queue: &wgpu::Queue,
         configs: &C,
     ) -> Result<Vec<CommandBuffer>> {
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("RenderGraph Serial Encoder"),
-        });
+        let mut encoder = backend.create_command_encoder("RenderGraph Serial Encoder");
 
         let mut command_buffers = Vec::new();
+        let readback_writers = self.readback_writers(configs);
 
         for &node_index in &self.execution_order {
             if self.culled_passes.contains(&node_index) {
@@ -1735,19 +3596,35 @@ impl<C> RenderGraph<C> {
                 continue;
             }
 
-            let slot_mappings = self.pass_resource_mappings.get(&node.name).ok_or_else(|| {
-                RenderGraphError::ResourceNotFound {
+            let slot_mappings = self
+                .pass_resource_mappings
+                .get(&node_index)
+                .ok_or_else(|| RenderGraphError::ResourceNotFound {
                     resource: format!("pass_{}_mappings", node.name),
                     id: ResourceId(0),
-                }
-            })?;
+                })?;
+
+            let query = self
+                .profiler
+                .as_mut()
+                .and_then(|profiler| profiler.begin_pass(&mut encoder));
+            let execute_start = Instant::now();
+
+            encoder.push_debug_group(&node.name);
 
             let sub_graph_commands = {
                 let context = PassExecutionContext {
                     encoder: &mut encoder,
                     resources: &self.resources,
-                    device,
+                    device: backend.device(),
                     slot_mappings,
+                    slot_views: self.pass_slot_views.get(&node_index),
+                    load_ops: self.load_ops.get(&node_index),
+                    store_ops: self.pass_store_ops.get(&node_index),
+                    stencil_load_ops: self.stencil_load_ops.get(&node_index),
+                    stencil_store_ops: self.pass_stencil_store_ops.get(&node_index),
+                    uploads: &self.uploads,
+                    bind_groups: &mut self.bind_groups,
                     configs,
                     sub_graph_commands: Vec::new(),
                 };
@@ -1755,6 +3632,36 @@ impl<C> RenderGraph<C> {
                 node.pass.execute(context)?
             };
 
+            encoder.pop_debug_group();
+
+            if let Some(profiler) = &mut self.profiler {
+                profiler.end_pass(&mut encoder, &node.name, execute_start.elapsed(), query);
+            }
+
+            if let Some(capture) = &mut self.capture {
+                let mut slots: Vec<SlotCapture> = slot_mappings
+                    .iter()
+                    .map(|(slot, &resource)| SlotCapture {
+                        slot: slot.clone(),
+                        resource,
+                        resource_name: self
+                            .resources
+                            .get_descriptor(resource)
+                            .map(|descriptor| descriptor.name.clone())
+                            .unwrap_or_default(),
+                    })
+                    .collect();
+                slots.sort_by(|a, b| a.slot.cmp(&b.slot));
+                capture.record_execute(&node.name, execute_start, slots);
+            }
+
+            backend.pass_executed(&node.name);
+
+            self.readbacks
+                .record(backend, &mut encoder, &self.resources, |resource_id| {
+                    readback_writers.get(&resource_id) == Some(&node_index)
+                });
+
             for command in sub_graph_commands {
                 command_buffers.push(encoder.finish());
 
@@ -1850,15 +3757,37 @@ impl<C> RenderGraph<C> {
                     }
                 }
 
-                let sub_graph_buffers = sub_graph.execute(device, queue, configs)?;
+                let sub_graph_start = Instant::now();
+                if let Some(capture) = &self.capture {
+                    sub_graph.capture = Some(FrameCapture::begin(capture.start()));
+                }
+
+                let sub_graph_buffers = sub_graph.execute(backend, queue, configs)?;
                 command_buffers.extend(sub_graph_buffers);
 
-                encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                    label: Some("RenderGraph Serial Encoder"),
-                });
+                if let Some(capture) = &mut self.capture {
+                    capture.record_sub_graph(
+                        &command.sub_graph_name,
+                        &node.name,
+                        command.inputs.len(),
+                        sub_graph_start,
+                        sub_graph.frame_capture.take(),
+                    );
+                }
+
+                encoder = backend.create_command_encoder("RenderGraph Serial Encoder");
             }
         }
 
+        self.readbacks
+            .reject_unwritten(&self.resources, &readback_writers);
+        self.readbacks
+            .record(backend, &mut encoder, &self.resources, |_| true);
+
+        if let Some(profiler) = &mut self.profiler {
+            profiler.end_frame(&mut encoder);
+        }
+
         command_buffers.push(encoder.finish());
         Ok(command_buffers)
     }
@@ -1878,13 +3807,7 @@ impl<C> RenderGraph<C> {
             .update_transient_descriptor(id, width, height)?;
 
         self.aliasing_info = None;
-        self.resources.handles.retain(|id, _| {
-            if let Some(descriptor) = self.resources.descriptors.get(id) {
-                descriptor.is_external
-            } else {
-                false
-            }
-        });
+        self.resources.release_transient_handles();
         self.needs_resource_reallocation = true;
         Ok(())
     }
@@ -1896,6 +3819,17 @@ impl<C> Default for RenderGraph<C> {
     }
 }
 
+struct StoreOps {
+    resources: HashMap<ResourceId, StoreOp>,
+    passes: HashMap<NodeIndex, HashMap<ResourceId, StoreOp>>,
+    stencil: HashMap<NodeIndex, HashMap<ResourceId, StoreOp>>,
+}
+
+struct LoadOps {
+    passes: HashMap<NodeIndex, HashMap<ResourceId, AttachmentLoadOp>>,
+    stencil: HashMap<NodeIndex, HashMap<ResourceId, AttachmentLoadOp>>,
+}
+
 #[derive(Debug, Clone)]
 struct ResourceLifetime {
     resource_id: ResourceId,
@@ -1908,8 +3842,8 @@ enum PooledResource {
     Buffer { buffer: Arc<Buffer> },
 }
 
-#[derive(Clone)]
-enum PoolDescriptorInfo {
+#[derive(Debug, Clone)]
+pub enum PoolDescriptorInfo {
     Texture(RenderGraphTextureDescriptor),
     Buffer(RenderGraphBufferDescriptor),
 }
@@ -1920,6 +3854,20 @@ pub struct PoolSlot {
     lifetime_end: usize,
 }
 
+impl PoolSlot {
+    pub fn descriptor_info(&self) -> Option<&PoolDescriptorInfo> {
+        self.descriptor_info.as_ref()
+    }
+
+    pub fn lifetime_end(&self) -> usize {
+        self.lifetime_end
+    }
+
+    pub fn is_allocated(&self) -> bool {
+        self.resource.is_some()
+    }
+}
+
 #[derive(Clone)]
 struct PoolHeapEntry {
     pool_index: usize,
@@ -1949,6 +3897,7 @@ impl Ord for PoolHeapEntry {
 
 pub struct ResourceAliasingInfo {
     pub aliases: HashMap<ResourceId, usize>,
+    pub base_mip_levels: HashMap<ResourceId, u32>,
     pub pools: Vec<PoolSlot>,
 }
 
@@ -0,0 +1,292 @@
+use crate::{
+    PoolDescriptorInfo, RenderGraph, RenderGraphBufferDescriptor, RenderGraphError,
+    RenderGraphTextureDescriptor, ResourceAliasingInfo, ResourceId, ResourceType, Result,
+    SubresourceRange,
+};
+use wgpu::{TextureAspect, TextureDimension, TextureFormat};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AliasingMode {
+    #[default]
+    Exact,
+    Compatible,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetPolicy {
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryBudget {
+    pub bytes: u64,
+    pub policy: BudgetPolicy,
+}
+
+impl MemoryBudget {
+    pub fn warn(bytes: u64) -> Self {
+        Self {
+            bytes,
+            policy: BudgetPolicy::Warn,
+        }
+    }
+
+    pub fn fail(bytes: u64) -> Self {
+        Self {
+            bytes,
+            policy: BudgetPolicy::Fail,
+        }
+    }
+}
+
+fn texel_block_size(format: TextureFormat) -> u64 {
+    if let Some(size) = format.block_copy_size(None) {
+        return size as u64;
+    }
+
+    let depth = if format.has_depth_aspect() {
+        format
+            .block_copy_size(Some(TextureAspect::DepthOnly))
+            .unwrap_or(4)
+    } else {
+        0
+    };
+    let stencil = if format.has_stencil_aspect() { 1 } else { 0 };
+
+    match depth + stencil {
+        0 => 4,
+        size => size as u64,
+    }
+}
+
+impl RenderGraphTextureDescriptor {
+    pub fn estimated_bytes(&self) -> u64 {
+        let (block_width, block_height) = self.format.block_dimensions();
+        let block_size = texel_block_size(self.format);
+        let layers = match self.dimension {
+            TextureDimension::D3 => 1,
+            _ => self.depth_or_array_layers as u64,
+        };
+
+        let bytes_per_layer: u64 = (0..self.mip_level_count)
+            .map(|level| {
+                let width = (self.width >> level).max(1).div_ceil(block_width) as u64;
+                let height = (self.height >> level).max(1).div_ceil(block_height) as u64;
+                let depth = match self.dimension {
+                    TextureDimension::D3 => (self.depth_or_array_layers >> level).max(1) as u64,
+                    _ => 1,
+                };
+                width * height * depth * block_size
+            })
+            .sum();
+
+        bytes_per_layer * layers * self.sample_count as u64
+    }
+}
+
+impl RenderGraphBufferDescriptor {
+    pub fn estimated_bytes(&self) -> u64 {
+        self.size
+    }
+}
+
+impl PoolDescriptorInfo {
+    pub fn estimated_bytes(&self) -> u64 {
+        match self {
+            PoolDescriptorInfo::Texture(descriptor) => descriptor.estimated_bytes(),
+            PoolDescriptorInfo::Buffer(descriptor) => descriptor.estimated_bytes(),
+        }
+    }
+}
+
+impl ResourceAliasingInfo {
+    pub fn estimated_bytes(&self) -> u64 {
+        self.pools
+            .iter()
+            .filter_map(|pool| pool.descriptor_info())
+            .map(PoolDescriptorInfo::estimated_bytes)
+            .sum()
+    }
+}
+
+pub(crate) fn pool_placement(
+    pool: &RenderGraphTextureDescriptor,
+    resource: &RenderGraphTextureDescriptor,
+    base_mip_level: u32,
+) -> Option<SubresourceRange> {
+    if base_mip_level == 0
+        && pool.mip_level_count == resource.mip_level_count
+        && pool.depth_or_array_layers == resource.depth_or_array_layers
+    {
+        return None;
+    }
+
+    let range = SubresourceRange::mips(base_mip_level..base_mip_level + resource.mip_level_count);
+    Some(match resource.dimension {
+        TextureDimension::D3 => range,
+        _ => range.with_layers(0..resource.depth_or_array_layers),
+    })
+}
+
+impl<C> RenderGraph<C> {
+    pub fn aliasing_mode(&self) -> AliasingMode {
+        self.aliasing_mode
+    }
+
+    pub fn set_aliasing_mode(&mut self, mode: AliasingMode) {
+        if self.aliasing_mode != mode {
+            self.aliasing_mode = mode;
+            self.needs_recompile = true;
+        }
+    }
+
+    pub fn memory_budget(&self) -> Option<MemoryBudget> {
+        self.memory_budget
+    }
+
+    pub fn set_memory_budget(&mut self, budget: Option<MemoryBudget>) {
+        self.memory_budget = budget;
+    }
+
+    pub fn estimated_transient_bytes(&self) -> Option<u64> {
+        self.aliasing_info
+            .as_ref()
+            .map(|aliasing_info| aliasing_info.estimated_bytes() + self.history_bytes())
+    }
+
+    pub(crate) fn unaliased_transient_bytes(&self, aliasing_info: &ResourceAliasingInfo) -> u64 {
+        aliasing_info
+            .aliases
+            .keys()
+            .filter_map(|&id| self.resource_estimated_bytes(id))
+            .sum::<u64>()
+            + self.history_bytes()
+    }
+
+    fn history_bytes(&self) -> u64 {
+        self.resources
+            .history_pairs
+            .keys()
+            .filter_map(|&id| self.resource_estimated_bytes(id))
+            .map(|bytes| bytes * 2)
+            .sum()
+    }
+
+    fn resource_estimated_bytes(&self, id: ResourceId) -> Option<u64> {
+        match &self.resources.get_descriptor(id)?.resource_type {
+            ResourceType::TransientColor { descriptor, .. }
+            | ResourceType::TransientDepth { descriptor, .. } => Some(descriptor.estimated_bytes()),
+            ResourceType::TransientBuffer { descriptor } => Some(descriptor.estimated_bytes()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn check_memory_budget(&mut self) -> Result<()> {
+        let (Some(budget), Some(estimated)) =
+            (self.memory_budget, self.estimated_transient_bytes())
+        else {
+            return Ok(());
+        };
+
+        if estimated <= budget.bytes {
+            self.budget_warning = None;
+            return Ok(());
+        }
+
+        match budget.policy {
+            BudgetPolicy::Fail => Err(RenderGraphError::MemoryBudgetExceeded {
+                estimated,
+                budget: budget.bytes,
+            }),
+            BudgetPolicy::Warn => {
+                if self.budget_warning != Some(estimated) {
+                    log::warn!(
+                        "{}",
+                        RenderGraphError::MemoryBudgetExceeded {
+                            estimated,
+                            budget: budget.bytes,
+                        }
+                    );
+                    self.budget_warning = Some(estimated);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn texture_pool_mip(
+        &self,
+        pool: &RenderGraphTextureDescriptor,
+        resource: &RenderGraphTextureDescriptor,
+    ) -> Option<u32> {
+        if self.aliasing_mode == AliasingMode::Exact || pool.dimension != TextureDimension::D2 {
+            return Self::can_alias_textures(pool, resource).then_some(0);
+        }
+
+        if pool.format != resource.format
+            || pool.sample_count != resource.sample_count
+            || pool.dimension != resource.dimension
+        {
+            return None;
+        }
+
+        (0..pool.mip_level_count).find(|&level| {
+            (pool.width >> level).max(1) == resource.width
+                && (pool.height >> level).max(1) == resource.height
+                && (level == 0 || level + resource.mip_level_count <= pool.mip_level_count)
+        })
+    }
+
+    pub(crate) fn grow_texture_pool(
+        pool: &mut RenderGraphTextureDescriptor,
+        resource: &RenderGraphTextureDescriptor,
+        base_mip_level: u32,
+    ) -> bool {
+        let mut grown = pool.clone();
+        grown.usage |= resource.usage;
+        grown.mip_level_count = grown
+            .mip_level_count
+            .max(base_mip_level + resource.mip_level_count);
+        if grown.dimension == TextureDimension::D2 {
+            grown.depth_or_array_layers = grown
+                .depth_or_array_layers
+                .max(resource.depth_or_array_layers);
+        }
+        for &format in &resource.view_formats {
+            if !grown.view_formats.contains(&format) {
+                grown.view_formats.push(format);
+            }
+        }
+
+        let changed = grown.usage != pool.usage
+            || grown.mip_level_count != pool.mip_level_count
+            || grown.depth_or_array_layers != pool.depth_or_array_layers
+            || grown.view_formats != pool.view_formats;
+        *pool = grown;
+        changed
+    }
+
+    pub(crate) fn buffer_pool_fits(
+        &self,
+        pool: &RenderGraphBufferDescriptor,
+        resource: &RenderGraphBufferDescriptor,
+    ) -> bool {
+        match self.aliasing_mode {
+            AliasingMode::Exact => Self::can_alias_buffers(pool, resource),
+            AliasingMode::Compatible => pool.mapped_at_creation == resource.mapped_at_creation,
+        }
+    }
+
+    pub(crate) fn grow_buffer_pool(
+        pool: &mut RenderGraphBufferDescriptor,
+        resource: &RenderGraphBufferDescriptor,
+    ) -> bool {
+        let size = pool.size.max(resource.size);
+        let usage = pool.usage | resource.usage;
+        let changed = size != pool.size || usage != pool.usage;
+        pool.size = size;
+        pool.usage = usage;
+        changed
+    }
+}
@@ -0,0 +1,382 @@
+use crate::RenderGraph;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::num::NonZeroU32;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+use wgpu::{
+    BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BufferAddress,
+    ColorTargetState, ComputePipeline, ComputePipelineDescriptor, DepthStencilState, Device,
+    ErrorFilter, Features, MultisampleState, PipelineCache, PipelineCacheDescriptor,
+    PipelineCompilationOptions, PipelineLayout, PipelineLayoutDescriptor, PrimitiveState,
+    PushConstantRange, RenderPipeline, RenderPipelineDescriptor, ShaderModule,
+    ShaderModuleDescriptor, ShaderSource, VertexAttribute, VertexStepMode,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PipelineRegistryStats {
+    pub shader_modules: usize,
+    pub bind_group_layouts: usize,
+    pub pipeline_layouts: usize,
+    pub render_pipelines: usize,
+    pub compute_pipelines: usize,
+    pub reused: u64,
+}
+
+#[derive(Default)]
+pub struct PipelineRegistry {
+    cache: Option<PipelineCache>,
+    shader_modules: HashMap<String, ShaderModule>,
+    shader_labels: HashMap<String, String>,
+    bind_group_layouts: HashMap<Vec<BindGroupLayoutEntry>, BindGroupLayout>,
+    pipeline_layouts: HashMap<PipelineLayoutKey, PipelineLayout>,
+    render_pipelines: HashMap<RenderPipelineKey, RenderPipeline>,
+    compute_pipelines: HashMap<ComputePipelineKey, ComputePipeline>,
+    reused: u64,
+}
+
+type PipelineLayoutKey = (Vec<BindGroupLayout>, Vec<PushConstantRange>);
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct StageKey {
+    module: ShaderModule,
+    entry_point: Option<String>,
+    constants: Vec<(String, u64)>,
+    zero_initialize_workgroup_memory: bool,
+}
+
+impl StageKey {
+    fn new(
+        module: &ShaderModule,
+        entry_point: Option<&str>,
+        options: &PipelineCompilationOptions,
+    ) -> Self {
+        Self {
+            module: module.clone(),
+            entry_point: entry_point.map(str::to_string),
+            constants: options
+                .constants
+                .iter()
+                .map(|&(name, value)| (name.to_string(), value.to_bits()))
+                .collect(),
+            zero_initialize_workgroup_memory: options.zero_initialize_workgroup_memory,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct VertexBufferKey {
+    array_stride: BufferAddress,
+    step_mode: VertexStepMode,
+    attributes: Vec<VertexAttribute>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct RenderPipelineKey {
+    layout: Option<PipelineLayout>,
+    vertex: StageKey,
+    buffers: Vec<VertexBufferKey>,
+    primitive: PrimitiveState,
+    depth_stencil: Option<DepthStencilState>,
+    multisample: MultisampleState,
+    fragment: Option<(StageKey, Vec<Option<ColorTargetState>>)>,
+    multiview: Option<NonZeroU32>,
+}
+
+impl RenderPipelineKey {
+    fn new(descriptor: &RenderPipelineDescriptor) -> Self {
+        Self {
+            layout: descriptor.layout.cloned(),
+            vertex: StageKey::new(
+                descriptor.vertex.module,
+                descriptor.vertex.entry_point,
+                &descriptor.vertex.compilation_options,
+            ),
+            buffers: descriptor
+                .vertex
+                .buffers
+                .iter()
+                .map(|buffer| VertexBufferKey {
+                    array_stride: buffer.array_stride,
+                    step_mode: buffer.step_mode,
+                    attributes: buffer.attributes.to_vec(),
+                })
+                .collect(),
+            primitive: descriptor.primitive,
+            depth_stencil: descriptor.depth_stencil.clone(),
+            multisample: descriptor.multisample,
+            fragment: descriptor.fragment.as_ref().map(|fragment| {
+                (
+                    StageKey::new(
+                        fragment.module,
+                        fragment.entry_point,
+                        &fragment.compilation_options,
+                    ),
+                    fragment.targets.to_vec(),
+                )
+            }),
+            multiview: descriptor.multiview,
+        }
+    }
+
+    fn uses(&self, module: &ShaderModule) -> bool {
+        self.vertex.module == *module
+            || self
+                .fragment
+                .as_ref()
+                .is_some_and(|(fragment, _)| fragment.module == *module)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct ComputePipelineKey {
+    layout: Option<PipelineLayout>,
+    stage: StageKey,
+}
+
+impl ComputePipelineKey {
+    fn new(descriptor: &ComputePipelineDescriptor) -> Self {
+        Self {
+            layout: descriptor.layout.cloned(),
+            stage: StageKey::new(
+                descriptor.module,
+                descriptor.entry_point,
+                &descriptor.compilation_options,
+            ),
+        }
+    }
+}
+
+fn poll_ready<F: Future>(future: F) -> Option<F::Output> {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => Some(output),
+        Poll::Pending => None,
+    }
+}
+
+struct RegistryKeys {
+    shader_modules: HashSet<String>,
+    bind_group_layouts: HashSet<Vec<BindGroupLayoutEntry>>,
+    pipeline_layouts: HashSet<PipelineLayoutKey>,
+    render_pipelines: HashSet<RenderPipelineKey>,
+    compute_pipelines: HashSet<ComputePipelineKey>,
+}
+
+impl PipelineRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn enable_cache(&mut self, device: &Device) -> bool {
+        unsafe { self.create_cache(device, None) }
+    }
+
+    /// # Safety
+    ///
+    /// `data` must have been returned by [`PipelineRegistry::cache_data`] on the same adapter.
+    pub unsafe fn enable_cache_with_data(&mut self, device: &Device, data: &[u8]) -> bool {
+        unsafe { self.create_cache(device, Some(data)) }
+    }
+
+    unsafe fn create_cache(&mut self, device: &Device, data: Option<&[u8]>) -> bool {
+        if !device.features().contains(Features::PIPELINE_CACHE) {
+            return false;
+        }
+
+        self.cache = Some(unsafe {
+            device.create_pipeline_cache(&PipelineCacheDescriptor {
+                label: Some("RenderGraph Pipeline Cache"),
+                data,
+                fallback: true,
+            })
+        });
+        true
+    }
+
+    pub fn cache_data(&self) -> Option<Vec<u8>> {
+        self.cache.as_ref().and_then(PipelineCache::get_data)
+    }
+
+    pub fn shader_module(&mut self, device: &Device, label: &str, wgsl: &str) -> ShaderModule {
+        if let Some(previous) = self
+            .shader_labels
+            .insert(label.to_string(), wgsl.to_string())
+            .filter(|previous| previous != wgsl)
+        {
+            self.evict_shader_module(&previous);
+        }
+
+        let reused = &mut self.reused;
+        self.shader_modules
+            .entry(wgsl.to_string())
+            .and_modify(|_| *reused += 1)
+            .or_insert_with(|| {
+                device.create_shader_module(ShaderModuleDescriptor {
+                    label: Some(label),
+                    source: ShaderSource::Wgsl(wgsl.into()),
+                })
+            })
+            .clone()
+    }
+
+    fn evict_shader_module(&mut self, wgsl: &str) {
+        if self.shader_labels.values().any(|source| source == wgsl) {
+            return;
+        }
+        if let Some(module) = self.shader_modules.remove(wgsl) {
+            self.evict_pipelines(&module);
+        }
+    }
+
+    fn evict_pipelines(&mut self, module: &ShaderModule) {
+        self.render_pipelines.retain(|key, _| !key.uses(module));
+        self.compute_pipelines
+            .retain(|key, _| key.stage.module != *module);
+    }
+
+    pub fn bind_group_layout(
+        &mut self,
+        device: &Device,
+        descriptor: &BindGroupLayoutDescriptor,
+    ) -> BindGroupLayout {
+        let reused = &mut self.reused;
+        self.bind_group_layouts
+            .entry(descriptor.entries.to_vec())
+            .and_modify(|_| *reused += 1)
+            .or_insert_with(|| device.create_bind_group_layout(descriptor))
+            .clone()
+    }
+
+    pub fn pipeline_layout(
+        &mut self,
+        device: &Device,
+        descriptor: &PipelineLayoutDescriptor,
+    ) -> PipelineLayout {
+        let reused = &mut self.reused;
+        self.pipeline_layouts
+            .entry((
+                descriptor
+                    .bind_group_layouts
+                    .iter()
+                    .map(|&layout| layout.clone())
+                    .collect(),
+                descriptor.push_constant_ranges.to_vec(),
+            ))
+            .and_modify(|_| *reused += 1)
+            .or_insert_with(|| device.create_pipeline_layout(descriptor))
+            .clone()
+    }
+
+    pub fn render_pipeline(
+        &mut self,
+        device: &Device,
+        descriptor: &RenderPipelineDescriptor,
+    ) -> RenderPipeline {
+        let cache = self.cache.as_ref();
+        let reused = &mut self.reused;
+        self.render_pipelines
+            .entry(RenderPipelineKey::new(descriptor))
+            .and_modify(|_| *reused += 1)
+            .or_insert_with(|| {
+                device.create_render_pipeline(&RenderPipelineDescriptor {
+                    cache: descriptor.cache.or(cache),
+                    ..descriptor.clone()
+                })
+            })
+            .clone()
+    }
+
+    pub fn compute_pipeline(
+        &mut self,
+        device: &Device,
+        descriptor: &ComputePipelineDescriptor,
+    ) -> ComputePipeline {
+        let cache = self.cache.as_ref();
+        let reused = &mut self.reused;
+        self.compute_pipelines
+            .entry(ComputePipelineKey::new(descriptor))
+            .and_modify(|_| *reused += 1)
+            .or_insert_with(|| {
+                device.create_compute_pipeline(&ComputePipelineDescriptor {
+                    cache: descriptor.cache.or(cache),
+                    ..descriptor.clone()
+                })
+            })
+            .clone()
+    }
+
+    pub fn try_create<T>(
+        &mut self,
+        device: &Device,
+        create: impl FnOnce(&mut Self) -> T,
+    ) -> Result<T, wgpu::Error> {
+        let keys = self.keys();
+
+        device.push_error_scope(ErrorFilter::Internal);
+        device.push_error_scope(ErrorFilter::Validation);
+        let value = create(self);
+        let validation = poll_ready(device.pop_error_scope()).flatten();
+        let internal = poll_ready(device.pop_error_scope()).flatten();
+
+        match validation.or(internal) {
+            Some(error) => {
+                self.retain(&keys);
+                Err(error)
+            }
+            None => Ok(value),
+        }
+    }
+
+    fn keys(&self) -> RegistryKeys {
+        RegistryKeys {
+            shader_modules: self.shader_modules.keys().cloned().collect(),
+            bind_group_layouts: self.bind_group_layouts.keys().cloned().collect(),
+            pipeline_layouts: self.pipeline_layouts.keys().cloned().collect(),
+            render_pipelines: self.render_pipelines.keys().cloned().collect(),
+            compute_pipelines: self.compute_pipelines.keys().cloned().collect(),
+        }
+    }
+
+    fn retain(&mut self, keys: &RegistryKeys) {
+        self.shader_modules
+            .retain(|key, _| keys.shader_modules.contains(key));
+        self.bind_group_layouts
+            .retain(|key, _| keys.bind_group_layouts.contains(key));
+        self.pipeline_layouts
+            .retain(|key, _| keys.pipeline_layouts.contains(key));
+        self.render_pipelines
+            .retain(|key, _| keys.render_pipelines.contains(key));
+        self.compute_pipelines
+            .retain(|key, _| keys.compute_pipelines.contains(key));
+    }
+
+    pub fn stats(&self) -> PipelineRegistryStats {
+        PipelineRegistryStats {
+            shader_modules: self.shader_modules.len(),
+            bind_group_layouts: self.bind_group_layouts.len(),
+            pipeline_layouts: self.pipeline_layouts.len(),
+            render_pipelines: self.render_pipelines.len(),
+            compute_pipelines: self.compute_pipelines.len(),
+            reused: self.reused,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.shader_modules.clear();
+        self.shader_labels.clear();
+        self.bind_group_layouts.clear();
+        self.pipeline_layouts.clear();
+        self.render_pipelines.clear();
+        self.compute_pipelines.clear();
+    }
+}
+
+impl<C> RenderGraph<C> {
+    pub fn pipelines(&self) -> &PipelineRegistry {
+        &self.pipelines
+    }
+
+    pub fn pipelines_mut(&mut self) -> &mut PipelineRegistry {
+        &mut self.pipelines
+    }
+}
@@ -0,0 +1,371 @@
+use crate::RenderGraph;
+use std::sync::{Arc, Mutex};
+use web_time::Duration;
+use wgpu::{
+    Buffer, BufferAsyncError, BufferDescriptor, BufferUsages, CommandEncoder, Device, Features,
+    QuerySet, QuerySetDescriptor, QueryType, Queue,
+};
+
+const MAX_FRAMES_IN_FLIGHT: usize = 3;
+const MAX_COMPLETED_FRAMES: usize = 64;
+const TIMESTAMP_SIZE: u64 = std::mem::size_of::<u64>() as u64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassTiming {
+    pub name: String,
+    pub cpu_prepare: Duration,
+    pub cpu_execute: Duration,
+    pub gpu: Option<Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameTimings {
+    pub frame: u64,
+    pub passes: Vec<PassTiming>,
+}
+
+impl FrameTimings {
+    pub fn pass(&self, name: &str) -> Option<&PassTiming> {
+        self.passes.iter().find(|timing| timing.name == name)
+    }
+
+    pub fn has_gpu_timings(&self) -> bool {
+        self.passes.iter().any(|timing| timing.gpu.is_some())
+    }
+
+    pub fn cpu_total(&self) -> Duration {
+        self.passes
+            .iter()
+            .map(|timing| timing.cpu_prepare + timing.cpu_execute)
+            .sum()
+    }
+
+    pub fn gpu_total(&self) -> Option<Duration> {
+        self.passes.iter().map(|timing| timing.gpu).sum()
+    }
+}
+
+type MapResult = Arc<Mutex<Option<std::result::Result<(), BufferAsyncError>>>>;
+
+enum ReadbackState {
+    Free,
+    Recorded,
+    Mapping(MapResult),
+}
+
+struct Readback {
+    buffer: Buffer,
+    state: ReadbackState,
+    frame: Option<FrameTimings>,
+    queries: Vec<(usize, u32)>,
+    query_count: u32,
+}
+
+struct GpuTimer {
+    query_set: QuerySet,
+    resolve_buffer: Buffer,
+    capacity: u32,
+    readbacks: Vec<Readback>,
+}
+
+impl GpuTimer {
+    fn new(device: &Device, capacity: u32) -> Self {
+        Self {
+            query_set: device.create_query_set(&QuerySetDescriptor {
+                label: Some("RenderGraph Timestamps"),
+                ty: QueryType::Timestamp,
+                count: capacity,
+            }),
+            resolve_buffer: device.create_buffer(&BufferDescriptor {
+                label: Some("RenderGraph Timestamp Resolve"),
+                size: capacity as u64 * TIMESTAMP_SIZE,
+                usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            }),
+            capacity,
+            readbacks: Vec::new(),
+        }
+    }
+
+    fn acquire_readback(&mut self, device: &Device) -> Option<usize> {
+        if let Some(index) = self
+            .readbacks
+            .iter()
+            .position(|readback| matches!(readback.state, ReadbackState::Free))
+        {
+            if self.readbacks[index].buffer.size() < self.resolve_buffer.size() {
+                self.readbacks[index].buffer = create_readback_buffer(device, self.capacity);
+            }
+            return Some(index);
+        }
+
+        if self.readbacks.len() < MAX_FRAMES_IN_FLIGHT {
+            self.readbacks.push(Readback {
+                buffer: create_readback_buffer(device, self.capacity),
+                state: ReadbackState::Free,
+                frame: None,
+                queries: Vec::new(),
+                query_count: 0,
+            });
+            return Some(self.readbacks.len() - 1);
+        }
+
+        None
+    }
+
+    fn is_idle(&self) -> bool {
+        self.readbacks
+            .iter()
+            .all(|readback| matches!(readback.state, ReadbackState::Free))
+    }
+}
+
+fn create_readback_buffer(device: &Device, capacity: u32) -> Buffer {
+    device.create_buffer(&BufferDescriptor {
+        label: Some("RenderGraph Timestamp Readback"),
+        size: capacity as u64 * TIMESTAMP_SIZE,
+        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+#[derive(Default)]
+pub(crate) struct Profiler {
+    frame: u64,
+    timings: Vec<PassTiming>,
+    queries: Vec<(usize, u32)>,
+    next_query: u32,
+    timestamp_period: f32,
+    gpu: Option<GpuTimer>,
+    active_readback: Option<usize>,
+    completed: Vec<FrameTimings>,
+}
+
+impl Profiler {
+    pub(crate) fn begin_frame(&mut self, device: &Device, queue: &Queue, pass_count: usize) {
+        self.collect();
+
+        self.frame += 1;
+        self.timings.clear();
+        self.queries.clear();
+        self.next_query = 0;
+        self.active_readback = None;
+
+        let required = Features::TIMESTAMP_QUERY | Features::TIMESTAMP_QUERY_INSIDE_ENCODERS;
+        if !device.features().contains(required) {
+            return;
+        }
+
+        self.timestamp_period = queue.get_timestamp_period();
+
+        let capacity = (pass_count.max(1) as u32 * 2)
+            .next_power_of_two()
+            .min(wgpu::QUERY_SET_MAX_QUERIES);
+
+        let needs_new_timer = match &self.gpu {
+            Some(gpu) => gpu.capacity < capacity && gpu.is_idle(),
+            None => true,
+        };
+        if needs_new_timer {
+            self.gpu = Some(GpuTimer::new(device, capacity));
+        }
+
+        if let Some(gpu) = &mut self.gpu {
+            self.active_readback = gpu.acquire_readback(device);
+        }
+    }
+
+    fn timing_mut(&mut self, name: &str) -> usize {
+        match self.timings.iter().position(|timing| timing.name == name) {
+            Some(index) => index,
+            None => {
+                self.timings.push(PassTiming {
+                    name: name.to_string(),
+                    cpu_prepare: Duration::ZERO,
+                    cpu_execute: Duration::ZERO,
+                    gpu: None,
+                });
+                self.timings.len() - 1
+            }
+        }
+    }
+
+    pub(crate) fn record_prepare(&mut self, name: &str, duration: Duration) {
+        let index = self.timing_mut(name);
+        self.timings[index].cpu_prepare = duration;
+    }
+
+    pub(crate) fn begin_pass(&mut self, encoder: &mut CommandEncoder) -> Option<u32> {
+        self.active_readback?;
+        let gpu = self.gpu.as_ref()?;
+        if self.next_query + 2 > gpu.capacity {
+            return None;
+        }
+
+        let query = self.next_query;
+        encoder.write_timestamp(&gpu.query_set, query);
+        self.next_query += 2;
+        Some(query)
+    }
+
+    pub(crate) fn end_pass(
+        &mut self,
+        encoder: &mut CommandEncoder,
+        name: &str,
+        duration: Duration,
+        query: Option<u32>,
+    ) {
+        let index = self.timing_mut(name);
+        self.timings[index].cpu_execute = duration;
+
+        if let (Some(query), Some(gpu)) = (query, &self.gpu) {
+            encoder.write_timestamp(&gpu.query_set, query + 1);
+            self.queries.push((index, query));
+        }
+    }
+
+    pub(crate) fn end_frame(&mut self, encoder: &mut CommandEncoder) {
+        let frame = FrameTimings {
+            frame: self.frame,
+            passes: std::mem::take(&mut self.timings),
+        };
+
+        let (Some(readback_index), Some(gpu)) = (self.active_readback.take(), &mut self.gpu) else {
+            self.push_completed(frame);
+            return;
+        };
+
+        if self.next_query == 0 {
+            self.push_completed(frame);
+            return;
+        }
+
+        let size = self.next_query as u64 * TIMESTAMP_SIZE;
+        encoder.resolve_query_set(&gpu.query_set, 0..self.next_query, &gpu.resolve_buffer, 0);
+
+        let readback = &mut gpu.readbacks[readback_index];
+        encoder.copy_buffer_to_buffer(&gpu.resolve_buffer, 0, &readback.buffer, 0, size);
+        readback.state = ReadbackState::Recorded;
+        readback.frame = Some(frame);
+        readback.queries = std::mem::take(&mut self.queries);
+        readback.query_count = self.next_query;
+    }
+
+    pub(crate) fn frame_submitted(&mut self) {
+        let Some(gpu) = &mut self.gpu else {
+            return;
+        };
+
+        for readback in &mut gpu.readbacks {
+            if !matches!(readback.state, ReadbackState::Recorded) {
+                continue;
+            }
+
+            let result: MapResult = Arc::new(Mutex::new(None));
+            let callback_result = Arc::clone(&result);
+            readback
+                .buffer
+                .slice(..readback.query_count as u64 * TIMESTAMP_SIZE)
+                .map_async(wgpu::MapMode::Read, move |map_result| {
+                    *callback_result.lock().unwrap() = Some(map_result);
+                });
+            readback.state = ReadbackState::Mapping(result);
+        }
+    }
+
+    fn collect(&mut self) {
+        let Some(gpu) = &mut self.gpu else {
+            return;
+        };
+
+        let mut finished = Vec::new();
+
+        for readback in &mut gpu.readbacks {
+            match &readback.state {
+                ReadbackState::Free | ReadbackState::Recorded => {}
+                ReadbackState::Mapping(result) => {
+                    let Some(map_result) = result.lock().unwrap().take() else {
+                        continue;
+                    };
+
+                    let Some(mut frame) = readback.frame.take() else {
+                        readback.state = ReadbackState::Free;
+                        continue;
+                    };
+
+                    if map_result.is_ok() {
+                        let size = readback.query_count as u64 * TIMESTAMP_SIZE;
+                        let timestamps: Vec<u64> = readback
+                            .buffer
+                            .slice(..size)
+                            .get_mapped_range()
+                            .chunks_exact(TIMESTAMP_SIZE as usize)
+                            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+                            .collect();
+                        readback.buffer.unmap();
+
+                        for &(timing_index, query) in &readback.queries {
+                            let start = timestamps[query as usize];
+                            let end = timestamps[query as usize + 1];
+                            let nanoseconds =
+                                end.saturating_sub(start) as f64 * self.timestamp_period as f64;
+                            frame.passes[timing_index].gpu =
+                                Some(Duration::from_nanos(nanoseconds as u64));
+                        }
+                    }
+
+                    readback.state = ReadbackState::Free;
+                    finished.push(frame);
+                }
+            }
+        }
+
+        finished.sort_by_key(|frame| frame.frame);
+        for frame in finished {
+            self.push_completed(frame);
+        }
+    }
+
+    fn push_completed(&mut self, frame: FrameTimings) {
+        if self.completed.len() == MAX_COMPLETED_FRAMES {
+            self.completed.remove(0);
+        }
+        self.completed.push(frame);
+    }
+
+    pub(crate) fn take_completed(&mut self) -> Vec<FrameTimings> {
+        self.collect();
+        let mut completed = std::mem::take(&mut self.completed);
+        completed.sort_by_key(|frame| frame.frame);
+        completed
+    }
+}
+
+impl<C> RenderGraph<C> {
+    pub fn enable_profiling(&mut self) {
+        if self.profiler.is_none() {
+            self.profiler = Some(Profiler::default());
+        }
+    }
+
+    pub fn disable_profiling(&mut self) {
+        self.profiler = None;
+    }
+
+    pub fn is_profiling(&self) -> bool {
+        self.profiler.is_some()
+    }
+
+    pub fn profiler_frame_submitted(&mut self) {
+        if let Some(profiler) = &mut self.profiler {
+            profiler.frame_submitted();
+        }
+    }
+
+    pub fn take_frame_timings(&mut self) -> Vec<FrameTimings> {
+        self.profiler
+            .as_mut()
+            .map(Profiler::take_completed)
+            .unwrap_or_default()
+    }
+}
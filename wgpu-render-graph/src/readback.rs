@@ -0,0 +1,582 @@
+use crate::backend::RenderGraphBackend;
+use crate::{
+    RenderGraph, RenderGraphError, RenderGraphResources, ResourceHandle, ResourceId, ResourceType,
+    Result,
+};
+use petgraph::stable_graph::NodeIndex;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use wgpu::{
+    Buffer, BufferDescriptor, BufferUsages, COPY_BUFFER_ALIGNMENT, COPY_BYTES_PER_ROW_ALIGNMENT,
+    CommandEncoder, Extent3d, Origin3d, TexelCopyBufferInfo, TexelCopyBufferLayout,
+    TexelCopyTextureInfo, Texture, TextureAspect, TextureFormat, TextureUsages,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReadbackData {
+    Texture {
+        format: TextureFormat,
+        width: u32,
+        height: u32,
+        bytes: Vec<u8>,
+    },
+    Buffer {
+        bytes: Vec<u8>,
+    },
+}
+
+impl ReadbackData {
+    pub fn bytes(&self) -> &[u8] {
+        match self {
+            ReadbackData::Texture { bytes, .. } | ReadbackData::Buffer { bytes } => bytes,
+        }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        match self {
+            ReadbackData::Texture { bytes, .. } | ReadbackData::Buffer { bytes } => bytes,
+        }
+    }
+
+    #[cfg(feature = "image")]
+    pub fn to_rgba_image(&self) -> Option<image::RgbaImage> {
+        let ReadbackData::Texture {
+            format,
+            width,
+            height,
+            bytes,
+        } = self
+        else {
+            return None;
+        };
+
+        let pixels = match format {
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => bytes.clone(),
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => bytes
+                .chunks_exact(4)
+                .flat_map(|texel| [texel[2], texel[1], texel[0], texel[3]])
+                .collect(),
+            _ => return None,
+        };
+
+        image::RgbaImage::from_raw(*width, *height, pixels)
+    }
+}
+
+type ReadbackCallback = Box<dyn FnOnce(Result<ReadbackData>) + Send>;
+
+#[derive(Default)]
+struct ReadbackState {
+    result: Option<Result<ReadbackData>>,
+    waker: Option<Waker>,
+    callback: Option<ReadbackCallback>,
+}
+
+type SharedReadback = Arc<Mutex<ReadbackState>>;
+
+fn complete(shared: &SharedReadback, result: Result<ReadbackData>) {
+    let mut state = shared.lock().unwrap();
+    match state.callback.take() {
+        Some(callback) => {
+            drop(state);
+            callback(result);
+        }
+        None => {
+            state.result = Some(result);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+pub struct Readback {
+    shared: SharedReadback,
+}
+
+impl Readback {
+    pub fn is_ready(&self) -> bool {
+        self.shared.lock().unwrap().result.is_some()
+    }
+
+    pub fn try_take(&self) -> Option<Result<ReadbackData>> {
+        self.shared.lock().unwrap().result.take()
+    }
+}
+
+impl Future for Readback {
+    type Output = Result<ReadbackData>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.shared.lock().unwrap();
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(context.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ReadbackLayout {
+    Texture {
+        format: TextureFormat,
+        width: u32,
+        height: u32,
+        padded_bytes_per_row: u32,
+        bytes_per_row: u32,
+        rows: u32,
+    },
+    Buffer {
+        size: u64,
+    },
+}
+
+impl ReadbackLayout {
+    fn staging_size(&self) -> u64 {
+        match *self {
+            ReadbackLayout::Texture {
+                padded_bytes_per_row,
+                rows,
+                ..
+            } => padded_bytes_per_row as u64 * rows as u64,
+            ReadbackLayout::Buffer { size } => size,
+        }
+    }
+
+    fn unpad(&self, mapped: &[u8]) -> ReadbackData {
+        match *self {
+            ReadbackLayout::Texture {
+                format,
+                width,
+                height,
+                padded_bytes_per_row,
+                bytes_per_row,
+                ..
+            } => ReadbackData::Texture {
+                format,
+                width,
+                height,
+                bytes: mapped
+                    .chunks(padded_bytes_per_row as usize)
+                    .flat_map(|row| &row[..bytes_per_row as usize])
+                    .copied()
+                    .collect(),
+            },
+            ReadbackLayout::Buffer { .. } => ReadbackData::Buffer {
+                bytes: mapped.to_vec(),
+            },
+        }
+    }
+}
+
+fn readback_aspect(format: TextureFormat) -> TextureAspect {
+    if format.has_depth_aspect() {
+        TextureAspect::DepthOnly
+    } else if format.has_stencil_aspect() {
+        TextureAspect::StencilOnly
+    } else {
+        TextureAspect::All
+    }
+}
+
+fn texture_layout(format: TextureFormat, width: u32, height: u32) -> Option<ReadbackLayout> {
+    let aspect = readback_aspect(format);
+    let block_size = format.block_copy_size(Some(aspect))?;
+    let (block_width, block_height) = format.block_dimensions();
+    let bytes_per_row = width.div_ceil(block_width) * block_size;
+
+    Some(ReadbackLayout::Texture {
+        format: format.aspect_specific_format(aspect).unwrap_or(format),
+        width,
+        height,
+        padded_bytes_per_row: bytes_per_row.next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT),
+        bytes_per_row,
+        rows: height.div_ceil(block_height),
+    })
+}
+
+enum CopySource<'a> {
+    Texture(&'a Texture),
+    Buffer(&'a Buffer),
+}
+
+struct ReadbackRequest {
+    resource: ResourceId,
+    shared: SharedReadback,
+}
+
+struct RecordedReadback {
+    resource: String,
+    buffer: Buffer,
+    layout: ReadbackLayout,
+    shared: SharedReadback,
+}
+
+#[derive(Default)]
+pub(crate) struct ReadbackQueue {
+    marked: HashSet<ResourceId>,
+    pending: Vec<ReadbackRequest>,
+    recorded: Vec<RecordedReadback>,
+}
+
+impl ReadbackQueue {
+    pub(crate) fn is_marked(&self, id: ResourceId) -> bool {
+        self.marked.contains(&id)
+    }
+
+    pub(crate) fn record<B: RenderGraphBackend + ?Sized>(
+        &mut self,
+        backend: &B,
+        encoder: &mut CommandEncoder,
+        resources: &RenderGraphResources,
+        ready: impl Fn(ResourceId) -> bool,
+    ) {
+        let (ready, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|request| ready(request.resource));
+        self.pending = pending;
+
+        for request in ready {
+            match Self::encode_copy(backend, encoder, resources, request.resource) {
+                Ok((buffer, layout)) => self.recorded.push(RecordedReadback {
+                    resource: resources
+                        .get_descriptor(request.resource)
+                        .map(|descriptor| descriptor.name.clone())
+                        .unwrap_or_default(),
+                    buffer,
+                    layout,
+                    shared: request.shared,
+                }),
+                Err(error) => complete(&request.shared, Err(error)),
+            }
+        }
+    }
+
+    pub(crate) fn reject_unwritten(
+        &mut self,
+        resources: &RenderGraphResources,
+        writers: &HashMap<ResourceId, NodeIndex>,
+    ) {
+        let (rejected, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|request| {
+                !writers.contains_key(&request.resource)
+                    && !resources.is_history(request.resource)
+                    && resources
+                        .get_descriptor(request.resource)
+                        .is_some_and(|descriptor| !descriptor.is_external)
+            });
+        self.pending = pending;
+
+        for request in rejected {
+            complete(
+                &request.shared,
+                Err(RenderGraphError::ReadbackFailed {
+                    resource: resources
+                        .get_descriptor(request.resource)
+                        .map(|descriptor| descriptor.name.clone())
+                        .unwrap_or_default(),
+                    reason: "no enabled pass wrote it this frame".to_string(),
+                }),
+            );
+        }
+    }
+
+    fn encode_copy<B: RenderGraphBackend + ?Sized>(
+        backend: &B,
+        encoder: &mut CommandEncoder,
+        resources: &RenderGraphResources,
+        id: ResourceId,
+    ) -> Result<(Buffer, ReadbackLayout)> {
+        let descriptor =
+            resources
+                .get_descriptor(id)
+                .ok_or_else(|| RenderGraphError::DescriptorNotFound {
+                    resource: format!("{:?}", id),
+                    id,
+                })?;
+        let handle =
+            resources
+                .get_handle(id)
+                .ok_or_else(|| RenderGraphError::ResourceNotBound {
+                    resource: descriptor.name.clone(),
+                    id,
+                })?;
+
+        let unsupported = |reason: &str| RenderGraphError::ReadbackUnsupported {
+            resource: descriptor.name.clone(),
+            reason: reason.to_string(),
+        };
+
+        let (source, layout) = match (&descriptor.resource_type, handle) {
+            (
+                ResourceType::TransientColor {
+                    descriptor: texture_desc,
+                    ..
+                }
+                | ResourceType::TransientDepth {
+                    descriptor: texture_desc,
+                    ..
+                },
+                ResourceHandle::TransientTexture { texture, .. },
+            ) => (
+                CopySource::Texture(texture),
+                texture_layout(texture_desc.format, texture_desc.width, texture_desc.height),
+            ),
+            (_, ResourceHandle::ExternalTexture { texture: None, .. }) => {
+                return Err(unsupported(
+                    "the external texture was bound as a view only; bind it with set_external_texture_from",
+                ));
+            }
+            (
+                _,
+                ResourceHandle::ExternalTexture {
+                    texture: Some(texture),
+                    ..
+                },
+            ) => {
+                if texture.sample_count() > 1 {
+                    return Err(unsupported("multisampled textures cannot be copied"));
+                }
+                if !texture.usage().contains(TextureUsages::COPY_SRC) {
+                    return Err(unsupported("the bound texture lacks COPY_SRC usage"));
+                }
+                (
+                    CopySource::Texture(texture),
+                    texture_layout(texture.format(), texture.width(), texture.height()),
+                )
+            }
+            (
+                _,
+                ResourceHandle::TransientBuffer { buffer }
+                | ResourceHandle::ExternalBuffer { buffer },
+            ) => (
+                CopySource::Buffer(buffer),
+                Some(ReadbackLayout::Buffer {
+                    size: buffer.size() / COPY_BUFFER_ALIGNMENT * COPY_BUFFER_ALIGNMENT,
+                }),
+            ),
+            _ => return Err(unsupported("the resource cannot be copied")),
+        };
+        let layout = layout.ok_or_else(|| unsupported("the resource cannot be copied"))?;
+
+        let label = format!("{} readback", descriptor.name);
+        let staging = backend.create_buffer(&BufferDescriptor {
+            label: Some(&label),
+            size: layout.staging_size(),
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        match (source, layout) {
+            (
+                CopySource::Texture(texture),
+                ReadbackLayout::Texture {
+                    format,
+                    width,
+                    height,
+                    padded_bytes_per_row,
+                    rows,
+                    ..
+                },
+            ) => {
+                let placement = resources.get_pool_placement(id).unwrap_or_default();
+                encoder.copy_texture_to_buffer(
+                    TexelCopyTextureInfo {
+                        texture,
+                        mip_level: placement.base_mip_level,
+                        origin: Origin3d {
+                            x: 0,
+                            y: 0,
+                            z: placement.base_array_layer,
+                        },
+                        aspect: readback_aspect(format),
+                    },
+                    TexelCopyBufferInfo {
+                        buffer: &staging,
+                        layout: TexelCopyBufferLayout {
+                            offset: 0,
+                            bytes_per_row: Some(padded_bytes_per_row),
+                            rows_per_image: Some(rows),
+                        },
+                    },
+                    Extent3d {
+                        width,
+                        height,
+                        depth_or_array_layers: 1,
+                    },
+                );
+            }
+            (CopySource::Buffer(buffer), ReadbackLayout::Buffer { size }) => {
+                encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, size)
+            }
+            _ => {}
+        }
+
+        Ok((staging, layout))
+    }
+
+    pub(crate) fn map(&mut self) {
+        for recorded in self.recorded.drain(..) {
+            let RecordedReadback {
+                resource,
+                buffer,
+                layout,
+                shared,
+            } = recorded;
+
+            if layout.staging_size() == 0 {
+                complete(&shared, Ok(layout.unpad(&[])));
+                continue;
+            }
+
+            let mapped = buffer.clone();
+            buffer
+                .slice(..)
+                .map_async(wgpu::MapMode::Read, move |map_result| {
+                    let result = match map_result {
+                        Ok(()) => {
+                            let data = layout.unpad(&mapped.slice(..).get_mapped_range());
+                            mapped.unmap();
+                            Ok(data)
+                        }
+                        Err(error) => Err(RenderGraphError::ReadbackFailed {
+                            resource,
+                            reason: error.to_string(),
+                        }),
+                    };
+                    complete(&shared, result);
+                });
+        }
+    }
+}
+
+impl RenderGraphResources {
+    fn add_copy_src(&mut self, id: ResourceId) {
+        if let Some(
+            ResourceType::TransientColor { descriptor, .. }
+            | ResourceType::TransientDepth { descriptor, .. },
+        ) = self
+            .descriptors
+            .get_mut(&id)
+            .map(|descriptor| &mut descriptor.resource_type)
+        {
+            descriptor.usage |= TextureUsages::COPY_SRC;
+        }
+    }
+}
+
+impl<C> RenderGraph<C> {
+    pub fn read_back(&mut self, id: ResourceId) -> Result<Readback> {
+        let shared = SharedReadback::default();
+        self.request_readback(id, Arc::clone(&shared))?;
+        Ok(Readback { shared })
+    }
+
+    pub fn read_back_with(
+        &mut self,
+        id: ResourceId,
+        callback: impl FnOnce(Result<ReadbackData>) + Send + 'static,
+    ) -> Result<()> {
+        let shared = SharedReadback::default();
+        shared.lock().unwrap().callback = Some(Box::new(callback));
+        self.request_readback(id, shared)
+    }
+
+    pub fn map_readbacks(&mut self) {
+        self.readbacks.map();
+    }
+
+    fn request_readback(&mut self, id: ResourceId, shared: SharedReadback) -> Result<()> {
+        let name = self.resource_name(id);
+        let descriptor = self.resources.descriptors.get_mut(&id).ok_or_else(|| {
+            RenderGraphError::ResourceNotFound {
+                resource: name.clone(),
+                id,
+            }
+        })?;
+
+        let unsupported = |reason: &str| RenderGraphError::ReadbackUnsupported {
+            resource: name.clone(),
+            reason: reason.to_string(),
+        };
+
+        let grown = match &mut descriptor.resource_type {
+            ResourceType::TransientColor { descriptor, .. }
+            | ResourceType::TransientDepth { descriptor, .. } => {
+                if descriptor.sample_count > 1 {
+                    return Err(unsupported("multisampled textures cannot be copied"));
+                }
+                if texture_layout(descriptor.format, descriptor.width, descriptor.height).is_none()
+                {
+                    return Err(unsupported(&format!(
+                        "{:?} textures cannot be copied to a buffer",
+                        descriptor.format
+                    )));
+                }
+                let grown = !descriptor.usage.contains(TextureUsages::COPY_SRC);
+                descriptor.usage |= TextureUsages::COPY_SRC;
+                grown
+            }
+            ResourceType::TransientBuffer { descriptor } => {
+                let grown = !descriptor.usage.contains(BufferUsages::COPY_SRC);
+                descriptor.usage |= BufferUsages::COPY_SRC;
+                grown
+            }
+            ResourceType::ExternalBuffer
+            | ResourceType::ExternalColor { .. }
+            | ResourceType::ExternalDepth { .. } => false,
+        };
+
+        if grown {
+            if let Some((current, previous)) = self.resources.history_pair(id) {
+                self.resources.add_copy_src(current);
+                self.resources.add_copy_src(previous);
+                self.resources.discard_history(id);
+            }
+            self.needs_recompile = true;
+        }
+
+        if self.readbacks.marked.insert(id) {
+            self.needs_recompile = true;
+        }
+
+        self.readbacks.pending.push(ReadbackRequest {
+            resource: id,
+            shared,
+        });
+        Ok(())
+    }
+
+    pub(crate) fn readback_writers(&self, configs: &C) -> HashMap<ResourceId, NodeIndex> {
+        let mut writers = HashMap::new();
+
+        for &node_index in &self.execution_order {
+            if self.culled_passes.contains(&node_index) {
+                continue;
+            }
+
+            let node = &self.graph[node_index];
+            if !node.pass.is_enabled(configs) {
+                continue;
+            }
+
+            for request in &self.readbacks.pending {
+                if node.writes.contains(&request.resource)
+                    || node.reads_writes.contains(&request.resource)
+                    || node.resolves.contains(&request.resource)
+                {
+                    writers.insert(request.resource, node_index);
+                }
+            }
+        }
+
+        writers
+    }
+}
@@ -0,0 +1,276 @@
+use crate::{AttachmentLoadOp, MemoryBudget, PoolDescriptorInfo, RenderGraph, ResourceId};
+use petgraph::stable_graph::NodeIndex;
+use std::collections::HashMap;
+use wgpu::StoreOp;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CullReason {
+    OutputsNeverConsumed { outputs: Vec<String> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CulledPass {
+    pub name: String,
+    pub reason: CullReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasAssignment {
+    pub resource: ResourceId,
+    pub name: String,
+    pub pool_slot: usize,
+    pub base_mip_level: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct PoolSlotReport {
+    pub index: usize,
+    pub descriptor: Option<PoolDescriptorInfo>,
+    pub resources: Vec<String>,
+    pub lifetime_end: usize,
+    pub estimated_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceStoreOp {
+    pub resource: ResourceId,
+    pub name: String,
+    pub store_op: StoreOp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassLoadOp {
+    pub pass: String,
+    pub resource: ResourceId,
+    pub name: String,
+    pub load_op: AttachmentLoadOp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassStoreOp {
+    pub pass: String,
+    pub resource: ResourceId,
+    pub name: String,
+    pub store_op: StoreOp,
+}
+
+#[derive(Debug, Clone)]
+pub struct CompiledGraphReport {
+    pub execution_order: Vec<String>,
+    pub culled_passes: Vec<CulledPass>,
+    pub aliases: Vec<AliasAssignment>,
+    pub pools: Vec<PoolSlotReport>,
+    pub store_ops: Vec<ResourceStoreOp>,
+    pub load_ops: Vec<PassLoadOp>,
+    pub pass_store_ops: Vec<PassStoreOp>,
+    pub stencil_load_ops: Vec<PassLoadOp>,
+    pub stencil_store_ops: Vec<PassStoreOp>,
+    pub peak_transient_bytes: u64,
+    pub unaliased_transient_bytes: u64,
+    pub memory_budget: Option<MemoryBudget>,
+}
+
+impl CompiledGraphReport {
+    pub fn is_culled(&self, pass: &str) -> bool {
+        self.culled_passes.iter().any(|culled| culled.name == pass)
+    }
+
+    pub fn pool_slot(&self, resource: ResourceId) -> Option<usize> {
+        self.aliases
+            .iter()
+            .find(|alias| alias.resource == resource)
+            .map(|alias| alias.pool_slot)
+    }
+
+    pub fn is_over_budget(&self) -> bool {
+        self.memory_budget
+            .is_some_and(|budget| self.peak_transient_bytes > budget.bytes)
+    }
+
+    pub fn store_op(&self, resource: ResourceId) -> Option<StoreOp> {
+        self.store_ops
+            .iter()
+            .find(|entry| entry.resource == resource)
+            .map(|entry| entry.store_op)
+    }
+
+    pub fn pass_store_op(&self, pass: &str, resource: ResourceId) -> Option<StoreOp> {
+        self.pass_store_ops
+            .iter()
+            .find(|entry| entry.pass == pass && entry.resource == resource)
+            .map(|entry| entry.store_op)
+    }
+
+    pub fn load_op(&self, pass: &str, resource: ResourceId) -> Option<AttachmentLoadOp> {
+        self.load_ops
+            .iter()
+            .find(|entry| entry.pass == pass && entry.resource == resource)
+            .map(|entry| entry.load_op)
+    }
+
+    pub fn stencil_load_op(&self, pass: &str, resource: ResourceId) -> Option<AttachmentLoadOp> {
+        self.stencil_load_ops
+            .iter()
+            .find(|entry| entry.pass == pass && entry.resource == resource)
+            .map(|entry| entry.load_op)
+    }
+
+    pub fn stencil_store_op(&self, pass: &str, resource: ResourceId) -> Option<StoreOp> {
+        self.stencil_store_ops
+            .iter()
+            .find(|entry| entry.pass == pass && entry.resource == resource)
+            .map(|entry| entry.store_op)
+    }
+}
+
+impl<C> RenderGraph<C> {
+    pub fn report(&self) -> Option<CompiledGraphReport> {
+        if self.needs_recompile {
+            return None;
+        }
+
+        let execution_order = self
+            .execution_order
+            .iter()
+            .map(|&node_index| self.graph[node_index].name.clone())
+            .collect();
+
+        let culled_passes = self
+            .execution_order
+            .iter()
+            .filter(|node_index| self.culled_passes.contains(node_index))
+            .map(|&node_index| {
+                let node = &self.graph[node_index];
+                CulledPass {
+                    name: node.name.clone(),
+                    reason: CullReason::OutputsNeverConsumed {
+                        outputs: node
+                            .writes
+                            .iter()
+                            .chain(&node.reads_writes)
+                            .map(|&id| self.resource_name(id))
+                            .collect(),
+                    },
+                }
+            })
+            .collect();
+
+        let mut aliases = Vec::new();
+        let mut pools = Vec::new();
+        let mut unaliased_transient_bytes = 0;
+
+        if let Some(aliasing_info) = &self.aliasing_info {
+            aliases = aliasing_info
+                .aliases
+                .iter()
+                .map(|(&resource, &pool_slot)| AliasAssignment {
+                    resource,
+                    name: self.resource_name(resource),
+                    pool_slot,
+                    base_mip_level: aliasing_info
+                        .base_mip_levels
+                        .get(&resource)
+                        .copied()
+                        .unwrap_or(0),
+                })
+                .collect::<Vec<_>>();
+            aliases.sort_by_key(|alias| (alias.pool_slot, alias.resource.0));
+
+            pools = aliasing_info
+                .pools
+                .iter()
+                .enumerate()
+                .map(|(index, pool_slot)| PoolSlotReport {
+                    index,
+                    descriptor: pool_slot.descriptor_info().cloned(),
+                    resources: aliases
+                        .iter()
+                        .filter(|alias| alias.pool_slot == index)
+                        .map(|alias| alias.name.clone())
+                        .collect(),
+                    lifetime_end: pool_slot.lifetime_end(),
+                    estimated_bytes: pool_slot
+                        .descriptor_info()
+                        .map_or(0, PoolDescriptorInfo::estimated_bytes),
+                })
+                .collect();
+            unaliased_transient_bytes = self.unaliased_transient_bytes(aliasing_info);
+        }
+
+        let mut store_ops = self
+            .store_ops
+            .iter()
+            .map(|(&resource, &store_op)| ResourceStoreOp {
+                resource,
+                name: self.resource_name(resource),
+                store_op,
+            })
+            .collect::<Vec<_>>();
+        store_ops.sort_by_key(|entry| entry.resource.0);
+
+        Some(CompiledGraphReport {
+            execution_order,
+            culled_passes,
+            aliases,
+            pools,
+            store_ops,
+            load_ops: self.pass_load_op_entries(&self.load_ops),
+            pass_store_ops: self.pass_store_op_entries(&self.pass_store_ops),
+            stencil_load_ops: self.pass_load_op_entries(&self.stencil_load_ops),
+            stencil_store_ops: self.pass_store_op_entries(&self.pass_stencil_store_ops),
+            peak_transient_bytes: self.estimated_transient_bytes().unwrap_or(0),
+            unaliased_transient_bytes,
+            memory_budget: self.memory_budget(),
+        })
+    }
+
+    fn pass_load_op_entries(
+        &self,
+        load_ops: &HashMap<NodeIndex, HashMap<ResourceId, AttachmentLoadOp>>,
+    ) -> Vec<PassLoadOp> {
+        let mut load_op_entries = Vec::new();
+        for &node_index in &self.execution_order {
+            let Some(pass_load_ops) = load_ops.get(&node_index) else {
+                continue;
+            };
+
+            let mut entries = pass_load_ops
+                .iter()
+                .map(|(&resource, &load_op)| PassLoadOp {
+                    pass: self.graph[node_index].name.clone(),
+                    resource,
+                    name: self.resource_name(resource),
+                    load_op,
+                })
+                .collect::<Vec<_>>();
+            entries.sort_by_key(|entry| entry.resource.0);
+            load_op_entries.extend(entries);
+        }
+        load_op_entries
+    }
+
+    fn pass_store_op_entries(
+        &self,
+        store_ops: &HashMap<NodeIndex, HashMap<ResourceId, StoreOp>>,
+    ) -> Vec<PassStoreOp> {
+        let mut store_op_entries = Vec::new();
+        for &node_index in &self.execution_order {
+            let Some(node_store_ops) = store_ops.get(&node_index) else {
+                continue;
+            };
+
+            let mut entries = node_store_ops
+                .iter()
+                .map(|(&resource, &store_op)| PassStoreOp {
+                    pass: self.graph[node_index].name.clone(),
+                    resource,
+                    name: self.resource_name(resource),
+                    store_op,
+                })
+                .collect::<Vec<_>>();
+            entries.sort_by_key(|entry| entry.resource.0);
+            store_op_entries.extend(entries);
+        }
+        store_op_entries
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{RenderGraph, RenderGraphError, ResourceId, ResourceType, Result};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SizeMode {
+    #[default]
+    Fixed,
+    RelativeToOutput(f32),
+    RelativeToResource(ResourceId, f32),
+}
+
+fn scale_size((width, height): (u32, u32), scale: f32) -> (u32, u32) {
+    (
+        ((width as f32 * scale).round() as u32).max(1),
+        ((height as f32 * scale).round() as u32).max(1),
+    )
+}
+
+impl<C> RenderGraph<C> {
+    pub fn output_size(&self) -> Option<(u32, u32)> {
+        self.output_size
+    }
+
+    pub fn set_output_size(&mut self, width: u32, height: u32) -> Result<()> {
+        self.output_size = Some((width, height));
+        self.apply_size_modes()?;
+
+        for sub_graph in self.sub_graphs.values_mut() {
+            sub_graph.set_output_size(width, height)?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn apply_size_modes(&mut self) -> Result<()> {
+        let mut relative: Vec<ResourceId> = self
+            .resources
+            .size_modes
+            .iter()
+            .filter(|(_, mode)| **mode != SizeMode::Fixed)
+            .map(|(id, _)| *id)
+            .collect();
+        if relative.is_empty() {
+            return Ok(());
+        }
+        relative.sort_by_key(|id| id.0);
+
+        let mut resolved = HashMap::new();
+        let mut changed = false;
+
+        for id in relative {
+            let Some((width, height)) = self.resolve_size(id, &mut resolved, &mut Vec::new())?
+            else {
+                continue;
+            };
+
+            if self.texture_size(id) != Some((width, height)) {
+                self.resources
+                    .update_transient_descriptor(id, width, height)?;
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.aliasing_info = None;
+            self.resources.release_transient_handles();
+            self.needs_resource_reallocation = true;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn resolved_size(&self, id: ResourceId) -> Option<(u32, u32)> {
+        self.resolve_size(id, &mut HashMap::new(), &mut Vec::new())
+            .ok()
+            .flatten()
+    }
+
+    fn texture_size(&self, id: ResourceId) -> Option<(u32, u32)> {
+        match &self.resources.get_descriptor(id)?.resource_type {
+            ResourceType::TransientColor { descriptor, .. }
+            | ResourceType::TransientDepth { descriptor, .. } => {
+                Some((descriptor.width, descriptor.height))
+            }
+            _ => None,
+        }
+    }
+
+    fn resolve_size(
+        &self,
+        id: ResourceId,
+        resolved: &mut HashMap<ResourceId, Option<(u32, u32)>>,
+        stack: &mut Vec<ResourceId>,
+    ) -> Result<Option<(u32, u32)>> {
+        if let Some(size) = resolved.get(&id) {
+            return Ok(*size);
+        }
+
+        let name = self.resource_name(id);
+        let Some(fixed_size) = self.texture_size(id) else {
+            return Err(RenderGraphError::InvalidSizeMode {
+                resource: stack
+                    .last()
+                    .map(|&dependent| self.resource_name(dependent))
+                    .unwrap_or(name.clone()),
+                reason: format!("'{}' is not a transient texture", name),
+            });
+        };
+
+        let size = match self.resources.size_mode(id) {
+            SizeMode::Fixed => Some(fixed_size),
+            SizeMode::RelativeToOutput(scale) => {
+                self.output_size.map(|output| scale_size(output, scale))
+            }
+            SizeMode::RelativeToResource(reference, scale) => {
+                if stack.contains(&id) {
+                    return Err(RenderGraphError::InvalidSizeMode {
+                        resource: name,
+                        reason: "relative sizes form a cycle".to_string(),
+                    });
+                }
+
+                stack.push(id);
+                let base = self.resolve_size(reference, resolved, stack)?;
+                stack.pop();
+
+                base.map(|base| scale_size(base, scale))
+            }
+        };
+
+        resolved.insert(id, size);
+        Ok(size)
+    }
+}
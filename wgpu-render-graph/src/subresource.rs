@@ -0,0 +1,271 @@
+use crate::{RenderGraphTextureDescriptor, ResourceId, SlotBinding, VersionedResourceId};
+use std::ops::Range;
+use wgpu::TextureAspect;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubresourceRange {
+    pub base_mip_level: u32,
+    pub mip_level_count: Option<u32>,
+    pub base_array_layer: u32,
+    pub array_layer_count: Option<u32>,
+}
+
+impl SubresourceRange {
+    pub const ALL: Self = Self {
+        base_mip_level: 0,
+        mip_level_count: None,
+        base_array_layer: 0,
+        array_layer_count: None,
+    };
+
+    pub fn mip(level: u32) -> Self {
+        Self::ALL.with_mips(level..level + 1)
+    }
+
+    pub fn mips(levels: Range<u32>) -> Self {
+        Self::ALL.with_mips(levels)
+    }
+
+    pub fn layer(layer: u32) -> Self {
+        Self::ALL.with_layers(layer..layer + 1)
+    }
+
+    pub fn layers(layers: Range<u32>) -> Self {
+        Self::ALL.with_layers(layers)
+    }
+
+    pub fn with_mips(mut self, levels: Range<u32>) -> Self {
+        self.base_mip_level = levels.start;
+        self.mip_level_count = Some(levels.end.saturating_sub(levels.start));
+        self
+    }
+
+    pub fn with_layers(mut self, layers: Range<u32>) -> Self {
+        self.base_array_layer = layers.start;
+        self.array_layer_count = Some(layers.end.saturating_sub(layers.start));
+        self
+    }
+
+    pub fn is_all(&self) -> bool {
+        *self == Self::ALL
+    }
+
+    fn mip_end(&self) -> Option<u32> {
+        self.mip_level_count
+            .map(|count| self.base_mip_level + count)
+    }
+
+    fn layer_end(&self) -> Option<u32> {
+        self.array_layer_count
+            .map(|count| self.base_array_layer + count)
+    }
+
+    pub fn overlaps(&self, other: &SubresourceRange) -> bool {
+        let intersects = |start_a: u32, end_a: Option<u32>, start_b: u32, end_b: Option<u32>| {
+            end_a.is_none_or(|end_a| start_b < end_a) && end_b.is_none_or(|end_b| start_a < end_b)
+        };
+
+        intersects(
+            self.base_mip_level,
+            self.mip_end(),
+            other.base_mip_level,
+            other.mip_end(),
+        ) && intersects(
+            self.base_array_layer,
+            self.layer_end(),
+            other.base_array_layer,
+            other.layer_end(),
+        )
+    }
+
+    pub fn contains(&self, other: &SubresourceRange) -> bool {
+        let contains = |start_a: u32, end_a: Option<u32>, start_b: u32, end_b: Option<u32>| {
+            start_a <= start_b
+                && match (end_a, end_b) {
+                    (None, _) => true,
+                    (Some(_), None) => false,
+                    (Some(end_a), Some(end_b)) => end_b <= end_a,
+                }
+        };
+
+        contains(
+            self.base_mip_level,
+            self.mip_end(),
+            other.base_mip_level,
+            other.mip_end(),
+        ) && contains(
+            self.base_array_layer,
+            self.layer_end(),
+            other.base_array_layer,
+            other.layer_end(),
+        )
+    }
+
+    pub fn is_within(&self, descriptor: &RenderGraphTextureDescriptor) -> bool {
+        let layers = match descriptor.dimension {
+            wgpu::TextureDimension::D3 => 1,
+            _ => descriptor.depth_or_array_layers,
+        };
+
+        self.mip_level_count != Some(0)
+            && self.array_layer_count != Some(0)
+            && self.mip_end().unwrap_or(self.base_mip_level + 1) <= descriptor.mip_level_count
+            && self.layer_end().unwrap_or(self.base_array_layer + 1) <= layers
+    }
+
+    pub(crate) fn within(&self, placement: &SubresourceRange) -> SubresourceRange {
+        SubresourceRange {
+            base_mip_level: placement.base_mip_level + self.base_mip_level,
+            mip_level_count: self.mip_level_count.or(placement
+                .mip_level_count
+                .map(|count| count.saturating_sub(self.base_mip_level))),
+            base_array_layer: placement.base_array_layer + self.base_array_layer,
+            array_layer_count: self.array_layer_count.or(placement
+                .array_layer_count
+                .map(|count| count.saturating_sub(self.base_array_layer))),
+        }
+    }
+
+    pub(crate) fn view_dimension(
+        &self,
+        descriptor: &RenderGraphTextureDescriptor,
+    ) -> Option<wgpu::TextureViewDimension> {
+        if descriptor.dimension != wgpu::TextureDimension::D2 {
+            return None;
+        }
+
+        let layer_count = self
+            .array_layer_count
+            .unwrap_or(descriptor.depth_or_array_layers - self.base_array_layer);
+        if layer_count == 1 {
+            Some(wgpu::TextureViewDimension::D2)
+        } else {
+            Some(wgpu::TextureViewDimension::D2Array)
+        }
+    }
+}
+
+impl Default for SubresourceRange {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+pub(crate) fn format_subresource_span(base: u32, count: Option<u32>) -> String {
+    match count {
+        Some(1) => base.to_string(),
+        Some(count) => format!("{}..{}", base, base + count),
+        None => format!("{}..", base),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AccessRange {
+    pub subresource: SubresourceRange,
+    pub aspect: TextureAspect,
+}
+
+impl AccessRange {
+    pub const ALL: Self = Self {
+        subresource: SubresourceRange::ALL,
+        aspect: TextureAspect::All,
+    };
+
+    pub fn new(subresource: SubresourceRange, aspect: TextureAspect) -> Self {
+        Self {
+            subresource,
+            aspect,
+        }
+    }
+
+    pub fn overlaps(&self, other: &AccessRange) -> bool {
+        let aspects_overlap = self.aspect == TextureAspect::All
+            || other.aspect == TextureAspect::All
+            || self.aspect == other.aspect;
+
+        aspects_overlap && self.subresource.overlaps(&other.subresource)
+    }
+
+    pub fn contains(&self, other: &AccessRange) -> bool {
+        let aspect_contains = self.aspect == TextureAspect::All || self.aspect == other.aspect;
+
+        aspect_contains && self.subresource.contains(&other.subresource)
+    }
+
+    pub(crate) fn restrict(&self, aspect: TextureAspect) -> Option<Self> {
+        if aspect == TextureAspect::All || self.aspect == aspect {
+            Some(*self)
+        } else if self.aspect == TextureAspect::All {
+            Some(Self::new(self.subresource, aspect))
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for AccessRange {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+pub(crate) fn restrict_ranges(ranges: &[AccessRange], aspect: TextureAspect) -> Vec<AccessRange> {
+    ranges
+        .iter()
+        .filter_map(|range| range.restrict(aspect))
+        .collect()
+}
+
+pub(crate) fn ranges_overlap(a: &[AccessRange], b: &[AccessRange]) -> bool {
+    a.iter().any(|a| b.iter().any(|b| a.overlaps(b)))
+}
+
+pub(crate) fn ranges_cover(a: &[AccessRange], b: &[AccessRange]) -> bool {
+    b.iter().all(|b| a.iter().any(|a| a.contains(b)))
+}
+
+impl ResourceId {
+    pub fn subresource(self, range: SubresourceRange) -> SlotBinding {
+        SlotBinding::from(self).subresource(range)
+    }
+
+    pub fn mip(self, level: u32) -> SlotBinding {
+        self.subresource(SubresourceRange::mip(level))
+    }
+
+    pub fn layer(self, layer: u32) -> SlotBinding {
+        self.subresource(SubresourceRange::layer(layer))
+    }
+}
+
+impl VersionedResourceId {
+    pub fn subresource(self, range: SubresourceRange) -> SlotBinding {
+        SlotBinding::from(self).subresource(range)
+    }
+
+    pub fn mip(self, level: u32) -> SlotBinding {
+        self.subresource(SubresourceRange::mip(level))
+    }
+
+    pub fn layer(self, layer: u32) -> SlotBinding {
+        self.subresource(SubresourceRange::layer(layer))
+    }
+}
+
+impl SlotBinding {
+    pub fn subresource(mut self, range: SubresourceRange) -> Self {
+        self.subresource = Some(range);
+        self
+    }
+
+    pub fn mip(self, level: u32) -> Self {
+        let range = self.subresource.unwrap_or_default();
+        self.subresource(range.with_mips(level..level + 1))
+    }
+
+    pub fn layer(self, layer: u32) -> Self {
+        let range = self.subresource.unwrap_or_default();
+        self.subresource(range.with_layers(layer..layer + 1))
+    }
+}
@@ -0,0 +1,146 @@
+use crate::backend::RenderGraphBackend;
+use crate::{PassExecutionContext, PassPrepareContext, RenderGraph, RenderGraphError, Result};
+use std::num::NonZeroU64;
+use wgpu::{
+    BindingResource, Buffer, BufferBinding, BufferDescriptor, BufferUsages, COPY_BUFFER_ALIGNMENT,
+    Device, Queue,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadAllocation {
+    offset: u32,
+    size: NonZeroU64,
+    frame: u64,
+}
+
+impl UploadAllocation {
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size.get()
+    }
+}
+
+pub(crate) struct UploadRing {
+    buffer: Option<Buffer>,
+    data: Vec<u8>,
+    alignment: u64,
+    frame: u64,
+}
+
+impl UploadRing {
+    pub(crate) fn new() -> Self {
+        Self {
+            buffer: None,
+            data: Vec::new(),
+            alignment: COPY_BUFFER_ALIGNMENT,
+            frame: 0,
+        }
+    }
+
+    pub(crate) fn begin_frame(&mut self, device: &Device) {
+        self.data.clear();
+        self.frame += 1;
+        self.alignment =
+            (device.limits().min_uniform_buffer_offset_alignment as u64).max(COPY_BUFFER_ALIGNMENT);
+    }
+
+    fn push(&mut self, bytes: &[u8]) -> UploadAllocation {
+        let offset = (self.data.len() as u64).next_multiple_of(self.alignment);
+        let size = (bytes.len() as u64)
+            .next_multiple_of(COPY_BUFFER_ALIGNMENT)
+            .max(COPY_BUFFER_ALIGNMENT);
+
+        self.data.resize(offset as usize, 0);
+        self.data.extend_from_slice(bytes);
+        self.data.resize((offset + size) as usize, 0);
+
+        UploadAllocation {
+            offset: offset as u32,
+            size: NonZeroU64::MIN.saturating_add(size - 1),
+            frame: self.frame,
+        }
+    }
+
+    pub(crate) fn flush<B: RenderGraphBackend + ?Sized>(
+        &mut self,
+        backend: &B,
+        queue: &Queue,
+    ) -> bool {
+        if self.data.is_empty() {
+            return false;
+        }
+
+        let required = self.data.len() as u64;
+        let reallocated = self
+            .buffer
+            .as_ref()
+            .is_none_or(|buffer| buffer.size() < required);
+
+        if reallocated {
+            self.buffer = Some(backend.create_buffer(&BufferDescriptor {
+                label: Some("RenderGraph Uploads"),
+                size: required.next_power_of_two().max(self.alignment),
+                usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+        }
+
+        if let Some(buffer) = &self.buffer {
+            queue.write_buffer(buffer, 0, &self.data);
+        }
+
+        reallocated
+    }
+
+    pub(crate) fn buffer(&self) -> Option<&Buffer> {
+        self.buffer.as_ref()
+    }
+
+    fn allocation_buffer(&self, allocation: UploadAllocation) -> Option<&Buffer> {
+        self.buffer.as_ref().filter(|_| {
+            allocation.frame == self.frame
+                && allocation.offset as u64 + allocation.size() <= self.used_bytes()
+        })
+    }
+
+    pub(crate) fn used_bytes(&self) -> u64 {
+        self.data.len() as u64
+    }
+}
+
+impl<'a, C> PassPrepareContext<'a, C> {
+    pub fn upload<T: bytemuck::Pod>(&mut self, value: &T) -> UploadAllocation {
+        self.uploads.push(bytemuck::bytes_of(value))
+    }
+
+    pub fn upload_bytes(&mut self, bytes: &[u8]) -> UploadAllocation {
+        self.uploads.push(bytes)
+    }
+}
+
+impl<'r, 'e, C> PassExecutionContext<'r, 'e, C> {
+    pub fn upload_binding(&self, allocation: UploadAllocation) -> Result<BindingResource<'r>> {
+        let buffer =
+            self.uploads
+                .allocation_buffer(allocation)
+                .ok_or(RenderGraphError::StaleUpload {
+                    offset: allocation.offset,
+                    size: allocation.size(),
+                })?;
+
+        Ok(BindingResource::Buffer(BufferBinding {
+            buffer,
+            offset: 0,
+            size: Some(allocation.size),
+        }))
+    }
+}
+
+impl<C> RenderGraph<C> {
+    pub fn uploaded_bytes(&self) -> u64 {
+        self.uploads.used_bytes()
+    }
+}
@@ -0,0 +1,537 @@
+use crate::{
+    RenderGraph, RenderGraphTextureDescriptor, ResourceHandle, ResourceId, ResourceType, SlotUsage,
+};
+use petgraph::stable_graph::{NodeIndex, StableDiGraph};
+use std::collections::{HashMap, HashSet, VecDeque};
+use wgpu::{BufferUsages, TextureFormat, TextureUsages};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ValidationIssue {
+    #[error("Pass '{pass}' reads '{resource}' through slot '{slot}', but no pass ever writes it")]
+    ReadNeverWritten {
+        pass: String,
+        slot: String,
+        resource: String,
+    },
+
+    #[error("Transient resource '{resource}' is written but never read")]
+    TransientNeverRead { resource: String },
+
+    #[error(
+        "External resource '{resource}' is used but was never bound with set_external_texture or set_external_buffer"
+    )]
+    ExternalNotBound { resource: String },
+
+    #[error(
+        "Pass '{pass}' uses '{resource}' through slot '{slot}' as {access}, which requires {required:?} but the resource only has {actual:?}"
+    )]
+    TextureUsageMismatch {
+        pass: String,
+        slot: String,
+        resource: String,
+        access: &'static str,
+        required: TextureUsages,
+        actual: TextureUsages,
+    },
+
+    #[error(
+        "Pass '{pass}' uses '{resource}' through slot '{slot}' as {access}, which requires {required:?} but the resource only has {actual:?}"
+    )]
+    BufferUsageMismatch {
+        pass: String,
+        slot: String,
+        resource: String,
+        access: &'static str,
+        required: BufferUsages,
+        actual: BufferUsages,
+    },
+
+    #[error("Resource '{resource}' is declared as a {expected} texture but has format {format:?}")]
+    FormatMismatch {
+        resource: String,
+        expected: &'static str,
+        format: TextureFormat,
+    },
+
+    #[error(
+        "Resource '{resource}' lists view format {view_format:?}, which is not an sRGB or linear variant of {format:?}"
+    )]
+    IncompatibleViewFormat {
+        resource: String,
+        format: TextureFormat,
+        view_format: TextureFormat,
+    },
+
+    #[error(
+        "Resource '{resource}' sets a stencil clear value, but format {format:?} has no stencil aspect"
+    )]
+    StencilClearWithoutStencil {
+        resource: String,
+        format: TextureFormat,
+    },
+
+    #[error(
+        "External resource '{resource}' is declared as {declared:?}, but the bound texture is {bound:?}"
+    )]
+    ExternalFormatMismatch {
+        resource: String,
+        declared: TextureFormat,
+        bound: TextureFormat,
+    },
+
+    #[error("Resource '{resource}' cannot resolve into '{target}': {reason}")]
+    InvalidResolveTarget {
+        resource: String,
+        target: String,
+        reason: String,
+    },
+
+    #[error("Render graph contains a cycle: {}", passes.join(" -> "))]
+    Cycle { passes: Vec<String> },
+}
+
+impl ValidationIssue {
+    pub fn severity(&self) -> Severity {
+        match self {
+            ValidationIssue::TransientNeverRead { .. }
+            | ValidationIssue::StencilClearWithoutStencil { .. } => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity() == Severity::Error
+    }
+}
+
+struct TextureInfo {
+    format: TextureFormat,
+    size: Option<(u32, u32)>,
+    sample_count: u32,
+    usage: TextureUsages,
+}
+
+enum ResourceUsage {
+    Texture(TextureUsages),
+    Buffer(BufferUsages),
+}
+
+impl<C> RenderGraph<C> {
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        self.validate_resource_flow(&mut issues);
+        self.validate_formats(&mut issues);
+        self.validate_usages(&mut issues);
+        self.validate_resolve_targets(&mut issues);
+        self.validate_acyclic(&mut issues);
+
+        issues
+    }
+
+    fn slot_name(&self, pass: NodeIndex, resource_id: ResourceId) -> String {
+        self.pass_resource_mappings
+            .get(&pass)
+            .and_then(|mappings| {
+                let mut slots = mappings
+                    .iter()
+                    .filter(|(_, id)| **id == resource_id)
+                    .map(|(slot, _)| slot.as_str())
+                    .collect::<Vec<_>>();
+                slots.sort_unstable();
+                slots.first().map(|slot| slot.to_string())
+            })
+            .unwrap_or_default()
+    }
+
+    fn validate_resource_flow(&self, issues: &mut Vec<ValidationIssue>) {
+        let mut used: HashSet<ResourceId> = HashSet::new();
+        let mut read: HashSet<ResourceId> = HashSet::new();
+
+        for node_index in self.graph.node_indices() {
+            let node = &self.graph[node_index];
+            used.extend(
+                node.reads
+                    .iter()
+                    .chain(&node.writes)
+                    .chain(&node.reads_writes)
+                    .chain(&node.resolves),
+            );
+            read.extend(node.reads.iter().chain(&node.reads_writes));
+
+            for &resource_id in &node.reads {
+                let is_transient = self
+                    .resources
+                    .get_descriptor(resource_id)
+                    .is_some_and(|descriptor| !descriptor.is_external);
+
+                if is_transient
+                    && !self.resources.is_previous(resource_id)
+                    && self.latest_version(resource_id).version == 0
+                {
+                    issues.push(ValidationIssue::ReadNeverWritten {
+                        pass: node.name.clone(),
+                        slot: self.slot_name(node_index, resource_id),
+                        resource: self.resource_name(resource_id),
+                    });
+                }
+            }
+        }
+
+        let mut resource_ids: Vec<ResourceId> = used.into_iter().collect();
+        resource_ids.sort_by_key(|id| id.0);
+
+        for resource_id in resource_ids {
+            let Some(descriptor) = self.resources.get_descriptor(resource_id) else {
+                continue;
+            };
+
+            if descriptor.is_external {
+                if self.resources.get_handle(resource_id).is_none() {
+                    issues.push(ValidationIssue::ExternalNotBound {
+                        resource: descriptor.name.clone(),
+                    });
+                }
+            } else if !read.contains(&resource_id)
+                && self.resources.resolve_target(resource_id).is_none()
+                && !self.resources.is_history(resource_id)
+            {
+                issues.push(ValidationIssue::TransientNeverRead {
+                    resource: descriptor.name.clone(),
+                });
+            }
+        }
+    }
+
+    fn validate_formats(&self, issues: &mut Vec<ValidationIssue>) {
+        let mut descriptors: Vec<_> = self.resources.descriptors.iter().collect();
+        descriptors.sort_by_key(|(id, _)| id.0);
+
+        for (&id, descriptor) in descriptors {
+            match &descriptor.resource_type {
+                ResourceType::TransientColor {
+                    descriptor: texture_descriptor,
+                    ..
+                } if texture_descriptor.format.is_depth_stencil_format() => {
+                    issues.push(ValidationIssue::FormatMismatch {
+                        resource: descriptor.name.clone(),
+                        expected: "color",
+                        format: texture_descriptor.format,
+                    });
+                }
+                ResourceType::TransientDepth {
+                    descriptor: RenderGraphTextureDescriptor { format, .. },
+                    ..
+                }
+                | ResourceType::ExternalDepth { format, .. }
+                    if !format.is_depth_stencil_format() =>
+                {
+                    issues.push(ValidationIssue::FormatMismatch {
+                        resource: descriptor.name.clone(),
+                        expected: "depth",
+                        format: *format,
+                    });
+                }
+                ResourceType::TransientDepth {
+                    descriptor: RenderGraphTextureDescriptor { format, .. },
+                    clear_stencil: Some(_),
+                    ..
+                }
+                | ResourceType::ExternalDepth {
+                    format,
+                    clear_stencil: Some(_),
+                    ..
+                } if !format.has_stencil_aspect() => {
+                    issues.push(ValidationIssue::StencilClearWithoutStencil {
+                        resource: descriptor.name.clone(),
+                        format: *format,
+                    });
+                }
+                _ => {}
+            }
+
+            if let ResourceType::ExternalDepth { format, .. } = &descriptor.resource_type
+                && let Some(texture) = self.texture_info(id)
+                && texture.format != *format
+            {
+                issues.push(ValidationIssue::ExternalFormatMismatch {
+                    resource: descriptor.name.clone(),
+                    declared: *format,
+                    bound: texture.format,
+                });
+            }
+
+            if let ResourceType::TransientColor {
+                descriptor: texture_descriptor,
+                ..
+            }
+            | ResourceType::TransientDepth {
+                descriptor: texture_descriptor,
+                ..
+            } = &descriptor.resource_type
+            {
+                for &view_format in &texture_descriptor.view_formats {
+                    if view_format.remove_srgb_suffix()
+                        != texture_descriptor.format.remove_srgb_suffix()
+                    {
+                        issues.push(ValidationIssue::IncompatibleViewFormat {
+                            resource: descriptor.name.clone(),
+                            format: texture_descriptor.format,
+                            view_format,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_usages(&self, issues: &mut Vec<ValidationIssue>) {
+        for node_index in self.graph.node_indices() {
+            let node = &self.graph[node_index];
+            let Some(mappings) = self.pass_resource_mappings.get(&node_index) else {
+                continue;
+            };
+
+            let reads = node.pass.reads();
+            let mut slots: Vec<(&String, &ResourceId)> = mappings.iter().collect();
+            slots.sort_unstable_by_key(|(slot, _)| *slot);
+
+            for (slot, &resource_id) in slots {
+                let Some(descriptor) = self.resources.get_descriptor(resource_id) else {
+                    continue;
+                };
+
+                match self.resource_usage(resource_id, &descriptor.resource_type) {
+                    Some(ResourceUsage::Texture(actual)) => {
+                        let usage = node.pass.slot_usage(slot).unwrap_or(
+                            if reads.contains(&slot.as_str()) {
+                                SlotUsage::Sampled
+                            } else {
+                                SlotUsage::Attachment
+                            },
+                        );
+                        let required = usage.texture_usage();
+                        if required.is_empty() || !actual.contains(required) {
+                            issues.push(ValidationIssue::TextureUsageMismatch {
+                                pass: node.name.clone(),
+                                slot: slot.clone(),
+                                resource: descriptor.name.clone(),
+                                access: usage.describe(),
+                                required,
+                                actual,
+                            });
+                        }
+                    }
+                    Some(ResourceUsage::Buffer(actual)) => {
+                        let usage = node.pass.slot_usage(slot).unwrap_or(SlotUsage::Storage);
+                        let required = usage.buffer_usage();
+                        if required.is_empty() || !actual.contains(required) {
+                            issues.push(ValidationIssue::BufferUsageMismatch {
+                                pass: node.name.clone(),
+                                slot: slot.clone(),
+                                resource: descriptor.name.clone(),
+                                access: usage.describe(),
+                                required,
+                                actual,
+                            });
+                        }
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    fn resource_usage(
+        &self,
+        resource_id: ResourceId,
+        resource_type: &ResourceType,
+    ) -> Option<ResourceUsage> {
+        match resource_type {
+            ResourceType::TransientColor { descriptor, .. }
+            | ResourceType::TransientDepth { descriptor, .. } => {
+                Some(ResourceUsage::Texture(descriptor.usage))
+            }
+            ResourceType::TransientBuffer { descriptor } => {
+                Some(ResourceUsage::Buffer(descriptor.usage))
+            }
+            ResourceType::ExternalColor { .. } | ResourceType::ExternalDepth { .. } => {
+                match self.resources.get_handle(resource_id)? {
+                    ResourceHandle::ExternalTexture {
+                        texture: Some(texture),
+                        ..
+                    } => Some(ResourceUsage::Texture(texture.usage())),
+                    _ => None,
+                }
+            }
+            ResourceType::ExternalBuffer => match self.resources.get_handle(resource_id)? {
+                ResourceHandle::ExternalBuffer { buffer } => {
+                    Some(ResourceUsage::Buffer(buffer.usage()))
+                }
+                _ => None,
+            },
+        }
+    }
+
+    fn texture_info(&self, resource_id: ResourceId) -> Option<TextureInfo> {
+        match &self.resources.get_descriptor(resource_id)?.resource_type {
+            ResourceType::TransientColor { descriptor, .. }
+            | ResourceType::TransientDepth { descriptor, .. } => Some(TextureInfo {
+                format: descriptor.format,
+                size: self.resolved_size(resource_id),
+                sample_count: descriptor.sample_count,
+                usage: descriptor.usage,
+            }),
+            ResourceType::ExternalColor { .. } | ResourceType::ExternalDepth { .. } => {
+                match self.resources.get_handle(resource_id)? {
+                    ResourceHandle::ExternalTexture {
+                        texture: Some(texture),
+                        ..
+                    } => Some(TextureInfo {
+                        format: texture.format(),
+                        size: Some((texture.width(), texture.height())),
+                        sample_count: texture.sample_count(),
+                        usage: texture.usage(),
+                    }),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn validate_resolve_targets(&self, issues: &mut Vec<ValidationIssue>) {
+        let mut resolve_targets: Vec<(ResourceId, ResourceId)> = self
+            .resources
+            .descriptors
+            .keys()
+            .filter_map(|&id| self.resources.resolve_target(id).map(|target| (id, target)))
+            .collect();
+        resolve_targets.sort_by_key(|(id, _)| id.0);
+
+        for (resource_id, target_id) in resolve_targets {
+            let is_color = |id: ResourceId| {
+                matches!(
+                    self.resources
+                        .get_descriptor(id)
+                        .map(|descriptor| &descriptor.resource_type),
+                    Some(ResourceType::TransientColor { .. } | ResourceType::ExternalColor { .. })
+                )
+            };
+
+            let mut reasons = Vec::new();
+            if !is_color(resource_id) || !is_color(target_id) {
+                reasons.push("only color textures can be resolved".to_string());
+            }
+
+            let source = self.texture_info(resource_id);
+            let target = self.texture_info(target_id);
+
+            if let Some(source) = &source
+                && source.sample_count <= 1
+            {
+                reasons.push("the resource is not multisampled".to_string());
+            }
+
+            if let Some(target) = &target {
+                if target.sample_count != 1 {
+                    reasons.push(format!(
+                        "the target has {} samples instead of 1",
+                        target.sample_count
+                    ));
+                }
+                if !target.usage.contains(TextureUsages::RENDER_ATTACHMENT) {
+                    reasons.push("the target lacks RENDER_ATTACHMENT usage".to_string());
+                }
+            }
+
+            if let (Some(source), Some(target)) = (&source, &target) {
+                if source.format != target.format {
+                    reasons.push(format!(
+                        "formats {:?} and {:?} differ",
+                        source.format, target.format
+                    ));
+                }
+                if let (Some((source_width, source_height)), Some((target_width, target_height))) =
+                    (source.size, target.size)
+                    && (source_width, source_height) != (target_width, target_height)
+                {
+                    reasons.push(format!(
+                        "sizes {}x{} and {}x{} differ",
+                        source_width, source_height, target_width, target_height
+                    ));
+                }
+            }
+
+            if !reasons.is_empty() {
+                issues.push(ValidationIssue::InvalidResolveTarget {
+                    resource: self.resource_name(resource_id),
+                    target: self.resource_name(target_id),
+                    reason: reasons.join(", "),
+                });
+            }
+        }
+    }
+
+    fn validate_acyclic(&self, issues: &mut Vec<ValidationIssue>) {
+        let mut dependencies: StableDiGraph<String, ()> =
+            self.graph.map(|_, node| node.name.clone(), |_, _| ());
+        dependencies.clear_edges();
+        for (from, to, _) in self.dependency_edges() {
+            dependencies.add_edge(from, to, ());
+        }
+
+        for component in petgraph::algo::tarjan_scc(&dependencies) {
+            if component.len() < 2 {
+                continue;
+            }
+
+            let members: HashSet<NodeIndex> = component.iter().copied().collect();
+            let start = *component.iter().min().unwrap();
+            let cycle = find_cycle(&dependencies, start, &members);
+
+            issues.push(ValidationIssue::Cycle {
+                passes: cycle
+                    .into_iter()
+                    .map(|node_index| dependencies[node_index].clone())
+                    .collect(),
+            });
+        }
+    }
+}
+
+fn find_cycle(
+    graph: &StableDiGraph<String, ()>,
+    start: NodeIndex,
+    members: &HashSet<NodeIndex>,
+) -> Vec<NodeIndex> {
+    let mut parents: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(current) = queue.pop_front() {
+        for next in graph.neighbors(current) {
+            if next == start {
+                let mut path = vec![current];
+                while let Some(&parent) = parents.get(path.last().unwrap()) {
+                    path.push(parent);
+                }
+                path.reverse();
+                path.push(start);
+                return path;
+            }
+
+            if members.contains(&next) && next != start && !parents.contains_key(&next) {
+                parents.insert(next, current);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    vec![start]
+}
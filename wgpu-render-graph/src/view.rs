@@ -0,0 +1,222 @@
+use crate::subresource::format_subresource_span;
+use crate::{
+    RenderGraphTextureDescriptor, ResourceId, SlotBinding, SubresourceRange, VersionedResourceId,
+};
+use wgpu::{TextureAspect, TextureDimension, TextureFormat, TextureViewDimension};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TextureViewSpec {
+    pub format: Option<TextureFormat>,
+    pub dimension: Option<TextureViewDimension>,
+    pub aspect: TextureAspect,
+}
+
+impl TextureViewSpec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn depth_only() -> Self {
+        Self::new().aspect(TextureAspect::DepthOnly)
+    }
+
+    pub fn stencil_only() -> Self {
+        Self::new().aspect(TextureAspect::StencilOnly)
+    }
+
+    pub fn format(mut self, format: TextureFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    pub fn dimension(mut self, dimension: TextureViewDimension) -> Self {
+        self.dimension = Some(dimension);
+        self
+    }
+
+    pub fn aspect(mut self, aspect: TextureAspect) -> Self {
+        self.aspect = aspect;
+        self
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub(crate) fn check(
+        &self,
+        descriptor: &RenderGraphTextureDescriptor,
+        range: &SubresourceRange,
+    ) -> std::result::Result<(), String> {
+        let Some(aspect_format) = descriptor.format.aspect_specific_format(self.aspect) else {
+            return Err(format!(
+                "aspect {:?} is not available on format {:?}",
+                self.aspect, descriptor.format
+            ));
+        };
+
+        if let Some(format) = self.format
+            && format != descriptor.format
+            && format != aspect_format
+            && !descriptor.view_formats.contains(&format)
+        {
+            return Err(format!(
+                "format {:?} is neither {:?} nor one of its view formats {:?}",
+                format, descriptor.format, descriptor.view_formats
+            ));
+        }
+
+        if let Some(dimension) = self.dimension {
+            let layers = match descriptor.dimension {
+                TextureDimension::D3 => 1,
+                _ => descriptor.depth_or_array_layers,
+            };
+            let layer_count = range
+                .array_layer_count
+                .unwrap_or(layers.saturating_sub(range.base_array_layer));
+
+            let compatible = match dimension {
+                TextureViewDimension::D1 => descriptor.dimension == TextureDimension::D1,
+                TextureViewDimension::D2 => {
+                    descriptor.dimension == TextureDimension::D2 && layer_count == 1
+                }
+                TextureViewDimension::D2Array => descriptor.dimension == TextureDimension::D2,
+                TextureViewDimension::Cube => {
+                    descriptor.dimension == TextureDimension::D2 && layer_count == 6
+                }
+                TextureViewDimension::CubeArray => {
+                    descriptor.dimension == TextureDimension::D2
+                        && layer_count > 0
+                        && layer_count.is_multiple_of(6)
+                }
+                TextureViewDimension::D3 => descriptor.dimension == TextureDimension::D3,
+            };
+
+            if !compatible {
+                return Err(format!(
+                    "a {:?} view cannot cover {} layers of a {:?} texture",
+                    dimension, layer_count, descriptor.dimension
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) struct SlotView {
+    pub(crate) subresource: SubresourceRange,
+    pub(crate) spec: TextureViewSpec,
+}
+
+impl SlotView {
+    pub(crate) fn is_default(&self) -> bool {
+        self.subresource.is_all() && self.spec.is_default()
+    }
+
+    pub(crate) fn attachment(&self) -> SlotView {
+        if self.spec.aspect == TextureAspect::All {
+            return *self;
+        }
+
+        SlotView {
+            subresource: self.subresource,
+            spec: TextureViewSpec {
+                format: None,
+                aspect: TextureAspect::All,
+                ..self.spec
+            },
+        }
+    }
+
+    pub(crate) fn label(&self, name: &str) -> String {
+        let mut details = Vec::new();
+        if !self.subresource.is_all() {
+            details.push(format!(
+                "mips {}, layers {}",
+                format_subresource_span(
+                    self.subresource.base_mip_level,
+                    self.subresource.mip_level_count
+                ),
+                format_subresource_span(
+                    self.subresource.base_array_layer,
+                    self.subresource.array_layer_count
+                ),
+            ));
+        }
+        if let Some(format) = self.spec.format {
+            details.push(format!("{:?}", format));
+        }
+        if let Some(dimension) = self.spec.dimension {
+            details.push(format!("{:?}", dimension));
+        }
+        if self.spec.aspect != TextureAspect::All {
+            details.push(format!("{:?}", self.spec.aspect));
+        }
+
+        if details.is_empty() {
+            name.to_string()
+        } else {
+            format!("{} ({})", name, details.join(", "))
+        }
+    }
+}
+
+impl ResourceId {
+    pub fn view(self, spec: TextureViewSpec) -> SlotBinding {
+        SlotBinding::from(self).view(spec)
+    }
+
+    pub fn view_format(self, format: TextureFormat) -> SlotBinding {
+        SlotBinding::from(self).view_format(format)
+    }
+
+    pub fn view_dimension(self, dimension: TextureViewDimension) -> SlotBinding {
+        SlotBinding::from(self).view_dimension(dimension)
+    }
+
+    pub fn aspect(self, aspect: TextureAspect) -> SlotBinding {
+        SlotBinding::from(self).aspect(aspect)
+    }
+}
+
+impl VersionedResourceId {
+    pub fn view(self, spec: TextureViewSpec) -> SlotBinding {
+        SlotBinding::from(self).view(spec)
+    }
+
+    pub fn view_format(self, format: TextureFormat) -> SlotBinding {
+        SlotBinding::from(self).view_format(format)
+    }
+
+    pub fn view_dimension(self, dimension: TextureViewDimension) -> SlotBinding {
+        SlotBinding::from(self).view_dimension(dimension)
+    }
+
+    pub fn aspect(self, aspect: TextureAspect) -> SlotBinding {
+        SlotBinding::from(self).aspect(aspect)
+    }
+}
+
+impl SlotBinding {
+    pub fn view(mut self, spec: TextureViewSpec) -> Self {
+        self.view = Some(spec);
+        self
+    }
+
+    pub fn view_format(self, format: TextureFormat) -> Self {
+        let spec = self.view.unwrap_or_default();
+        self.view(spec.format(format))
+    }
+
+    pub fn view_dimension(self, dimension: TextureViewDimension) -> Self {
+        let spec = self.view.unwrap_or_default();
+        self.view(spec.dimension(dimension))
+    }
+
+    pub fn aspect(self, aspect: TextureAspect) -> Self {
+        let spec = self.view.unwrap_or_default();
+        self.view(spec.aspect(aspect))
+    }
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use common::{blit, fill, noop_device};
+use wgpu::StoreOp;
+use wgpu_render_graph::{BackendEvent, RecordingBackend, RenderGraph, ResourceId};
+
+fn transient(graph: &mut RenderGraph, name: &str) -> ResourceId {
+    graph.add_color_texture(name).size(64, 64).transient()
+}
+
+#[test]
+fn executes_passes_in_dependency_order() {
+    let (device, queue) = noop_device();
+    let mut graph = RenderGraph::new();
+    let hdr = transient(&mut graph, "hdr");
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(blit("tonemap"), &[("input", hdr), ("output", surface)])
+        .unwrap();
+    graph.add_pass(fill("scene"), &[("output", hdr)]).unwrap();
+
+    let backend = RecordingBackend::new(&device);
+    graph.execute(&backend, &queue, &()).unwrap();
+
+    assert_eq!(backend.executed_passes(), ["scene", "tonemap"]);
+    assert_eq!(backend.created_textures(), ["pool_0 (hdr)"]);
+}
+
+#[test]
+fn culled_passes_are_neither_prepared_nor_executed() {
+    let (device, queue) = noop_device();
+    let mut graph = RenderGraph::new();
+    let unused = transient(&mut graph, "unused");
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(fill("orphan"), &[("output", unused)])
+        .unwrap();
+    graph
+        .add_pass(fill("present"), &[("output", surface)])
+        .unwrap();
+
+    let backend = RecordingBackend::new(&device);
+    graph.execute(&backend, &queue, &()).unwrap();
+
+    assert_eq!(backend.executed_passes(), ["present"]);
+    assert!(!backend.events().iter().any(|event| matches!(
+        event,
+        BackendEvent::PassPrepared { pass } if pass == "orphan"
+    )));
+    assert!(backend.created_textures().is_empty());
+}
+
+#[test]
+fn transients_with_disjoint_lifetimes_share_a_texture() {
+    let (device, queue) = noop_device();
+    let mut graph = RenderGraph::new();
+    let first = transient(&mut graph, "first");
+    let second = transient(&mut graph, "second");
+    let third = transient(&mut graph, "third");
+    let surface = graph.add_color_texture("surface").external();
+
+    graph.add_pass(fill("a"), &[("output", first)]).unwrap();
+    graph
+        .add_pass(blit("b"), &[("input", first), ("output", second)])
+        .unwrap();
+    graph
+        .add_pass(blit("c"), &[("input", second), ("output", third)])
+        .unwrap();
+    graph
+        .add_pass(blit("d"), &[("input", third), ("output", surface)])
+        .unwrap();
+
+    let backend = RecordingBackend::new(&device);
+    graph.execute(&backend, &queue, &()).unwrap();
+
+    assert_eq!(backend.executed_passes(), ["a", "b", "c", "d"]);
+    assert_eq!(
+        backend.created_textures(),
+        ["pool_0 (first, third)", "pool_1 (second)"]
+    );
+
+    backend.take_events();
+    graph.execute(&backend, &queue, &()).unwrap();
+    assert!(backend.created_textures().is_empty());
+}
+
+#[test]
+fn store_ops_keep_only_observed_writes() {
+    let mut graph: RenderGraph = RenderGraph::new();
+    let scratch = transient(&mut graph, "scratch");
+    let surface = graph.add_color_texture("surface").external();
+    let preview = graph.add_color_texture("preview").no_store().external();
+
+    graph
+        .add_pass(fill("fill"), &[("output", scratch)])
+        .unwrap();
+    graph
+        .add_pass(blit("present"), &[("input", scratch), ("output", surface)])
+        .unwrap();
+    graph
+        .add_pass(blit("preview"), &[("input", scratch), ("output", preview)])
+        .unwrap();
+    graph.compile().unwrap();
+
+    let report = graph.report().unwrap();
+    assert_eq!(report.pass_store_op("fill", scratch), Some(StoreOp::Store));
+    assert_eq!(
+        report.pass_store_op("present", surface),
+        Some(StoreOp::Store)
+    );
+    assert_eq!(
+        report.pass_store_op("preview", preview),
+        Some(StoreOp::Discard)
+    );
+}
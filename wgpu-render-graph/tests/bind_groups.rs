@@ -0,0 +1,158 @@
+mod common;
+
+use common::noop_device;
+use std::sync::{Arc, Mutex};
+use wgpu::{BindGroupLayout, Sampler, TextureFormat, TextureUsages};
+use wgpu_render_graph::{
+    BindGroupSource, PassExecutionContext, PassNode, RecordingBackend, RenderGraph, ResourceId,
+    Result, SubGraphRunCommand,
+};
+
+struct SamplingPass {
+    layout: BindGroupLayout,
+    sampler: Arc<Mutex<Sampler>>,
+}
+
+impl PassNode for SamplingPass {
+    fn name(&self) -> &str {
+        "sample"
+    }
+
+    fn reads(&self) -> Vec<&str> {
+        vec!["input"]
+    }
+
+    fn writes(&self) -> Vec<&str> {
+        vec!["output"]
+    }
+
+    fn execute<'r, 'e>(
+        &mut self,
+        mut context: PassExecutionContext<'r, 'e>,
+    ) -> Result<Vec<SubGraphRunCommand<'r>>> {
+        let sampler = self.sampler.lock().unwrap().clone();
+        context.bind_group(
+            "sample",
+            &self.layout,
+            &[
+                BindGroupSource::Slot("input"),
+                BindGroupSource::Sampler(&sampler),
+            ],
+        )?;
+        Ok(context.into_sub_graph_commands())
+    }
+}
+
+fn sampling_layout(device: &wgpu::Device) -> BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("sample"),
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+        ],
+    })
+}
+
+fn bind_source(device: &wgpu::Device, graph: &mut RenderGraph, source: ResourceId) {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("source"),
+        size: wgpu::Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: TextureFormat::Rgba8Unorm,
+        usage: TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    graph
+        .resources_mut()
+        .set_external_texture_from(source, &texture, view);
+}
+
+fn sampling_graph(device: &wgpu::Device) -> (RenderGraph, ResourceId, Arc<Mutex<Sampler>>) {
+    let sampler = Arc::new(Mutex::new(device.create_sampler(&Default::default())));
+    let mut graph = RenderGraph::new();
+    let source = graph
+        .add_color_texture("source")
+        .format(TextureFormat::Rgba8Unorm)
+        .external();
+    let surface = graph.add_color_texture("surface").external();
+    graph
+        .add_pass(
+            Box::new(SamplingPass {
+                layout: sampling_layout(device),
+                sampler: Arc::clone(&sampler),
+            }),
+            &[("input", source), ("output", surface)],
+        )
+        .unwrap();
+    bind_source(device, &mut graph, source);
+    (graph, source, sampler)
+}
+
+#[test]
+fn bind_groups_are_reused_while_versions_are_unchanged() {
+    let (device, queue) = noop_device();
+    let (mut graph, _, _) = sampling_graph(&device);
+    let backend = RecordingBackend::new(&device);
+
+    for _ in 0..3 {
+        graph.execute(&backend, &queue, &()).unwrap();
+    }
+
+    assert_eq!(graph.bind_group_cache().created(), 1);
+    assert_eq!(graph.bind_group_cache().len(), 1);
+}
+
+#[test]
+fn rebinding_a_resource_rebuilds_and_evicts_its_bind_group() {
+    let (device, queue) = noop_device();
+    let (mut graph, source, _) = sampling_graph(&device);
+    let backend = RecordingBackend::new(&device);
+
+    graph.execute(&backend, &queue, &()).unwrap();
+    bind_source(&device, &mut graph, source);
+    graph.execute(&backend, &queue, &()).unwrap();
+
+    assert_eq!(graph.bind_group_cache().created(), 2);
+    assert_eq!(graph.bind_group_cache().len(), 1);
+}
+
+#[test]
+fn new_sampler_identities_miss_and_idle_entries_are_evicted() {
+    let (device, queue) = noop_device();
+    let (mut graph, _, sampler) = sampling_graph(&device);
+    let backend = RecordingBackend::new(&device);
+
+    graph.execute(&backend, &queue, &()).unwrap();
+    *sampler.lock().unwrap() = device.create_sampler(&Default::default());
+    graph.execute(&backend, &queue, &()).unwrap();
+
+    assert_eq!(graph.bind_group_cache().created(), 2);
+    assert_eq!(graph.bind_group_cache().len(), 2);
+
+    graph.execute(&backend, &queue, &()).unwrap();
+    assert_eq!(graph.bind_group_cache().len(), 2);
+    graph.execute(&backend, &queue, &()).unwrap();
+    assert_eq!(graph.bind_group_cache().len(), 1);
+    assert_eq!(graph.bind_group_cache().created(), 2);
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use common::{TestPass, fill, noop_device};
+use serde_json::Value;
+use wgpu_render_graph::{PassStatus, RecordingBackend, RenderGraph};
+
+#[test]
+fn chrome_trace_is_valid_json_with_escaped_names() {
+    let (device, queue) = noop_device();
+    let mut graph = RenderGraph::new();
+    let unused = graph.add_color_texture("unused").size(16, 16).transient();
+    let surface = graph.add_color_texture("surface \"final\"").external();
+
+    graph
+        .add_pass(fill("orphan"), &[("output", unused)])
+        .unwrap();
+    graph
+        .add_pass(
+            TestPass::new("present \"main\"\n")
+                .writing(&["output"])
+                .boxed(),
+            &[("output", surface)],
+        )
+        .unwrap();
+
+    graph.capture_next_frame();
+    let backend = RecordingBackend::new(&device);
+    graph.execute(&backend, &queue, &()).unwrap();
+
+    let capture = graph.take_frame_capture().unwrap();
+    assert_eq!(capture.pass("orphan").unwrap().status, PassStatus::Culled);
+
+    let trace: Value = serde_json::from_str(&capture.to_chrome_trace().unwrap()).unwrap();
+    let events = trace["traceEvents"].as_array().unwrap();
+
+    let record = events
+        .iter()
+        .find(|event| event["name"] == "present \"main\"\n" && event["cat"] == "record")
+        .unwrap();
+    assert_eq!(record["ph"], "X");
+    assert_eq!(record["args"]["status"], "executed");
+    assert_eq!(record["args"]["slots"]["output"], "surface \"final\"");
+
+    let culled = events
+        .iter()
+        .find(|event| event["name"] == "orphan")
+        .unwrap();
+    assert_eq!(culled["ph"], "i");
+    assert_eq!(culled["cat"], "culled");
+}
+
+#[test]
+fn skipped_passes_are_placed_in_execution_order() {
+    let (device, queue) = noop_device();
+    let mut graph = RenderGraph::new();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(fill("background"), &[("output", surface)])
+        .unwrap();
+    graph
+        .add_pass(
+            TestPass::new("debug")
+                .writing(&["output"])
+                .disabled()
+                .boxed(),
+            &[("output", surface)],
+        )
+        .unwrap();
+    graph
+        .add_pass(fill("present"), &[("output", surface)])
+        .unwrap();
+
+    graph.capture_next_frame();
+    let backend = RecordingBackend::new(&device);
+    graph.execute(&backend, &queue, &()).unwrap();
+
+    let capture = graph.take_frame_capture().unwrap();
+    let trace: Value = serde_json::from_str(&capture.to_chrome_trace().unwrap()).unwrap();
+    let events = trace["traceEvents"].as_array().unwrap();
+    let event = |name: &str, category: &str| {
+        events
+            .iter()
+            .find(|event| event["name"] == name && event["cat"] == category)
+            .unwrap()
+    };
+
+    let background = event("background", "record");
+    let disabled = event("debug", "disabled");
+    let present = event("present", "record");
+    let end = |event: &Value| event["ts"].as_f64().unwrap() + event["dur"].as_f64().unwrap();
+
+    assert_eq!(disabled["ph"], "i");
+    assert_eq!(disabled["ts"], present["ts"]);
+    assert!(disabled["ts"].as_f64().unwrap() >= end(background));
+}
@@ -0,0 +1,105 @@
+#![allow(dead_code)]
+
+use wgpu_render_graph::{PassExecutionContext, PassNode, Result, SlotUsage, SubGraphRunCommand};
+
+pub fn noop_device() -> (wgpu::Device, wgpu::Queue) {
+    wgpu::Device::noop(&wgpu::DeviceDescriptor::default())
+}
+
+pub struct TestPass {
+    name: &'static str,
+    reads: Vec<&'static str>,
+    writes: Vec<&'static str>,
+    reads_writes: Vec<&'static str>,
+    usages: Vec<(&'static str, SlotUsage)>,
+    enabled: bool,
+}
+
+impl TestPass {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            reads: Vec::new(),
+            writes: Vec::new(),
+            reads_writes: Vec::new(),
+            usages: Vec::new(),
+            enabled: true,
+        }
+    }
+
+    pub fn reading(mut self, slots: &[&'static str]) -> Self {
+        self.reads.extend(slots);
+        self
+    }
+
+    pub fn writing(mut self, slots: &[&'static str]) -> Self {
+        self.writes.extend(slots);
+        self
+    }
+
+    pub fn reading_writing(mut self, slots: &[&'static str]) -> Self {
+        self.reads_writes.extend(slots);
+        self
+    }
+
+    pub fn using(mut self, slot: &'static str, usage: SlotUsage) -> Self {
+        self.usages.push((slot, usage));
+        self
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.enabled = false;
+        self
+    }
+
+    pub fn boxed(self) -> Box<dyn PassNode> {
+        Box::new(self)
+    }
+}
+
+impl PassNode for TestPass {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn reads(&self) -> Vec<&str> {
+        self.reads.clone()
+    }
+
+    fn writes(&self) -> Vec<&str> {
+        self.writes.clone()
+    }
+
+    fn reads_writes(&self) -> Vec<&str> {
+        self.reads_writes.clone()
+    }
+
+    fn slot_usage(&self, slot: &str) -> Option<SlotUsage> {
+        self.usages
+            .iter()
+            .find(|(name, _)| *name == slot)
+            .map(|(_, usage)| *usage)
+    }
+
+    fn is_enabled(&self, _configs: &()) -> bool {
+        self.enabled
+    }
+
+    fn execute<'r, 'e>(
+        &mut self,
+        context: PassExecutionContext<'r, 'e>,
+    ) -> Result<Vec<SubGraphRunCommand<'r>>> {
+        Ok(context.into_sub_graph_commands())
+    }
+}
+
+pub fn blit(name: &'static str) -> Box<dyn PassNode> {
+    TestPass::new(name)
+        .reading(&["input"])
+        .writing(&["output"])
+        .boxed()
+}
+
+pub fn fill(name: &'static str) -> Box<dyn PassNode> {
+    TestPass::new(name).writing(&["output"]).boxed()
+}
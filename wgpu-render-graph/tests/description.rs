@@ -0,0 +1,182 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use common::{TestPass, blit, fill};
+use wgpu_render_graph::{
+    GraphDescription, GraphExport, PassRegistry, RenderGraph, RenderGraphError,
+};
+
+const DESCRIPTION: &str = r#"{
+    "resources": [
+        { "name": "scene", "relative_size": 1.0 },
+        { "name": "accumulation", "history": true, "size": [16, 16] },
+        { "name": "surface", "external": true }
+    ],
+    "passes": [
+        { "type": "fill", "slots": { "output": "scene" } },
+        { "type": "blit", "label": "present", "slots": { "input": "scene", "output": "surface" } }
+    ]
+}"#;
+
+fn registry() -> PassRegistry {
+    let mut registry = PassRegistry::new();
+    registry
+        .register("fill", |_| Ok(fill("fill")))
+        .register("blit", |_| Ok(blit("blit")))
+        .register("broken", |_| {
+            Err(RenderGraphError::InvalidDescription {
+                message: "missing parameter".to_string(),
+            })
+        })
+        .register("accumulate", |_| {
+            Ok(TestPass::new("accumulate")
+                .reading_writing(&["target"])
+                .boxed())
+        });
+    registry
+}
+
+fn description_with(pass: &str) -> GraphDescription {
+    let mut description = GraphDescription::from_json(DESCRIPTION).unwrap();
+    let pass = serde_json::from_str(pass).unwrap();
+    description.passes.push(pass);
+    description
+}
+
+fn assert_untouched(graph: &RenderGraph) {
+    let export = graph.export();
+    assert!(export.passes.is_empty());
+    assert!(export.resources.is_empty());
+}
+
+#[test]
+fn loads_resources_and_passes() {
+    let description = GraphDescription::from_json(DESCRIPTION).unwrap();
+    let (mut graph, resources) = RenderGraph::from_description(&description, &registry()).unwrap();
+
+    assert!(resources.contains_key("accumulation (previous)"));
+    assert!(graph.pass_index("present").is_some());
+    graph.compile().unwrap();
+    assert_eq!(graph.report().unwrap().execution_order, ["fill", "present"]);
+}
+
+#[test]
+fn failing_factories_leave_the_graph_untouched() {
+    let description = description_with(r#"{ "type": "broken", "slots": {} }"#);
+    let mut graph = RenderGraph::new();
+
+    let result = graph.load_description(&description, &registry());
+    assert!(matches!(
+        result,
+        Err(RenderGraphError::InvalidDescription { .. })
+    ));
+    assert_untouched(&graph);
+}
+
+#[test]
+fn unmapped_slots_leave_the_graph_untouched() {
+    let description = description_with(r#"{ "type": "blit", "slots": { "input": "scene" } }"#);
+    let mut graph = RenderGraph::new();
+
+    let result = graph.load_description(&description, &registry());
+    assert!(matches!(
+        result,
+        Err(RenderGraphError::SlotNotMapped { ref slot, .. }) if slot == "output"
+    ));
+    assert_untouched(&graph);
+}
+
+#[test]
+fn duplicate_labels_leave_the_graph_untouched() {
+    let description = description_with(
+        r#"{ "type": "fill", "label": "present", "slots": { "output": "surface" } }"#,
+    );
+    let mut graph = RenderGraph::new();
+
+    let result = graph.load_description(&description, &registry());
+    assert!(matches!(
+        result,
+        Err(RenderGraphError::DuplicatePassName { ref name }) if name == "present"
+    ));
+    assert_untouched(&graph);
+}
+
+#[test]
+fn writes_to_previous_frames_leave_the_graph_untouched() {
+    let description = description_with(
+        r#"{ "type": "accumulate", "slots": { "target": "accumulation (previous)" } }"#,
+    );
+    let mut graph = RenderGraph::new();
+
+    let result = graph.load_description(&description, &registry());
+    assert!(matches!(
+        result,
+        Err(RenderGraphError::PreviousFrameWrite { .. })
+    ));
+    assert_untouched(&graph);
+}
+
+#[test]
+fn labels_clashing_with_unlabeled_passes_leave_the_graph_untouched() {
+    let description = description_with(
+        r#"{ "type": "blit", "label": "fill", "slots": { "input": "scene", "output": "surface" } }"#,
+    );
+    let mut graph = RenderGraph::new();
+
+    let result = graph.load_description(&description, &registry());
+    assert!(matches!(
+        result,
+        Err(RenderGraphError::DuplicatePassName { ref name }) if name == "fill"
+    ));
+    assert_untouched(&graph);
+}
+
+#[test]
+fn failed_pass_insertion_rolls_back_the_load() {
+    let mut graph = RenderGraph::new();
+    let existing = graph.add_color_texture("existing").size(16, 16).transient();
+    graph
+        .add_pass(fill("existing_fill"), &[("output", existing)])
+        .unwrap();
+    let before = graph.export();
+
+    let description = GraphDescription::from_json(
+        r#"{
+            "resources": [
+                { "name": "mips", "size": [16, 16], "mip_levels": 2 },
+                { "name": "surface", "external": true }
+            ],
+            "passes": [
+                { "type": "blit", "slots": { "input": "mips", "output": "surface" } },
+                { "type": "fill", "slots": { "output": "mips" } }
+            ]
+        }"#,
+    )
+    .unwrap();
+    let result = graph.load_description(&description, &registry());
+    assert!(matches!(
+        result,
+        Err(RenderGraphError::InvalidSubresource { .. })
+    ));
+
+    let after = graph.export();
+    let names = |export: &GraphExport| {
+        (
+            export
+                .passes
+                .iter()
+                .map(|pass| pass.name.clone())
+                .collect::<Vec<_>>(),
+            export
+                .resources
+                .iter()
+                .map(|resource| (resource.id, resource.name.clone()))
+                .collect::<Vec<_>>(),
+        )
+    };
+    assert_eq!(names(&after), names(&before));
+
+    let surface = graph.add_color_texture("surface").external();
+    assert_eq!(surface.0, existing.0 + 1);
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use common::{blit, fill};
+use wgpu_render_graph::{RenderGraph, ResourceId};
+
+fn transient(graph: &mut RenderGraph, name: &str) -> ResourceId {
+    graph.add_color_texture(name).size(32, 32).transient()
+}
+
+fn post_graph() -> RenderGraph {
+    let mut graph = RenderGraph::new();
+    let hdr = transient(&mut graph, "hdr");
+    let debug = transient(&mut graph, "debug");
+    let surface = graph.add_color_texture("surface").external();
+
+    graph.add_pass(fill("scene"), &[("output", hdr)]).unwrap();
+    graph
+        .add_pass(blit("debug_view"), &[("input", hdr), ("output", debug)])
+        .unwrap();
+    graph
+        .add_pass(blit("tonemap"), &[("input", hdr), ("output", surface)])
+        .unwrap();
+    graph
+}
+
+fn edge_names(graph: &RenderGraph) -> Vec<(String, String, String)> {
+    let export = graph.export();
+    let pass_name = |index: usize| {
+        export
+            .passes
+            .iter()
+            .find(|pass| pass.index == index)
+            .unwrap()
+            .name
+            .clone()
+    };
+    export
+        .edges
+        .iter()
+        .map(|edge| {
+            (
+                pass_name(edge.from),
+                pass_name(edge.to),
+                edge.resource_name.clone(),
+            )
+        })
+        .collect()
+}
+
+fn dot_line<'a>(dot: &'a str, needle: &str) -> &'a str {
+    dot.lines().find(|line| line.contains(needle)).unwrap()
+}
+
+#[test]
+fn edges_are_exported_before_compile() {
+    let graph = post_graph();
+
+    assert_eq!(
+        edge_names(&graph),
+        [
+            ("scene".into(), "debug_view".into(), "hdr".into()),
+            ("scene".into(), "tonemap".into(), "hdr".into()),
+        ]
+    );
+}
+
+#[test]
+fn removed_passes_drop_their_edges() {
+    let mut graph = post_graph();
+    graph.compile().unwrap();
+    graph.remove_pass("debug_view").unwrap();
+
+    assert_eq!(
+        edge_names(&graph),
+        [("scene".into(), "tonemap".into(), "hdr".into())]
+    );
+    assert!(graph.export().passes.iter().all(|pass| !pass.culled));
+}
+
+#[test]
+fn dot_styles_culled_passes() {
+    let mut graph = post_graph();
+    graph.compile().unwrap();
+    let dot = graph.to_dot();
+
+    assert!(dot_line(&dot, "debug_view").contains("style=\"filled,dashed\""));
+    assert!(dot_line(&dot, "tonemap").contains("style=filled,"));
+}
+
+#[test]
+fn dot_labels_edges_with_resources() {
+    let mut graph = post_graph();
+    graph.compile().unwrap();
+    let dot = graph.to_dot();
+
+    assert!(dot.contains("pass_0 -> pass_2 [label=\"hdr\"];"));
+}
+
+#[test]
+fn dot_clusters_resources_by_pool_slot() {
+    let mut graph = post_graph();
+    graph.compile().unwrap();
+    let export = graph.export();
+    let dot = graph.to_dot();
+
+    let hdr = export
+        .resources
+        .iter()
+        .find(|resource| resource.name == "hdr")
+        .unwrap();
+    let pool = hdr.pool_slot.unwrap();
+    assert!(dot.contains(&format!("subgraph cluster_pool_{} {{", pool)));
+    assert!(dot.contains(&format!(
+        "resource_{} [label=\"hdr\", shape=note];",
+        hdr.id.0
+    )));
+    assert!(dot.contains(&format!(
+        "pass_0 -> resource_{} [style=dotted, arrowhead=none];",
+        hdr.id.0
+    )));
+}
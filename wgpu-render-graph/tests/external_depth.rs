@@ -0,0 +1,97 @@
+mod common;
+
+use common::{TestPass, noop_device};
+use wgpu::{TextureFormat, TextureUsages};
+use wgpu_render_graph::{AttachmentLoadOp, RenderGraph, ResourceId, ValidationIssue};
+
+fn depth_graph(format: TextureFormat) -> (RenderGraph, ResourceId) {
+    let mut graph = RenderGraph::new();
+    let depth = graph
+        .add_depth_texture("depth")
+        .format(format)
+        .clear_depth(1.0)
+        .external();
+    graph
+        .add_pass(
+            TestPass::new("prepass").writing(&["depth"]).boxed(),
+            &[("depth", depth)],
+        )
+        .unwrap();
+    (graph, depth)
+}
+
+#[test]
+fn depth_only_formats_have_no_stencil_ops() {
+    let (mut graph, depth) = depth_graph(TextureFormat::Depth32Float);
+    graph.compile().unwrap();
+
+    let report = graph.report().unwrap();
+    assert_eq!(
+        report.load_op("prepass", depth),
+        Some(AttachmentLoadOp::Clear)
+    );
+    assert_eq!(report.stencil_load_op("prepass", depth), None);
+    assert_eq!(report.stencil_store_op("prepass", depth), None);
+}
+
+#[test]
+fn depth_stencil_formats_have_stencil_ops() {
+    let (mut graph, depth) = depth_graph(TextureFormat::Depth24PlusStencil8);
+    graph.compile().unwrap();
+
+    let report = graph.report().unwrap();
+    assert_eq!(
+        report.stencil_load_op("prepass", depth),
+        Some(AttachmentLoadOp::Load)
+    );
+    assert!(report.stencil_store_op("prepass", depth).is_some());
+}
+
+#[test]
+fn stencil_clear_requires_stencil_aspect() {
+    let mut graph: RenderGraph = RenderGraph::new();
+    graph.add_depth_texture("depth").clear_stencil(0).external();
+
+    assert_eq!(
+        graph.validate(),
+        [ValidationIssue::StencilClearWithoutStencil {
+            resource: "depth".to_string(),
+            format: TextureFormat::Depth32Float,
+        }]
+    );
+}
+
+#[test]
+fn bound_texture_must_match_declared_format() {
+    let (device, _queue) = noop_device();
+    let (mut graph, depth) = depth_graph(TextureFormat::Depth24PlusStencil8);
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("depth"),
+        size: wgpu::Extent3d {
+            width: 32,
+            height: 32,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: TextureFormat::Depth32Float,
+        usage: TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    graph
+        .resources_mut()
+        .set_external_texture_from(depth, &texture, view);
+
+    assert!(
+        graph
+            .validate()
+            .contains(&ValidationIssue::ExternalFormatMismatch {
+                resource: "depth".to_string(),
+                declared: TextureFormat::Depth24PlusStencil8,
+                bound: TextureFormat::Depth32Float,
+            })
+    );
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::{TestPass, blit, fill};
+use wgpu_render_graph::RenderGraph;
+
+fn position(order: &[String], pass: &str) -> usize {
+    order.iter().position(|name| name == pass).unwrap()
+}
+
+#[test]
+fn readers_run_before_a_later_overwrite() {
+    let mut graph = RenderGraph::new();
+    let shared = graph.add_color_texture("shared").size(16, 16).transient();
+    let first_out = graph.add_color_texture("first_out").external();
+    let second_out = graph.add_color_texture("second_out").external();
+
+    graph
+        .add_pass(fill("write_a"), &[("output", shared)])
+        .unwrap();
+    graph
+        .add_pass(blit("read_a"), &[("input", shared), ("output", first_out)])
+        .unwrap();
+    graph
+        .add_pass(fill("write_b"), &[("output", shared)])
+        .unwrap();
+    graph
+        .add_pass(blit("read_b"), &[("input", shared), ("output", second_out)])
+        .unwrap();
+    graph.compile().unwrap();
+
+    let order = graph.report().unwrap().execution_order;
+    assert!(position(&order, "write_a") < position(&order, "read_a"));
+    assert!(position(&order, "read_a") < position(&order, "write_b"));
+    assert!(position(&order, "write_b") < position(&order, "read_b"));
+}
+
+#[test]
+fn writers_of_the_same_resource_keep_their_order() {
+    let mut graph = RenderGraph::new();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(fill("background"), &[("output", surface)])
+        .unwrap();
+    graph
+        .add_pass(fill("overlay"), &[("output", surface)])
+        .unwrap();
+    graph
+        .add_pass(fill("cursor"), &[("output", surface)])
+        .unwrap();
+    graph.compile().unwrap();
+
+    let order = graph.report().unwrap().execution_order;
+    assert_eq!(order, ["background", "overlay", "cursor"]);
+}
+
+#[test]
+fn read_write_passes_order_against_readers_and_writers() {
+    let mut graph = RenderGraph::new();
+    let accumulation = graph
+        .add_color_texture("accumulation")
+        .size(16, 16)
+        .transient();
+    let snapshot = graph.add_color_texture("snapshot").external();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(fill("clear"), &[("output", accumulation)])
+        .unwrap();
+    graph
+        .add_pass(
+            blit("snapshot"),
+            &[("input", accumulation), ("output", snapshot)],
+        )
+        .unwrap();
+    graph
+        .add_pass(
+            TestPass::new("accumulate")
+                .reading_writing(&["target"])
+                .boxed(),
+            &[("target", accumulation)],
+        )
+        .unwrap();
+    graph
+        .add_pass(
+            blit("present"),
+            &[("input", accumulation), ("output", surface)],
+        )
+        .unwrap();
+    graph.compile().unwrap();
+
+    let order = graph.report().unwrap().execution_order;
+    assert_eq!(order, ["clear", "snapshot", "accumulate", "present"]);
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::noop_device;
+use std::path::PathBuf;
+use wgpu::naga::valid::Capabilities;
+use wgpu_render_graph::{PipelineRegistry, ShaderLibrary, validate_wgsl};
+
+const EMBEDDED: &str = "@compute @workgroup_size(1) fn main() {}";
+const ON_DISK: &str = "@compute @workgroup_size(2) fn main() {}";
+const HALF_FLOAT: &str = "enable f16;\nfn half() -> f16 { return 1.0h; }";
+
+fn shader_root(name: &str) -> PathBuf {
+    let root =
+        std::env::temp_dir().join(format!("wgpu-render-graph-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("shader.wgsl"), ON_DISK).unwrap();
+    root
+}
+
+#[test]
+fn file_shaders_without_root_use_embedded_source() {
+    let mut shaders = ShaderLibrary::new();
+    shaders.register_file("shader", "shader.wgsl", EMBEDDED);
+
+    assert_eq!(shaders.source("shader"), Some(EMBEDDED));
+    assert!(shaders.poll().is_empty());
+}
+
+#[test]
+fn file_shaders_resolve_against_root() {
+    let root = shader_root("root");
+    let mut shaders = ShaderLibrary::new().with_root(&root);
+    shaders.register_file("shader", "shader.wgsl", EMBEDDED);
+
+    assert_eq!(shaders.source("shader"), Some(ON_DISK));
+    assert_eq!(shaders.generation("shader"), Some(1));
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn reload_validates_against_library_capabilities() {
+    let root = shader_root("capabilities");
+    std::fs::write(root.join("shader.wgsl"), HALF_FLOAT).unwrap();
+
+    let mut shaders = ShaderLibrary::new()
+        .with_root(&root)
+        .with_capabilities(Capabilities::empty());
+    shaders.register_file("shader", "shader.wgsl", EMBEDDED);
+
+    assert_eq!(shaders.source("shader"), Some(EMBEDDED));
+    assert!(shaders.error("shader").is_some());
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn validation_uses_the_given_capabilities() {
+    assert!(validate_wgsl(HALF_FLOAT, Capabilities::empty()).is_err());
+    assert!(validate_wgsl(HALF_FLOAT, Capabilities::SHADER_FLOAT16).is_ok());
+    assert!(validate_wgsl(HALF_FLOAT, ShaderLibrary::new().capabilities()).is_ok());
+}
+
+#[test]
+fn rejected_creation_is_dropped_from_registry() {
+    let (device, _queue) = noop_device();
+    let mut pipelines = PipelineRegistry::new();
+
+    let created = pipelines.try_create(&device, |pipelines| {
+        pipelines.shader_module(&device, "Valid", EMBEDDED)
+    });
+    assert!(created.is_ok());
+
+    let rejected = pipelines.try_create(&device, |pipelines| {
+        pipelines.shader_module(&device, "Invalid", "fn main( {")
+    });
+    assert!(rejected.is_err());
+    assert_eq!(pipelines.stats().shader_modules, 1);
+}
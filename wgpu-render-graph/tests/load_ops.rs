@@ -0,0 +1,114 @@
+mod common;
+
+use common::{TestPass, blit, fill};
+use wgpu_render_graph::{AttachmentLoadOp, RenderGraph};
+
+#[test]
+fn first_writer_clears_and_later_writers_load() {
+    let mut graph = RenderGraph::new();
+    let target = graph
+        .add_color_texture("target")
+        .size(32, 32)
+        .clear_color(wgpu::Color::BLACK)
+        .transient();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(fill("opaque"), &[("output", target)])
+        .unwrap();
+    graph
+        .add_pass(
+            TestPass::new("overlay")
+                .reading_writing(&["target"])
+                .boxed(),
+            &[("target", target)],
+        )
+        .unwrap();
+    graph
+        .add_pass(blit("present"), &[("input", target), ("output", surface)])
+        .unwrap();
+    graph.compile().unwrap();
+
+    let report = graph.report().unwrap();
+    assert_eq!(
+        report.load_op("opaque", target),
+        Some(AttachmentLoadOp::Clear)
+    );
+    assert_eq!(
+        report.load_op("overlay", target),
+        Some(AttachmentLoadOp::Load)
+    );
+}
+
+#[test]
+fn unobserved_contents_are_not_loaded() {
+    let mut graph = RenderGraph::new();
+    let scratch = graph.add_color_texture("scratch").size(32, 32).transient();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(fill("scratch_fill"), &[("output", scratch)])
+        .unwrap();
+    graph
+        .add_pass(blit("present"), &[("input", scratch), ("output", surface)])
+        .unwrap();
+    graph.compile().unwrap();
+
+    let report = graph.report().unwrap();
+    assert_eq!(
+        report.load_op("scratch_fill", scratch),
+        Some(AttachmentLoadOp::DontCare)
+    );
+    assert_eq!(
+        report.load_op("present", surface),
+        Some(AttachmentLoadOp::Load)
+    );
+}
+
+#[test]
+fn external_targets_clear_only_when_asked() {
+    let mut graph = RenderGraph::new();
+    let depth = graph
+        .add_depth_texture("depth")
+        .size(32, 32)
+        .clear_depth(1.0)
+        .external();
+    let surface = graph
+        .add_color_texture("surface")
+        .clear_color(wgpu::Color::WHITE)
+        .external();
+
+    graph
+        .add_pass(
+            TestPass::new("scene").writing(&["color", "depth"]).boxed(),
+            &[("color", surface), ("depth", depth)],
+        )
+        .unwrap();
+    graph
+        .add_pass(
+            TestPass::new("decals")
+                .reading_writing(&["color", "depth"])
+                .boxed(),
+            &[("color", surface), ("depth", depth)],
+        )
+        .unwrap();
+    graph.compile().unwrap();
+
+    let report = graph.report().unwrap();
+    assert_eq!(
+        report.load_op("scene", surface),
+        Some(AttachmentLoadOp::Clear)
+    );
+    assert_eq!(
+        report.load_op("scene", depth),
+        Some(AttachmentLoadOp::Clear)
+    );
+    assert_eq!(
+        report.load_op("decals", surface),
+        Some(AttachmentLoadOp::Load)
+    );
+    assert_eq!(
+        report.load_op("decals", depth),
+        Some(AttachmentLoadOp::Load)
+    );
+}
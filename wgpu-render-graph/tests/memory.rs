@@ -0,0 +1,165 @@
+mod common;
+
+use common::{blit, fill, noop_device};
+use std::sync::{Arc, Mutex};
+use wgpu::Texture;
+use wgpu_render_graph::{
+    MemoryBudget, PassExecutionContext, PassNode, RecordingBackend, RenderGraph, RenderGraphError,
+    ResourceHandle, Result, SubGraphRunCommand,
+};
+
+const TEXTURE_BYTES: u64 = 32 * 32 * 4;
+
+struct HistoryFrame {
+    previous: Texture,
+    current: Texture,
+    previous_valid: bool,
+}
+
+struct AccumulatePass {
+    frames: Arc<Mutex<Vec<HistoryFrame>>>,
+}
+
+impl PassNode for AccumulatePass {
+    fn name(&self) -> &str {
+        "accumulate"
+    }
+
+    fn reads(&self) -> Vec<&str> {
+        vec!["input"]
+    }
+
+    fn writes(&self) -> Vec<&str> {
+        vec!["output"]
+    }
+
+    fn execute<'r, 'e>(
+        &mut self,
+        context: PassExecutionContext<'r, 'e>,
+    ) -> Result<Vec<SubGraphRunCommand<'r>>> {
+        let texture = |slot: &str| match context.resources.get_handle(context.get_slot(slot)?) {
+            Some(ResourceHandle::TransientTexture { texture, .. }) => Ok(Texture::clone(texture)),
+            _ => panic!("history slots are transient textures"),
+        };
+        let frame = HistoryFrame {
+            previous: texture("input")?,
+            current: texture("output")?,
+            previous_valid: context.is_history_valid("input"),
+        };
+        self.frames.lock().unwrap().push(frame);
+        Ok(context.into_sub_graph_commands())
+    }
+}
+
+fn history_graph() -> RenderGraph {
+    history_graph_with(blit("accumulate"))
+}
+
+fn history_graph_with(accumulate: Box<dyn PassNode>) -> RenderGraph {
+    let mut graph = RenderGraph::new();
+    let accumulation = graph
+        .add_color_texture("accumulation")
+        .size(32, 32)
+        .history();
+    let previous = graph.previous(accumulation).unwrap();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(accumulate, &[("input", previous), ("output", accumulation)])
+        .unwrap();
+    graph
+        .add_pass(
+            blit("present"),
+            &[("input", accumulation), ("output", surface)],
+        )
+        .unwrap();
+    graph
+}
+
+#[test]
+fn history_textures_count_twice() {
+    let mut graph = history_graph();
+    graph.compile().unwrap();
+
+    let report = graph.report().unwrap();
+    assert_eq!(report.peak_transient_bytes, 2 * TEXTURE_BYTES);
+    assert_eq!(report.unaliased_transient_bytes, 2 * TEXTURE_BYTES);
+    assert_eq!(graph.estimated_transient_bytes(), Some(2 * TEXTURE_BYTES));
+}
+
+#[test]
+fn history_textures_count_towards_the_budget() {
+    let mut graph = history_graph();
+    graph.set_memory_budget(Some(MemoryBudget::fail(TEXTURE_BYTES)));
+
+    assert!(matches!(
+        graph.compile(),
+        Err(RenderGraphError::MemoryBudgetExceeded {
+            estimated,
+            budget: TEXTURE_BYTES,
+        }) if estimated == 2 * TEXTURE_BYTES
+    ));
+}
+
+#[test]
+fn warn_budget_compiles_over_budget() {
+    let mut graph = RenderGraph::new();
+    let scratch = graph.add_color_texture("scratch").size(32, 32).transient();
+    let surface = graph.add_color_texture("surface").external();
+    graph
+        .add_pass(fill("scratch_fill"), &[("output", scratch)])
+        .unwrap();
+    graph
+        .add_pass(blit("present"), &[("input", scratch), ("output", surface)])
+        .unwrap();
+    graph.set_memory_budget(Some(MemoryBudget::warn(TEXTURE_BYTES / 2)));
+
+    graph.compile().unwrap();
+    assert!(graph.report().unwrap().is_over_budget());
+}
+
+#[test]
+fn previous_reads_last_frames_texture() {
+    let (device, queue) = noop_device();
+    let frames = Arc::new(Mutex::new(Vec::new()));
+    let mut graph = history_graph_with(Box::new(AccumulatePass {
+        frames: Arc::clone(&frames),
+    }));
+    let backend = RecordingBackend::new(&device);
+
+    graph.execute(&backend, &queue, &()).unwrap();
+    graph.execute(&backend, &queue, &()).unwrap();
+    graph.execute(&backend, &queue, &()).unwrap();
+
+    let frames = frames.lock().unwrap();
+    assert_ne!(frames[0].previous, frames[0].current);
+    assert_eq!(frames[1].previous, frames[0].current);
+    assert_eq!(frames[1].current, frames[0].previous);
+    assert_eq!(frames[2].previous, frames[0].previous);
+    assert_eq!(frames[2].current, frames[0].current);
+    assert_eq!(
+        backend.created_textures(),
+        ["accumulation [0]", "accumulation [1]"]
+    );
+}
+
+#[test]
+fn previous_is_invalid_on_the_first_frame() {
+    let (device, queue) = noop_device();
+    let frames = Arc::new(Mutex::new(Vec::new()));
+    let mut graph = history_graph_with(Box::new(AccumulatePass {
+        frames: Arc::clone(&frames),
+    }));
+    let backend = RecordingBackend::new(&device);
+
+    graph.execute(&backend, &queue, &()).unwrap();
+    graph.execute(&backend, &queue, &()).unwrap();
+
+    let valid: Vec<bool> = frames
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|frame| frame.previous_valid)
+        .collect();
+    assert_eq!(valid, [false, true]);
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::noop_device;
+use wgpu_render_graph::PipelineRegistry;
+
+const FIRST: &str = "@compute @workgroup_size(1) fn main() {}";
+const SECOND: &str = "@compute @workgroup_size(2) fn main() {}";
+
+fn compute_pipeline(
+    device: &wgpu::Device,
+    pipelines: &mut PipelineRegistry,
+    label: &str,
+    source: &str,
+    constants: &[(&str, f64)],
+) -> wgpu::ComputePipeline {
+    let module = pipelines.shader_module(device, label, source);
+    pipelines.compute_pipeline(
+        device,
+        &wgpu::ComputePipelineDescriptor {
+            label: Some(label),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants,
+                ..Default::default()
+            },
+            cache: None,
+        },
+    )
+}
+
+#[test]
+fn identical_descriptors_are_reused() {
+    let (device, _queue) = noop_device();
+    let mut pipelines = PipelineRegistry::new();
+
+    let first = compute_pipeline(&device, &mut pipelines, "Shader", FIRST, &[]);
+    let second = compute_pipeline(&device, &mut pipelines, "Shader", FIRST, &[]);
+
+    assert_eq!(first, second);
+    let stats = pipelines.stats();
+    assert_eq!(stats.shader_modules, 1);
+    assert_eq!(stats.compute_pipelines, 1);
+    assert_eq!(stats.reused, 2);
+}
+
+#[test]
+fn differing_descriptors_get_distinct_pipelines() {
+    let (device, _queue) = noop_device();
+    let mut pipelines = PipelineRegistry::new();
+
+    let first = compute_pipeline(&device, &mut pipelines, "First", FIRST, &[]);
+    let second = compute_pipeline(&device, &mut pipelines, "Second", SECOND, &[]);
+
+    assert_ne!(first, second);
+    assert_eq!(pipelines.stats().compute_pipelines, 2);
+}
+
+#[test]
+fn reloading_a_shader_evicts_its_old_pipelines() {
+    let (device, _queue) = noop_device();
+    let mut pipelines = PipelineRegistry::new();
+
+    compute_pipeline(&device, &mut pipelines, "Shader", FIRST, &[]);
+    compute_pipeline(&device, &mut pipelines, "Other", SECOND, &[]);
+    compute_pipeline(&device, &mut pipelines, "Shader", SECOND, &[]);
+
+    let stats = pipelines.stats();
+    assert_eq!(stats.shader_modules, 1);
+    assert_eq!(stats.compute_pipelines, 1);
+}
+
+#[test]
+fn repeated_reloads_keep_the_registry_flat() {
+    let (device, _queue) = noop_device();
+    let mut pipelines = PipelineRegistry::new();
+
+    for size in 1..=5 {
+        let source = format!("@compute @workgroup_size({}) fn main() {{}}", size);
+        compute_pipeline(&device, &mut pipelines, "Shader", &source, &[]);
+
+        let stats = pipelines.stats();
+        assert_eq!(stats.shader_modules, 1);
+        assert_eq!(stats.compute_pipelines, 1);
+    }
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::{TestPass, blit, fill, noop_device};
+use wgpu_render_graph::{RecordingBackend, RenderGraph};
+
+fn profiled_graph() -> RenderGraph {
+    let mut graph = RenderGraph::new();
+    let hdr = graph.add_color_texture("hdr").size(32, 32).transient();
+    let unused = graph.add_color_texture("unused").size(32, 32).transient();
+    let overlay = graph.add_color_texture("overlay").external();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph.add_pass(fill("scene"), &[("output", hdr)]).unwrap();
+    graph
+        .add_pass(fill("orphan"), &[("output", unused)])
+        .unwrap();
+    graph
+        .add_pass(
+            TestPass::new("debug")
+                .writing(&["output"])
+                .disabled()
+                .boxed(),
+            &[("output", overlay)],
+        )
+        .unwrap();
+    graph
+        .add_pass(blit("tonemap"), &[("input", hdr), ("output", surface)])
+        .unwrap();
+    graph.enable_profiling();
+    graph
+}
+
+#[test]
+fn cpu_timings_are_reported_without_timestamp_queries() {
+    let (device, queue) = noop_device();
+    let mut graph = profiled_graph();
+    let backend = RecordingBackend::new(&device);
+
+    graph.execute(&backend, &queue, &()).unwrap();
+    graph.profiler_frame_submitted();
+
+    let frames = graph.take_frame_timings();
+    assert_eq!(frames.len(), 1);
+    let frame = &frames[0];
+    assert_eq!(frame.frame, 1);
+    let names: Vec<_> = frame.passes.iter().map(|pass| pass.name.as_str()).collect();
+    assert_eq!(names, ["scene", "tonemap"]);
+    assert!(!frame.has_gpu_timings());
+    assert_eq!(frame.gpu_total(), None);
+    assert_eq!(
+        frame.cpu_total(),
+        frame
+            .passes
+            .iter()
+            .map(|pass| pass.cpu_prepare + pass.cpu_execute)
+            .sum()
+    );
+}
+
+#[test]
+fn frames_are_numbered_and_drained() {
+    let (device, queue) = noop_device();
+    let mut graph = profiled_graph();
+    let backend = RecordingBackend::new(&device);
+
+    graph.execute(&backend, &queue, &()).unwrap();
+    graph.execute(&backend, &queue, &()).unwrap();
+
+    let frames: Vec<_> = graph
+        .take_frame_timings()
+        .iter()
+        .map(|frame| frame.frame)
+        .collect();
+    assert_eq!(frames, [1, 2]);
+    assert!(graph.take_frame_timings().is_empty());
+
+    graph.execute(&backend, &queue, &()).unwrap();
+    assert_eq!(graph.take_frame_timings()[0].frame, 3);
+}
+
+#[test]
+fn disabling_profiling_discards_timings() {
+    let (device, queue) = noop_device();
+    let mut graph = profiled_graph();
+    let backend = RecordingBackend::new(&device);
+
+    graph.execute(&backend, &queue, &()).unwrap();
+    graph.disable_profiling();
+    graph.profiler_frame_submitted();
+
+    assert!(!graph.is_profiling());
+    assert!(graph.take_frame_timings().is_empty());
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use common::{TestPass, blit, noop_device};
+use wgpu::{TextureFormat, TextureUsages};
+use wgpu_render_graph::{ReadbackData, RecordingBackend, RenderGraph, RenderGraphError};
+
+fn surface_texture(device: &wgpu::Device) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("surface"),
+        size: wgpu::Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: TextureFormat::Rgba8Unorm,
+        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
+
+#[test]
+fn readback_without_enabled_writer_fails() {
+    let (device, queue) = noop_device();
+    let mut graph: RenderGraph = RenderGraph::new();
+    let scratch = graph.add_color_texture("scratch").size(4, 4).transient();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(
+            TestPass::new("scratch_fill")
+                .writing(&["output"])
+                .disabled()
+                .boxed(),
+            &[("output", scratch)],
+        )
+        .unwrap();
+    graph
+        .add_pass(blit("present"), &[("input", scratch), ("output", surface)])
+        .unwrap();
+
+    let readback = graph.read_back(scratch).unwrap();
+    graph
+        .execute(&RecordingBackend::new(&device), &queue, &())
+        .unwrap();
+
+    assert!(matches!(
+        readback.try_take(),
+        Some(Err(RenderGraphError::ReadbackFailed { resource, .. })) if resource == "scratch"
+    ));
+}
+
+#[test]
+fn external_views_cannot_be_read_back() {
+    let (device, queue) = noop_device();
+    let mut graph: RenderGraph = RenderGraph::new();
+    let surface = graph.add_color_texture("surface").external();
+    graph
+        .add_pass(
+            TestPass::new("present").writing(&["output"]).boxed(),
+            &[("output", surface)],
+        )
+        .unwrap();
+
+    let texture = surface_texture(&device);
+    graph.resources_mut().set_external_texture(
+        surface,
+        texture.create_view(&wgpu::TextureViewDescriptor::default()),
+    );
+
+    let readback = graph.read_back(surface).unwrap();
+    graph
+        .execute(&RecordingBackend::new(&device), &queue, &())
+        .unwrap();
+
+    assert!(matches!(
+        readback.try_take(),
+        Some(Err(RenderGraphError::ReadbackUnsupported { .. }))
+    ));
+}
+
+#[test]
+fn external_textures_are_copied_from_their_texture() {
+    let (device, queue) = noop_device();
+    let mut graph: RenderGraph = RenderGraph::new();
+    let surface = graph.add_color_texture("surface").external();
+    graph
+        .add_pass(
+            TestPass::new("present").writing(&["output"]).boxed(),
+            &[("output", surface)],
+        )
+        .unwrap();
+
+    let texture = surface_texture(&device);
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    graph
+        .resources_mut()
+        .set_external_texture_from(surface, &texture, view);
+
+    let readback = graph.read_back(surface).unwrap();
+    let command_buffers = graph
+        .execute(&RecordingBackend::new(&device), &queue, &())
+        .unwrap();
+    queue.submit(command_buffers);
+    graph.map_readbacks();
+    device.poll(wgpu::PollType::Wait).unwrap();
+
+    match readback.try_take() {
+        Some(Ok(ReadbackData::Texture {
+            format,
+            width,
+            height,
+            bytes,
+        })) => {
+            assert_eq!(format, TextureFormat::Rgba8Unorm);
+            assert_eq!((width, height), (4, 4));
+            assert_eq!(bytes.len(), 4 * 4 * 4);
+        }
+        _ => panic!("expected texture readback data"),
+    }
+}
@@ -0,0 +1,216 @@
+mod common;
+
+use common::{TestPass, blit, fill};
+use wgpu_render_graph::{RenderGraph, RenderGraphError, SlotBinding};
+
+fn execution_order(graph: &mut RenderGraph) -> Vec<String> {
+    graph.compile().unwrap();
+    graph.report().unwrap().execution_order
+}
+
+#[test]
+fn rewiring_the_middle_writer_keeps_its_place_in_the_chain() {
+    let mut graph = RenderGraph::new();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(fill("background"), &[("output", surface)])
+        .unwrap();
+    graph
+        .add_pass(fill("overlay"), &[("output", surface)])
+        .unwrap();
+    let cursor = graph
+        .add_pass_with_bindings(fill("cursor"), &[("output", surface.into())])
+        .unwrap();
+
+    graph
+        .set_pass_slots("overlay", &[("output", surface)])
+        .unwrap();
+    assert_eq!(
+        execution_order(&mut graph),
+        ["background", "overlay", "cursor"]
+    );
+
+    let accumulate = TestPass::new("overlay")
+        .reading_writing(&["output"])
+        .boxed();
+    graph.replace_pass("overlay", accumulate).unwrap();
+    assert_eq!(
+        execution_order(&mut graph),
+        ["background", "overlay", "cursor"]
+    );
+
+    let latest: SlotBinding = cursor.get("output").unwrap().into();
+    graph
+        .add_pass_with_bindings(fill("tooltip"), &[("output", latest)])
+        .unwrap();
+}
+
+#[test]
+fn moving_the_middle_writer_renumbers_only_the_old_and_new_resource() {
+    let mut graph = RenderGraph::new();
+    let surface = graph.add_color_texture("surface").external();
+    let overlay_target = graph.add_color_texture("overlay_target").external();
+
+    graph
+        .add_pass(fill("background"), &[("output", surface)])
+        .unwrap();
+    graph
+        .add_pass(fill("overlay"), &[("output", surface)])
+        .unwrap();
+    graph
+        .add_pass(fill("cursor"), &[("output", surface)])
+        .unwrap();
+
+    graph
+        .set_pass_slots("overlay", &[("output", overlay_target)])
+        .unwrap();
+    assert_eq!(graph.latest_version(surface), surface.version(2));
+    assert_eq!(
+        graph.latest_version(overlay_target),
+        overlay_target.version(1)
+    );
+
+    graph
+        .set_pass_slots("overlay", &[("output", surface)])
+        .unwrap();
+    assert_eq!(graph.latest_version(surface), surface.version(3));
+    assert_eq!(
+        graph.latest_version(overlay_target),
+        overlay_target.version(0)
+    );
+
+    let order = execution_order(&mut graph);
+    let position = |pass: &str| order.iter().position(|name| name == pass).unwrap();
+    assert!(position("background") < position("cursor"));
+}
+
+#[test]
+fn rewired_readers_keep_the_version_they_read() {
+    let mut graph = RenderGraph::new();
+    let shared = graph.add_color_texture("shared").size(16, 16).transient();
+    let snapshot = graph.add_color_texture("snapshot").external();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(fill("clear"), &[("output", shared)])
+        .unwrap();
+    graph
+        .add_pass(blit("snapshot"), &[("input", shared), ("output", snapshot)])
+        .unwrap();
+    graph.add_pass(fill("draw"), &[("output", shared)]).unwrap();
+    graph
+        .add_pass(blit("present"), &[("input", shared), ("output", surface)])
+        .unwrap();
+
+    graph
+        .set_pass_slots("snapshot", &[("input", shared), ("output", snapshot)])
+        .unwrap();
+    assert_eq!(
+        execution_order(&mut graph),
+        ["clear", "snapshot", "draw", "present"]
+    );
+}
+
+#[test]
+fn failed_rebinding_leaves_the_pass_untouched() {
+    let mut graph = RenderGraph::new();
+    let surface = graph.add_color_texture("surface").external();
+    graph
+        .add_pass(fill("present"), &[("output", surface)])
+        .unwrap();
+
+    let result = graph.replace_pass("present", blit("present"));
+    assert!(matches!(
+        result,
+        Err(RenderGraphError::SlotNotMapped { ref slot, .. }) if slot == "input"
+    ));
+    let result = graph.set_pass_slots("present", &[("input", surface)]);
+    assert!(result.is_err());
+
+    assert_eq!(execution_order(&mut graph), ["present"]);
+    assert_eq!(graph.latest_version(surface), surface.version(1));
+}
+
+#[test]
+fn removing_passes_releases_their_pool_slots() {
+    let mut graph = RenderGraph::new();
+    let hdr = graph.add_color_texture("hdr").size(64, 64).transient();
+    let overlay = graph.add_color_texture("overlay").size(32, 32).transient();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph.add_pass(fill("scene"), &[("output", hdr)]).unwrap();
+    graph
+        .add_pass(blit("tonemap"), &[("input", hdr), ("output", surface)])
+        .unwrap();
+    graph
+        .add_pass(fill("overlay_draw"), &[("output", overlay)])
+        .unwrap();
+    graph
+        .add_pass(blit("overlay"), &[("input", overlay), ("output", surface)])
+        .unwrap();
+
+    graph.compile().unwrap();
+    let before = graph.report().unwrap();
+    assert_eq!(before.pools.len(), 2);
+
+    let removed = graph.remove_pass("overlay").unwrap();
+    assert_eq!(removed.name(), "overlay");
+    graph.remove_pass("overlay_draw").unwrap();
+    assert_eq!(graph.latest_version(surface), surface.version(1));
+
+    graph.compile().unwrap();
+    let after = graph.report().unwrap();
+    assert_eq!(after.execution_order, ["scene", "tonemap"]);
+    assert_eq!(after.pools.len(), 1);
+    assert_eq!(after.pools[0].resources, ["hdr"]);
+    assert!(after.peak_transient_bytes < before.peak_transient_bytes);
+    assert_eq!(after.peak_transient_bytes, 64 * 64 * 4);
+}
+
+#[test]
+fn replacing_a_pass_rebuilds_its_edges_and_versions() {
+    let mut graph = RenderGraph::new();
+    let hdr = graph.add_color_texture("hdr").size(16, 16).transient();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph.add_pass(fill("scene"), &[("output", hdr)]).unwrap();
+    graph
+        .add_pass(fill("background"), &[("output", surface)])
+        .unwrap();
+    graph
+        .add_pass(blit("overlay"), &[("input", hdr), ("output", surface)])
+        .unwrap();
+    let cursor = graph
+        .add_pass_with_bindings(fill("cursor"), &[("output", surface.into())])
+        .unwrap();
+    assert_eq!(cursor.get("output"), Some(surface.version(3)));
+    assert_eq!(graph.latest_version(surface), surface.version(3));
+
+    let inspect = TestPass::new("overlay").reading(&["output"]).boxed();
+    let previous = graph.replace_pass("overlay", inspect).unwrap();
+    assert_eq!(previous.reads(), ["input"]);
+    assert_eq!(graph.latest_version(surface), surface.version(2));
+
+    let export = graph.export();
+    let name = |index: usize| {
+        export
+            .passes
+            .iter()
+            .find(|pass| pass.index == index)
+            .unwrap()
+            .name
+            .as_str()
+    };
+    let edges: Vec<_> = export
+        .edges
+        .iter()
+        .map(|edge| (name(edge.from), name(edge.to), edge.resource_name.as_str()))
+        .collect();
+    assert!(edges.contains(&("background", "overlay", "surface")));
+    assert!(!edges.iter().any(|&(from, _, _)| from == "scene"));
+
+    let order = execution_order(&mut graph);
+    assert_eq!(order[..3], ["background", "overlay", "cursor"]);
+    assert!(graph.report().unwrap().is_culled("scene"));
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use common::{blit, fill};
+use wgpu::StoreOp;
+use wgpu_render_graph::{CullReason, RenderGraph, ResourceId};
+
+fn transient(graph: &mut RenderGraph, name: &str) -> ResourceId {
+    graph.add_color_texture(name).size(32, 32).transient()
+}
+
+#[test]
+fn report_requires_a_compiled_graph() {
+    let mut graph: RenderGraph = RenderGraph::new();
+    let surface = graph.add_color_texture("surface").external();
+    graph
+        .add_pass(fill("present"), &[("output", surface)])
+        .unwrap();
+
+    assert!(graph.report().is_none());
+    graph.compile().unwrap();
+    assert!(graph.report().is_some());
+}
+
+#[test]
+fn report_lists_execution_order_and_culled_passes() {
+    let mut graph = RenderGraph::new();
+    let lit = transient(&mut graph, "lit");
+    let debug = transient(&mut graph, "debug");
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(fill("lighting"), &[("output", lit)])
+        .unwrap();
+    graph
+        .add_pass(blit("debug_view"), &[("input", lit), ("output", debug)])
+        .unwrap();
+    graph
+        .add_pass(blit("present"), &[("input", lit), ("output", surface)])
+        .unwrap();
+    graph.compile().unwrap();
+
+    let report = graph.report().unwrap();
+    assert_eq!(report.execution_order.len(), 3);
+    assert_eq!(report.execution_order[0], "lighting");
+    assert!(report.is_culled("debug_view"));
+    assert!(!report.is_culled("present"));
+    assert_eq!(
+        report.culled_passes[0].reason,
+        CullReason::OutputsNeverConsumed {
+            outputs: vec!["debug".to_string()]
+        }
+    );
+}
+
+#[test]
+fn report_describes_pool_assignments_and_store_ops() {
+    let mut graph = RenderGraph::new();
+    let first = transient(&mut graph, "first");
+    let second = transient(&mut graph, "second");
+    let third = transient(&mut graph, "third");
+    let surface = graph.add_color_texture("surface").external();
+
+    graph.add_pass(fill("a"), &[("output", first)]).unwrap();
+    graph
+        .add_pass(blit("b"), &[("input", first), ("output", second)])
+        .unwrap();
+    graph
+        .add_pass(blit("c"), &[("input", second), ("output", third)])
+        .unwrap();
+    graph
+        .add_pass(blit("d"), &[("input", third), ("output", surface)])
+        .unwrap();
+    graph.compile().unwrap();
+
+    let report = graph.report().unwrap();
+    assert_eq!(report.pools.len(), 2);
+    assert_eq!(report.pool_slot(first), report.pool_slot(third));
+    assert_ne!(report.pool_slot(first), report.pool_slot(second));
+    assert_eq!(report.pool_slot(surface), None);
+    assert_eq!(report.pools[0].resources, ["first", "third"]);
+    assert_eq!(report.store_op(surface), Some(StoreOp::Store));
+    assert_eq!(report.store_op(third), Some(StoreOp::Store));
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use common::{TestPass, noop_device};
+use wgpu::{TextureFormat, TextureUsages};
+use wgpu_render_graph::{RenderGraph, ValidationIssue};
+
+fn resolve_issues(graph: &RenderGraph) -> Vec<String> {
+    graph
+        .validate()
+        .into_iter()
+        .filter_map(|issue| match issue {
+            ValidationIssue::InvalidResolveTarget { reason, .. } => Some(reason),
+            _ => None,
+        })
+        .collect()
+}
+
+fn draw(graph: &mut RenderGraph, msaa: wgpu_render_graph::ResourceId) {
+    graph
+        .add_pass(
+            TestPass::new("draw").writing(&["output"]).boxed(),
+            &[("output", msaa)],
+        )
+        .unwrap();
+}
+
+#[test]
+fn relative_sized_targets_are_size_checked() {
+    let mut graph = RenderGraph::new();
+    let resolved = graph
+        .add_color_texture("resolved")
+        .relative_to_output(0.5)
+        .transient();
+    let msaa = graph
+        .add_color_texture("msaa")
+        .relative_to_output(1.0)
+        .sample_count(4)
+        .resolve_to(resolved)
+        .transient();
+    draw(&mut graph, msaa);
+
+    graph.set_output_size(128, 128).unwrap();
+    assert_eq!(resolve_issues(&graph), ["sizes 128x128 and 64x64 differ"]);
+}
+
+#[test]
+fn matching_relative_sizes_resolve_cleanly() {
+    let mut graph = RenderGraph::new();
+    let resolved = graph
+        .add_color_texture("resolved")
+        .relative_to_output(1.0)
+        .transient();
+    let msaa = graph
+        .add_color_texture("msaa")
+        .relative_to(resolved, 1.0)
+        .sample_count(4)
+        .resolve_to(resolved)
+        .transient();
+    draw(&mut graph, msaa);
+
+    graph.set_output_size(96, 64).unwrap();
+    assert!(resolve_issues(&graph).is_empty());
+}
+
+#[test]
+fn bound_external_targets_are_checked() {
+    let (device, _queue) = noop_device();
+    let mut graph = RenderGraph::new();
+    let surface = graph.add_color_texture("surface").external();
+    let msaa = graph
+        .add_color_texture("msaa")
+        .size(64, 64)
+        .format(TextureFormat::Bgra8Unorm)
+        .sample_count(4)
+        .resolve_to(surface)
+        .transient();
+    draw(&mut graph, msaa);
+
+    assert!(resolve_issues(&graph).is_empty());
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("surface"),
+        size: wgpu::Extent3d {
+            width: 32,
+            height: 32,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: TextureFormat::Rgba8Unorm,
+        usage: TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    graph
+        .resources_mut()
+        .set_external_texture_from(surface, &texture, view);
+
+    assert_eq!(
+        resolve_issues(&graph),
+        [
+            "the target lacks RENDER_ATTACHMENT usage, formats Bgra8Unorm and Rgba8Unorm differ, sizes 64x64 and 32x32 differ"
+        ]
+    );
+}
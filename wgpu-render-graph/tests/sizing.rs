@@ -0,0 +1,111 @@
+mod common;
+
+use common::{blit, fill, noop_device};
+use wgpu_render_graph::{BackendEvent, RecordingBackend, RenderGraph, RenderGraphError, SizeMode};
+
+fn created_sizes(backend: &RecordingBackend) -> Vec<(String, u32, u32)> {
+    backend
+        .take_events()
+        .into_iter()
+        .filter_map(|event| match event {
+            BackendEvent::TextureCreated {
+                label,
+                width,
+                height,
+                ..
+            } => Some((label, width, height)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn relative_sizes_follow_the_output_size() {
+    let (device, queue) = noop_device();
+    let mut graph = RenderGraph::new();
+    let half = graph
+        .add_color_texture("half")
+        .relative_to_output(0.5)
+        .transient();
+    let quarter = graph
+        .add_color_texture("quarter")
+        .relative_to(half, 0.5)
+        .transient();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(fill("downsample"), &[("output", half)])
+        .unwrap();
+    graph
+        .add_pass(blit("blur"), &[("input", half), ("output", quarter)])
+        .unwrap();
+    graph
+        .add_pass(blit("present"), &[("input", quarter), ("output", surface)])
+        .unwrap();
+
+    graph.set_output_size(200, 100).unwrap();
+    let backend = RecordingBackend::new(&device);
+    graph.execute(&backend, &queue, &()).unwrap();
+    assert_eq!(
+        created_sizes(&backend),
+        [
+            ("pool_0 (half)".to_string(), 100, 50),
+            ("pool_1 (quarter)".to_string(), 50, 25),
+        ]
+    );
+
+    graph.set_output_size(64, 48).unwrap();
+    graph.execute(&backend, &queue, &()).unwrap();
+    assert_eq!(
+        created_sizes(&backend),
+        [
+            ("pool_0 (half)".to_string(), 32, 24),
+            ("pool_1 (quarter)".to_string(), 16, 12),
+        ]
+    );
+}
+
+#[test]
+fn unchanged_output_size_keeps_allocations() {
+    let (device, queue) = noop_device();
+    let mut graph = RenderGraph::new();
+    let half = graph
+        .add_color_texture("half")
+        .relative_to_output(0.5)
+        .transient();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(fill("downsample"), &[("output", half)])
+        .unwrap();
+    graph
+        .add_pass(blit("present"), &[("input", half), ("output", surface)])
+        .unwrap();
+
+    graph.set_output_size(64, 64).unwrap();
+    let backend = RecordingBackend::new(&device);
+    graph.execute(&backend, &queue, &()).unwrap();
+    assert_eq!(created_sizes(&backend).len(), 1);
+
+    graph.set_output_size(64, 64).unwrap();
+    graph.execute(&backend, &queue, &()).unwrap();
+    assert!(created_sizes(&backend).is_empty());
+}
+
+#[test]
+fn relative_size_cycles_are_rejected() {
+    let mut graph: RenderGraph = RenderGraph::new();
+    let first = graph.add_color_texture("first").size(8, 8).transient();
+    let second = graph
+        .add_color_texture("second")
+        .relative_to(first, 2.0)
+        .transient();
+    graph
+        .resources_mut()
+        .set_size_mode(first, SizeMode::RelativeToResource(second, 0.5));
+
+    assert!(matches!(
+        graph.set_output_size(64, 64),
+        Err(RenderGraphError::InvalidSizeMode { .. })
+    ));
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use common::{TestPass, blit, fill};
+use wgpu::TextureFormat;
+use wgpu_render_graph::{
+    RenderGraph, RenderGraphError, ResourceTemplate, SlotBinding, SlotUsage, SubresourceRange,
+};
+
+fn mip_chain(graph: &mut RenderGraph) -> wgpu_render_graph::ResourceId {
+    graph
+        .add_color_texture("bloom")
+        .size(64, 64)
+        .mip_levels(3)
+        .transient()
+}
+
+fn position(order: &[String], pass: &str) -> usize {
+    order.iter().position(|name| name == pass).unwrap()
+}
+
+#[test]
+fn per_mip_writes_form_a_downsample_chain() {
+    let mut graph = RenderGraph::new();
+    let bloom = mip_chain(&mut graph);
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass_with_bindings(
+            TestPass::new("mip_0").writing(&["output"]).boxed(),
+            &[("output", bloom.subresource(SubresourceRange::mip(0)))],
+        )
+        .unwrap();
+    graph
+        .add_pass_with_bindings(
+            blit("mip_1"),
+            &[
+                ("input", bloom.subresource(SubresourceRange::mip(0))),
+                ("output", bloom.subresource(SubresourceRange::mip(1))),
+            ],
+        )
+        .unwrap();
+    graph
+        .add_pass_with_bindings(
+            blit("present"),
+            &[
+                ("input", bloom.subresource(SubresourceRange::mip(1))),
+                ("output", SlotBinding::from(surface)),
+            ],
+        )
+        .unwrap();
+    graph.compile().unwrap();
+
+    let order = graph.report().unwrap().execution_order;
+    assert!(position(&order, "mip_0") < position(&order, "mip_1"));
+    assert!(position(&order, "mip_1") < position(&order, "present"));
+}
+
+#[test]
+fn attachments_must_cover_a_single_mip() {
+    let mut graph = RenderGraph::new();
+    let bloom = mip_chain(&mut graph);
+
+    let error = graph
+        .add_pass_with_bindings(
+            fill("downsample"),
+            &[("output", bloom.subresource(SubresourceRange::mips(0..2)))],
+        )
+        .unwrap_err();
+    assert!(matches!(error, RenderGraphError::InvalidSubresource { .. }));
+
+    let error = graph
+        .add_pass(fill("whole_chain"), &[("output", bloom)])
+        .unwrap_err();
+    assert!(matches!(error, RenderGraphError::InvalidSubresource { .. }));
+}
+
+#[test]
+fn attachments_must_cover_a_single_layer() {
+    let mut graph = RenderGraph::new();
+    let cube = graph.transient_color_from_template(
+        "cube",
+        &ResourceTemplate::new(TextureFormat::Rgba8Unorm, 32, 32).cube_map(),
+    );
+
+    graph
+        .add_pass_with_bindings(
+            fill("face_0"),
+            &[("output", cube.subresource(SubresourceRange::layer(0)))],
+        )
+        .unwrap();
+
+    let error = graph
+        .add_pass_with_bindings(
+            fill("faces"),
+            &[("output", cube.subresource(SubresourceRange::layers(1..6)))],
+        )
+        .unwrap_err();
+    assert!(matches!(error, RenderGraphError::InvalidSubresource { .. }));
+}
+
+#[test]
+fn storage_writes_may_cover_several_mips() {
+    let mut graph = RenderGraph::new();
+    let bloom = mip_chain(&mut graph);
+
+    graph
+        .add_pass(
+            TestPass::new("compute_mips")
+                .writing(&["output"])
+                .using("output", SlotUsage::Storage)
+                .boxed(),
+            &[("output", bloom)],
+        )
+        .unwrap();
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use common::noop_device;
+use std::sync::{Arc, Mutex};
+use wgpu_render_graph::{
+    BackendEvent, PassExecutionContext, PassNode, PassPrepareContext, RecordingBackend,
+    RenderGraph, RenderGraphError, Result, SubGraphRunCommand, UploadAllocation,
+};
+
+#[derive(Default)]
+struct Uploads {
+    sizes: Vec<usize>,
+    allocations: Vec<UploadAllocation>,
+}
+
+struct UploadPass {
+    uploads: Arc<Mutex<Uploads>>,
+}
+
+impl PassNode for UploadPass {
+    fn name(&self) -> &str {
+        "uploads"
+    }
+
+    fn reads(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    fn writes(&self) -> Vec<&str> {
+        vec!["output"]
+    }
+
+    fn prepare(&mut self, context: &mut PassPrepareContext<'_>) {
+        let mut uploads = self.uploads.lock().unwrap();
+        if uploads.sizes.is_empty() {
+            return;
+        }
+        uploads.allocations = std::mem::take(&mut uploads.sizes)
+            .into_iter()
+            .map(|size| context.upload_bytes(&vec![1; size]))
+            .collect();
+    }
+
+    fn execute<'r, 'e>(
+        &mut self,
+        context: PassExecutionContext<'r, 'e>,
+    ) -> Result<Vec<SubGraphRunCommand<'r>>> {
+        for &allocation in &self.uploads.lock().unwrap().allocations {
+            context.upload_binding(allocation)?;
+        }
+        Ok(context.into_sub_graph_commands())
+    }
+}
+
+fn upload_graph() -> (RenderGraph, Arc<Mutex<Uploads>>) {
+    let uploads = Arc::new(Mutex::new(Uploads::default()));
+    let mut graph = RenderGraph::new();
+    let surface = graph.add_color_texture("surface").external();
+    graph
+        .add_pass(
+            Box::new(UploadPass {
+                uploads: Arc::clone(&uploads),
+            }),
+            &[("output", surface)],
+        )
+        .unwrap();
+    (graph, uploads)
+}
+
+fn upload_buffer_sizes(backend: &RecordingBackend) -> Vec<u64> {
+    backend
+        .take_events()
+        .into_iter()
+        .filter_map(|event| match event {
+            BackendEvent::BufferCreated { label, size, .. } if label == "RenderGraph Uploads" => {
+                Some(size)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn allocations_are_aligned_to_the_uniform_offset_alignment() {
+    let (device, queue) = noop_device();
+    let alignment = device.limits().min_uniform_buffer_offset_alignment;
+    let (mut graph, uploads) = upload_graph();
+    uploads.lock().unwrap().sizes = vec![4, 10, 64];
+
+    graph
+        .execute(&RecordingBackend::new(&device), &queue, &())
+        .unwrap();
+
+    let allocations = uploads.lock().unwrap().allocations.clone();
+    let offsets: Vec<u32> = allocations
+        .iter()
+        .map(|allocation| allocation.offset())
+        .collect();
+    let sizes: Vec<u64> = allocations
+        .iter()
+        .map(|allocation| allocation.size())
+        .collect();
+    assert_eq!(offsets, [0, alignment, 2 * alignment]);
+    assert_eq!(sizes, [4, 12, 64]);
+    assert_eq!(graph.uploaded_bytes(), 2 * alignment as u64 + 64);
+}
+
+#[test]
+fn the_upload_buffer_grows_only_when_a_frame_needs_more() {
+    let (device, queue) = noop_device();
+    let alignment = device.limits().min_uniform_buffer_offset_alignment as u64;
+    let (mut graph, uploads) = upload_graph();
+    let backend = RecordingBackend::new(&device);
+
+    uploads.lock().unwrap().sizes = vec![16];
+    graph.execute(&backend, &queue, &()).unwrap();
+    assert_eq!(upload_buffer_sizes(&backend), [alignment]);
+
+    uploads.lock().unwrap().sizes = vec![16];
+    graph.execute(&backend, &queue, &()).unwrap();
+    assert!(upload_buffer_sizes(&backend).is_empty());
+
+    uploads.lock().unwrap().sizes = vec![16; 3];
+    graph.execute(&backend, &queue, &()).unwrap();
+    assert_eq!(
+        upload_buffer_sizes(&backend),
+        [(2 * alignment + 16).next_power_of_two()]
+    );
+}
+
+#[test]
+fn allocations_from_an_earlier_frame_are_stale() {
+    let (device, queue) = noop_device();
+    let (mut graph, uploads) = upload_graph();
+    let backend = RecordingBackend::new(&device);
+
+    uploads.lock().unwrap().sizes = vec![16];
+    graph.execute(&backend, &queue, &()).unwrap();
+
+    assert!(matches!(
+        graph.execute(&backend, &queue, &()),
+        Err(RenderGraphError::StaleUpload {
+            offset: 0,
+            size: 16
+        })
+    ));
+}
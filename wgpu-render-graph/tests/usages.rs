@@ -0,0 +1,191 @@
+mod common;
+
+use common::{TestPass, blit, fill, noop_device};
+use std::sync::Arc;
+use wgpu::{BufferUsages, TextureUsages};
+use wgpu_render_graph::{RenderGraph, SlotUsage, ValidationIssue};
+
+fn usage_issues(graph: &RenderGraph) -> Vec<ValidationIssue> {
+    graph
+        .validate()
+        .into_iter()
+        .filter(|issue| {
+            matches!(
+                issue,
+                ValidationIssue::TextureUsageMismatch { .. }
+                    | ValidationIssue::BufferUsageMismatch { .. }
+            )
+        })
+        .collect()
+}
+
+fn external_texture(
+    device: &wgpu::Device,
+    usage: TextureUsages,
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: 16,
+            height: 16,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8UnormSrgb,
+        usage,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    (texture, view)
+}
+
+#[test]
+fn sampled_reads_require_texture_binding() {
+    let mut graph = RenderGraph::new();
+    let source = graph
+        .add_color_texture("source")
+        .size(16, 16)
+        .usage(TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC)
+        .transient();
+    let surface = graph.add_color_texture("surface").external();
+
+    graph.add_pass(fill("fill"), &[("output", source)]).unwrap();
+    graph
+        .add_pass(blit("present"), &[("input", source), ("output", surface)])
+        .unwrap();
+
+    assert_eq!(
+        usage_issues(&graph),
+        [ValidationIssue::TextureUsageMismatch {
+            pass: "present".to_string(),
+            slot: "input".to_string(),
+            resource: "source".to_string(),
+            access: "a sampled texture",
+            required: TextureUsages::TEXTURE_BINDING,
+            actual: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+        }]
+    );
+}
+
+#[test]
+fn storage_writes_require_storage_binding() {
+    let mut graph = RenderGraph::new();
+    let target = graph.add_color_texture("target").size(16, 16).transient();
+    let storage = graph
+        .add_color_texture("storage")
+        .size(16, 16)
+        .usage(TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING)
+        .transient();
+    let surface = graph.add_color_texture("surface").external();
+
+    let compute = |name| {
+        TestPass::new(name)
+            .writing(&["output"])
+            .using("output", SlotUsage::Storage)
+            .boxed()
+    };
+    graph
+        .add_pass(compute("into_attachment"), &[("output", target)])
+        .unwrap();
+    graph
+        .add_pass(compute("into_storage"), &[("output", storage)])
+        .unwrap();
+    let combine = TestPass::new("combine")
+        .reading(&["a", "b"])
+        .writing(&["output"])
+        .boxed();
+    graph
+        .add_pass(
+            combine,
+            &[("a", target), ("b", storage), ("output", surface)],
+        )
+        .unwrap();
+
+    let issues = usage_issues(&graph);
+    assert_eq!(issues.len(), 1);
+    assert!(matches!(
+        &issues[0],
+        ValidationIssue::TextureUsageMismatch { pass, required, .. }
+            if pass == "into_attachment" && *required == TextureUsages::STORAGE_BINDING
+    ));
+}
+
+#[test]
+fn bound_external_textures_are_checked() {
+    let (device, _queue) = noop_device();
+    let mut graph = RenderGraph::new();
+    let surface = graph.add_color_texture("surface").external();
+    graph
+        .add_pass(fill("present"), &[("output", surface)])
+        .unwrap();
+
+    let (_, view) = external_texture(&device, TextureUsages::COPY_DST);
+    graph.resources_mut().set_external_texture(surface, view);
+    assert!(usage_issues(&graph).is_empty());
+
+    let (texture, view) = external_texture(&device, TextureUsages::COPY_DST);
+    graph
+        .resources_mut()
+        .set_external_texture_from(surface, &texture, view);
+    assert!(matches!(
+        usage_issues(&graph).as_slice(),
+        [ValidationIssue::TextureUsageMismatch {
+            access: "a render attachment",
+            ..
+        }]
+    ));
+
+    let (texture, view) = external_texture(&device, TextureUsages::RENDER_ATTACHMENT);
+    graph
+        .resources_mut()
+        .set_external_texture_from(surface, &texture, view);
+    assert!(usage_issues(&graph).is_empty());
+}
+
+#[test]
+fn buffer_slots_require_their_declared_usage() {
+    let (device, _queue) = noop_device();
+    let mut graph = RenderGraph::new();
+    let staging = graph
+        .add_buffer("staging")
+        .usage(BufferUsages::COPY_DST | BufferUsages::COPY_SRC)
+        .transient();
+    let indirect = graph.add_buffer("indirect").external();
+
+    let write = TestPass::new("write").writing(&["output"]).boxed();
+    graph.add_pass(write, &[("output", staging)]).unwrap();
+    let copy = TestPass::new("copy")
+        .reading(&["input"])
+        .writing(&["output"])
+        .using("input", SlotUsage::CopySrc)
+        .using("output", SlotUsage::CopyDst)
+        .boxed();
+    graph
+        .add_pass(copy, &[("input", staging), ("output", indirect)])
+        .unwrap();
+
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 256,
+        usage: BufferUsages::INDIRECT,
+        mapped_at_creation: false,
+    });
+    graph
+        .resources_mut()
+        .set_external_buffer(indirect, Arc::new(buffer));
+
+    let issues = usage_issues(&graph);
+    assert_eq!(issues.len(), 2);
+    assert!(issues.iter().any(|issue| matches!(
+        issue,
+        ValidationIssue::BufferUsageMismatch { pass, required, .. }
+            if pass == "write" && *required == BufferUsages::STORAGE
+    )));
+    assert!(issues.iter().any(|issue| matches!(
+        issue,
+        ValidationIssue::BufferUsageMismatch { pass, required, .. }
+            if pass == "copy" && *required == BufferUsages::COPY_DST
+    )));
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use common::{blit, fill, noop_device};
+use wgpu::{TextureFormat, TextureUsages};
+use wgpu_render_graph::{RenderGraph, ResourceId, Severity, ValidationIssue};
+
+fn bind_surface(device: &wgpu::Device, graph: &mut RenderGraph, surface: ResourceId) {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("surface"),
+        size: wgpu::Extent3d {
+            width: 16,
+            height: 16,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: TextureFormat::Rgba8UnormSrgb,
+        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    graph
+        .resources_mut()
+        .set_external_texture_from(surface, &texture, view);
+}
+
+#[test]
+fn reads_of_never_written_transients_are_reported() {
+    let mut graph = RenderGraph::new();
+    let scratch = graph.add_color_texture("scratch").size(16, 16).transient();
+    let surface = graph.add_color_texture("surface").external();
+    graph
+        .add_pass(blit("present"), &[("input", scratch), ("output", surface)])
+        .unwrap();
+
+    assert_eq!(
+        graph.validate(),
+        [
+            ValidationIssue::ReadNeverWritten {
+                pass: "present".to_string(),
+                slot: "input".to_string(),
+                resource: "scratch".to_string(),
+            },
+            ValidationIssue::ExternalNotBound {
+                resource: "surface".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn unread_transients_are_warnings() {
+    let (device, _queue) = noop_device();
+    let mut graph = RenderGraph::new();
+    let hdr = graph.add_color_texture("hdr").size(16, 16).transient();
+    let surface = graph.add_color_texture("surface").external();
+    graph.add_pass(fill("scene"), &[("output", hdr)]).unwrap();
+    graph
+        .add_pass(fill("present"), &[("output", surface)])
+        .unwrap();
+    bind_surface(&device, &mut graph, surface);
+
+    let issues = graph.validate();
+    assert_eq!(
+        issues,
+        [ValidationIssue::TransientNeverRead {
+            resource: "hdr".to_string(),
+        }]
+    );
+    assert_eq!(issues[0].severity(), Severity::Warning);
+    assert!(!issues[0].is_error());
+}
+
+#[test]
+fn only_unbound_externals_are_reported() {
+    let (device, _queue) = noop_device();
+    let mut graph = RenderGraph::new();
+    let overlay = graph.add_color_texture("overlay").external();
+    let surface = graph.add_color_texture("surface").external();
+    graph
+        .add_pass(blit("present"), &[("input", overlay), ("output", surface)])
+        .unwrap();
+    bind_surface(&device, &mut graph, surface);
+
+    let issues = graph.validate();
+    assert_eq!(
+        issues,
+        [ValidationIssue::ExternalNotBound {
+            resource: "overlay".to_string(),
+        }]
+    );
+    assert!(issues[0].is_error());
+}
+
+#[test]
+fn cycles_report_the_whole_chain() {
+    let mut graph = RenderGraph::new();
+    let seed = graph.add_color_texture("seed").size(16, 16).transient();
+    let a = graph.add_color_texture("a").size(16, 16).transient();
+    let b = graph.add_color_texture("b").size(16, 16).transient();
+    let c = graph.add_color_texture("c").size(16, 16).transient();
+
+    graph.add_pass(fill("seed"), &[("output", seed)]).unwrap();
+    graph
+        .add_pass(blit("first"), &[("input", seed), ("output", a)])
+        .unwrap();
+    graph
+        .add_pass(blit("second"), &[("input", a), ("output", b)])
+        .unwrap();
+    graph
+        .add_pass(blit("third"), &[("input", b), ("output", c)])
+        .unwrap();
+    graph
+        .set_pass_slots("first", &[("input", c), ("output", a)])
+        .unwrap();
+
+    let cycles: Vec<_> = graph
+        .validate()
+        .into_iter()
+        .filter(|issue| matches!(issue, ValidationIssue::Cycle { .. }))
+        .collect();
+    assert_eq!(
+        cycles,
+        [ValidationIssue::Cycle {
+            passes: vec![
+                "first".into(),
+                "second".into(),
+                "third".into(),
+                "first".into(),
+            ],
+        }]
+    );
+    assert_eq!(
+        cycles[0].to_string(),
+        "Render graph contains a cycle: first -> second -> third -> first"
+    );
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use common::{TestPass, blit, fill};
+use wgpu_render_graph::{RenderGraph, RenderGraphError, SlotBinding};
+
+#[test]
+fn pass_outputs_report_the_written_version() {
+    let mut graph = RenderGraph::new();
+    let surface = graph.add_color_texture("surface").external();
+
+    let first = graph
+        .add_pass_with_bindings(fill("first"), &[("output", surface.into())])
+        .unwrap();
+    let second = graph
+        .add_pass_with_bindings(fill("second"), &[("output", surface.into())])
+        .unwrap();
+
+    assert_eq!(first.get("output"), Some(surface.version(1)));
+    assert_eq!(second.get("output"), Some(surface.version(2)));
+    assert_eq!(graph.latest_version(surface), surface.version(2));
+}
+
+#[test]
+fn reading_an_unwritten_version_is_rejected() {
+    let mut graph = RenderGraph::new();
+    let input = graph.add_color_texture("input").size(16, 16).transient();
+    let surface = graph.add_color_texture("surface").external();
+
+    let result = graph.add_pass_with_bindings(
+        blit("early"),
+        &[
+            ("input", input.version(1).into()),
+            ("output", surface.into()),
+        ],
+    );
+    assert!(matches!(
+        result,
+        Err(RenderGraphError::VersionNotWritten {
+            version: 1,
+            latest: 0,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn writing_over_a_stale_version_is_rejected() {
+    let mut graph = RenderGraph::new();
+    let surface = graph.add_color_texture("surface").external();
+
+    let first = graph
+        .add_pass_with_bindings(fill("first"), &[("output", surface.into())])
+        .unwrap();
+    graph
+        .add_pass_with_bindings(fill("second"), &[("output", surface.into())])
+        .unwrap();
+
+    let stale = first.get("output").unwrap();
+    let result = graph.add_pass_with_bindings(fill("late"), &[("output", stale.into())]);
+    assert!(matches!(
+        result,
+        Err(RenderGraphError::VersionConflict {
+            expected: 1,
+            latest: 2,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn slots_binding_different_versions_of_one_resource_are_rejected() {
+    let mut graph = RenderGraph::new();
+    let history = graph.add_color_texture("history").size(16, 16).transient();
+    let surface = graph.add_color_texture("surface").external();
+
+    let first = graph
+        .add_pass_with_bindings(fill("first"), &[("output", history.into())])
+        .unwrap();
+    graph
+        .add_pass_with_bindings(fill("second"), &[("output", history.into())])
+        .unwrap();
+
+    let compare = TestPass::new("compare")
+        .reading(&["previous", "current"])
+        .writing(&["output"])
+        .boxed();
+    let bindings: [(&str, SlotBinding); 3] = [
+        ("previous", first.get("output").unwrap().into()),
+        ("current", history.into()),
+        ("output", surface.into()),
+    ];
+    let result = graph.add_pass_with_bindings(compare, &bindings);
+    assert!(matches!(
+        result,
+        Err(RenderGraphError::ConflictingVersions {
+            first: 1,
+            second: 2,
+            ..
+        })
+    ));
+    assert!(graph.pass_index("compare").is_none());
+}
+
+#[test]
+fn readers_of_an_older_version_run_before_its_next_writer() {
+    let mut graph = RenderGraph::new();
+    let shared = graph.add_color_texture("shared").size(16, 16).transient();
+    let early_out = graph.add_color_texture("early_out").external();
+    let surface = graph.add_color_texture("surface").external();
+
+    let first = graph
+        .add_pass_with_bindings(fill("first"), &[("output", shared.into())])
+        .unwrap();
+    graph
+        .add_pass_with_bindings(fill("second"), &[("output", shared.into())])
+        .unwrap();
+    graph
+        .add_pass_with_bindings(
+            blit("late_reader"),
+            &[
+                ("input", first.get("output").unwrap().into()),
+                ("output", early_out.into()),
+            ],
+        )
+        .unwrap();
+    graph
+        .add_pass(blit("present"), &[("input", shared), ("output", surface)])
+        .unwrap();
+    graph.compile().unwrap();
+
+    let order = graph.report().unwrap().execution_order;
+    let position = |pass: &str| order.iter().position(|name| name == pass).unwrap();
+    assert!(position("first") < position("late_reader"));
+    assert!(position("late_reader") < position("second"));
+    assert!(position("second") < position("present"));
+}
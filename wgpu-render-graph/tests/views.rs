@@ -0,0 +1,223 @@
+mod common;
+
+use common::{TestPass, noop_device};
+use std::sync::{Arc, Mutex};
+use wgpu::{TextureAspect, TextureFormat, TextureViewDimension};
+use wgpu_render_graph::{
+    PassExecutionContext, PassNode, RecordingBackend, RenderGraph, RenderGraphError,
+    ResourceTemplate, Result, SlotBinding, SlotUsage, SubGraphRunCommand, SubresourceRange,
+    TextureViewSpec,
+};
+
+type SlotViews = Vec<(String, TextureViewSpec, SubresourceRange)>;
+
+struct ViewProbe {
+    slots: Vec<&'static str>,
+    views: Arc<Mutex<SlotViews>>,
+}
+
+impl PassNode for ViewProbe {
+    fn name(&self) -> &str {
+        "probe"
+    }
+
+    fn reads(&self) -> Vec<&str> {
+        self.slots.clone()
+    }
+
+    fn writes(&self) -> Vec<&str> {
+        vec!["output"]
+    }
+
+    fn slot_usage(&self, slot: &str) -> Option<SlotUsage> {
+        (slot != "output").then_some(SlotUsage::Sampled)
+    }
+
+    fn execute<'r, 'e>(
+        &mut self,
+        context: PassExecutionContext<'r, 'e>,
+    ) -> Result<Vec<SubGraphRunCommand<'r>>> {
+        let mut views = self.views.lock().unwrap();
+        for &slot in &self.slots {
+            context.get_texture_view(slot)?;
+            views.push((
+                slot.to_string(),
+                context.get_view_spec(slot),
+                context.get_subresource(slot),
+            ));
+        }
+        Ok(context.into_sub_graph_commands())
+    }
+}
+
+fn copy_in(name: &'static str) -> Box<dyn PassNode> {
+    TestPass::new(name)
+        .writing(&["output"])
+        .using("output", SlotUsage::CopyDst)
+        .boxed()
+}
+
+#[test]
+fn custom_views_reach_the_pass() {
+    let (device, queue) = noop_device();
+    let mut graph = RenderGraph::new();
+    let color = graph
+        .add_color_texture("color")
+        .format(TextureFormat::Rgba8UnormSrgb)
+        .view_formats(&[TextureFormat::Rgba8Unorm])
+        .size(16, 16)
+        .transient();
+    let depth = graph
+        .add_depth_texture("depth")
+        .format(TextureFormat::Depth24PlusStencil8)
+        .size(16, 16)
+        .transient();
+    let layers = graph.transient_color_from_template(
+        "layers",
+        &ResourceTemplate::new(TextureFormat::Rgba8Unorm, 16, 16)
+            .mip_levels(2)
+            .array_layers(4),
+    );
+    let surface = graph.add_color_texture("surface").external();
+
+    graph
+        .add_pass(copy_in("color_in"), &[("output", color)])
+        .unwrap();
+    graph
+        .add_pass(copy_in("depth_in"), &[("output", depth)])
+        .unwrap();
+    graph
+        .add_pass(copy_in("layers_in"), &[("output", layers)])
+        .unwrap();
+
+    let views = Arc::new(Mutex::new(Vec::new()));
+    let layer = SubresourceRange {
+        base_mip_level: 1,
+        mip_level_count: Some(1),
+        base_array_layer: 2,
+        array_layer_count: Some(1),
+    };
+    graph
+        .add_pass_with_bindings(
+            Box::new(ViewProbe {
+                slots: vec!["linear", "depth", "layer", "array"],
+                views: Arc::clone(&views),
+            }),
+            &[
+                ("linear", color.view_format(TextureFormat::Rgba8Unorm)),
+                ("depth", depth.view(TextureViewSpec::depth_only())),
+                (
+                    "layer",
+                    layers
+                        .subresource(layer)
+                        .view_dimension(TextureViewDimension::D2),
+                ),
+                (
+                    "array",
+                    layers.view_dimension(TextureViewDimension::D2Array),
+                ),
+                ("output", SlotBinding::from(surface)),
+            ],
+        )
+        .unwrap();
+
+    graph
+        .execute(&RecordingBackend::new(&device), &queue, &())
+        .unwrap();
+
+    let views = views.lock().unwrap();
+    assert_eq!(
+        *views,
+        [
+            (
+                "linear".to_string(),
+                TextureViewSpec::new().format(TextureFormat::Rgba8Unorm),
+                SubresourceRange::default(),
+            ),
+            (
+                "depth".to_string(),
+                TextureViewSpec::new().aspect(TextureAspect::DepthOnly),
+                SubresourceRange::default(),
+            ),
+            (
+                "layer".to_string(),
+                TextureViewSpec::new().dimension(TextureViewDimension::D2),
+                layer,
+            ),
+            (
+                "array".to_string(),
+                TextureViewSpec::new().dimension(TextureViewDimension::D2Array),
+                SubresourceRange::default(),
+            ),
+        ]
+    );
+}
+
+fn view_error(binding: impl Fn(&mut RenderGraph) -> SlotBinding) -> String {
+    let mut graph = RenderGraph::new();
+    let binding = binding(&mut graph);
+    let surface = graph.add_color_texture("surface").external();
+    let error = graph
+        .add_pass_with_bindings(
+            Box::new(ViewProbe {
+                slots: vec!["input"],
+                views: Arc::default(),
+            }),
+            &[("input", binding), ("output", SlotBinding::from(surface))],
+        )
+        .unwrap_err();
+
+    assert!(graph.export().passes.is_empty());
+    match error {
+        RenderGraphError::InvalidTextureView { reason, .. } => reason,
+        error => panic!("expected an invalid view, got {:?}", error),
+    }
+}
+
+#[test]
+fn undeclared_view_formats_are_rejected() {
+    let reason = view_error(|graph| {
+        graph
+            .add_color_texture("color")
+            .format(TextureFormat::Rgba8UnormSrgb)
+            .transient()
+            .view_format(TextureFormat::Rgba8Unorm)
+    });
+    assert!(reason.contains("Rgba8Unorm"));
+}
+
+#[test]
+fn missing_aspects_are_rejected() {
+    let reason = view_error(|graph| {
+        graph
+            .add_depth_texture("depth")
+            .format(TextureFormat::Depth32Float)
+            .transient()
+            .view(TextureViewSpec::stencil_only())
+    });
+    assert!(reason.contains("StencilOnly"));
+}
+
+#[test]
+fn dimensions_must_fit_the_bound_layers() {
+    let reason = view_error(|graph| {
+        graph
+            .transient_color_from_template(
+                "layers",
+                &ResourceTemplate::new(TextureFormat::Rgba8Unorm, 16, 16).array_layers(4),
+            )
+            .view_dimension(TextureViewDimension::Cube)
+    });
+    assert!(reason.contains("4 layers"));
+}
+
+#[test]
+fn custom_views_of_external_textures_are_rejected() {
+    let reason = view_error(|graph| {
+        graph
+            .add_color_texture("external")
+            .external()
+            .view_format(TextureFormat::Rgba8Unorm)
+    });
+    assert!(reason.contains("transient"));
+}